@@ -0,0 +1,59 @@
+//! Compares the array backed 19x19 board with the bitboard
+//!
+//! Run with `cargo bench`.
+
+#[macro_use]
+extern crate criterion;
+extern crate rustgo;
+
+use std::hint::black_box;
+
+use criterion::Criterion;
+
+use rustgo::aga::Board19x19;
+use rustgo::go::analysis::playout::{playout, Random};
+use rustgo::go::board::BitBoard19;
+use rustgo::go::{Board, Player};
+
+/// Returns a board after the given number of random plays from the empty board
+fn middle_game<TBoard>(plays: usize) -> TBoard
+    where TBoard: Board
+{
+    let mut board = TBoard::new();
+    let mut random = Random::new(7);
+    let mut player = Player::Black;
+    for _ in 0..plays {
+        let candidates: Vec<_> = board.legal_moves(&player).collect();
+        if candidates.is_empty() {
+            break;
+        }
+        let position = candidates[random.below(candidates.len())];
+        let _ = board.play(&player, &position);
+        player = player.other();
+    }
+    board
+}
+
+/// Benchmarks random playouts and legality checks on the given board type
+fn bench_board<TBoard>(c: &mut Criterion, name: &str)
+    where TBoard: Board
+{
+    let empty = TBoard::new();
+    c.bench_function(&format!("{} playout", name), |b| {
+        let mut random = Random::new(1);
+        b.iter(|| playout(black_box(&empty), &Player::Black, &mut random))
+    });
+
+    let board = middle_game::<TBoard>(150);
+    c.bench_function(&format!("{} legal moves", name), |b| {
+        b.iter(|| black_box(&board).legal_moves(&Player::Black).count())
+    });
+}
+
+fn boards(c: &mut Criterion) {
+    bench_board::<Board19x19>(c, "Board19x19");
+    bench_board::<BitBoard19>(c, "BitBoard19");
+}
+
+criterion_group!(benches, boards);
+criterion_main!(benches);
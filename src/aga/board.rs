@@ -1,100 +1,23 @@
-use go::{Board, Stone};
-use aga::Position19x19;
+use go::{Board, DynBoard, ErasedBoard};
+use go::board::{BoardN, SquareBoard};
 
 /// A default 19x19 go board
-#[derive(Clone, Hash, Eq, PartialEq, Debug)]
-pub struct Board19x19 {
-    state: [[Stone; 19]; 19],
-}
-
-impl Board for Board19x19 {
-    type Position = Position19x19;
-
-    fn new() -> Self {
-        Board19x19 { state: [[Stone::Empty; 19]; 19] }
-    }
-
-    fn on_board(&self, position: &Position19x19) -> bool {
-        position.x < 19 && position.y < 19
-    }
-
-    fn at(&self, position: &Position19x19) -> Stone {
-        self.state[position.y][position.x]
-    }
-
-    fn set(&mut self, position: &Position19x19, stone: &Stone) {
-        self.state[position.y][position.x] = *stone;
-    }
-
-    fn set_handicap(&mut self, stones: u8) {
-        if 2 <= stones && stones <= 9 {
-            // upper right and lower left
-            self.set(&Position19x19 { x: 14, y: 4 }, &Stone::Black);
-            self.set(&Position19x19 { x: 4, y: 14 }, &Stone::Black);
-        }
-        if 3 <= stones && stones <= 9 {
-            // lower right
-            self.set(&Position19x19 { x: 14, y: 14 }, &Stone::Black);
-        }
-        if 4 <= stones && stones <= 9 {
-            // upper left
-            self.set(&Position19x19 { x: 4, y: 4 }, &Stone::Black);
-        }
-        if stones == 5 || stones == 7 || stones == 9 {
-            // middle
-            self.set(&Position19x19 { x: 10, y: 10 }, &Stone::Black);
-        }
-        if 6 <= stones && stones <= 9 {
-            // left side and right side
-            self.set(&Position19x19 { x: 4, y: 10 }, &Stone::Black);
-            self.set(&Position19x19 { x: 14, y: 10 }, &Stone::Black);
-        }
-        if stones == 8 || stones == 9 {
-            // upper side and lower side
-            self.set(&Position19x19 { x: 10, y: 4 }, &Stone::Black);
-            self.set(&Position19x19 { x: 10, y: 14 }, &Stone::Black);
-        }
-    }
-
-    fn positions(&self) -> Vec<Position19x19> {
-        let mut n = Vec::<Position19x19>::new();
-        for x in 0..19 {
-            for y in 0..19 {
-                n.push(Position19x19 { x: x, y: y });
-            }
-        }
-
-        n
-    }
-
-    fn neighbors(&self, position: &Position19x19) -> Vec<Position19x19> {
-        let mut n = Vec::<Position19x19>::new();
-
-        if position.x < 18 {
-            n.push(Position19x19 {
-                x: position.x + 1,
-                y: position.y,
-            });
-        }
-        if position.x > 0 {
-            n.push(Position19x19 {
-                x: position.x - 1,
-                y: position.y,
-            });
-        }
-        if position.y < 18 {
-            n.push(Position19x19 {
-                x: position.x,
-                y: position.y + 1,
-            });
-        }
-        if position.y > 0 {
-            n.push(Position19x19 {
-                x: position.x,
-                y: position.y - 1,
-            });
-        }
-
-        n
+pub type Board19x19 = SquareBoard<19>;
+
+/// A 13x13 go board
+pub type Board13x13 = SquareBoard<13>;
+
+/// A 9x9 go board
+pub type Board9x9 = SquareBoard<9>;
+
+/// Creates an empty board with the given number of lines
+///
+/// Returns None if there is no board of that size.
+pub fn board_with_size(size: usize) -> Option<Box<dyn DynBoard>> {
+    match size {
+        9 => Some(Box::new(ErasedBoard(Board9x9::new()))),
+        13 => Some(Box::new(ErasedBoard(Board13x13::new()))),
+        19 => Some(Box::new(ErasedBoard(Board19x19::new()))),
+        _ => BoardN::with_size(size).map(|b| Box::new(ErasedBoard(b)) as Box<dyn DynBoard>),
     }
 }
@@ -2,6 +2,7 @@ pub mod board;
 pub mod position;
 pub mod rules;
 
-pub use aga::board::Board19x19;
+pub use aga::board::{Board9x9, Board13x13, Board19x19};
 pub use aga::position::Position19x19;
-pub use aga::rules::{Action, GamePhase};
+pub use aga::rules::{Action, ForfeitReason, GamePhase, RejectReason};
+pub use rules::SuicideRule;
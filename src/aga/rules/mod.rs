@@ -1,8 +1,19 @@
 #![allow(dead_code)]
 use std::collections::HashSet;
+use std::fmt;
+use std::time::Duration;
 
-use go::{Player, Board, Stone};
+use go::{Player, PlayerId, Board, Group, GroupCache, PlayError, Stone, ZobristHash};
+use go::zobrist::player_key;
+use go::analysis::DeadStoneEstimator;
+use go::analysis::life_and_death::apply_special_cases;
+use go::score::{scoring_report, CountResult, GameResult, Score, ScoringReport};
 use engine;
+use rules::{KoRule, PassRule, RuleSet, ScoringMethod, SuicideRule};
+use time::{Clock, TimeControl};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[cfg(test)]
 mod test;
@@ -12,6 +23,10 @@ mod test;
 /// Stores a board-layout and the current player. Such a
 /// combination is not allowed to repeat with the same game.
 #[derive(Hash, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde",
+           serde(bound(serialize = "TBoard: Serialize",
+                       deserialize = "TBoard: Deserialize<'de>")))]
 struct KoState<TBoard>
     where TBoard: Board
 {
@@ -25,12 +40,7 @@ impl<TBoard> KoState<TBoard>
     /// Constructs a KoState from a board, position and player
     fn from_move(board: &TBoard, position: &TBoard::Position, player: &Player) -> Self {
         let mut board_copy = board.clone();
-
-        let captured_stones = board_copy.would_be_captured(player, position);
-        board_copy.set(position, &player.stone());
-        for captured_stone in &captured_stones {
-            board_copy.set(captured_stone, &Stone::Empty);
-        }
+        place(&mut board_copy, player, position);
 
         KoState {
             board: board_copy,
@@ -39,85 +49,598 @@ impl<TBoard> KoState<TBoard>
     }
 }
 
+impl<TBoard> KoState<TBoard>
+    where TBoard: ZobristHash
+{
+    /// Returns the zobrist hash of the board and the player to move
+    fn hash(&self) -> u64 {
+        self.board.zobrist_hash() ^ player_key(&self.player)
+    }
+}
+
+/// Places the stone of the player and removes the captured stones
+///
+/// A suicide removes the played stone together with the group it
+/// joined. Returns the removed stones of the opponent.
+fn place<TBoard>(board: &mut TBoard, player: &Player, position: &TBoard::Position)
+                 -> HashSet<TBoard::Position>
+    where TBoard: Board
+{
+    match board.play(player, position) {
+        Ok(info) => info.captured,
+        Err(PlayError::Suicide) => {
+            board.set(position, &player.stone());
+            let group = Group::new(board, position).positions;
+            for stone in &group {
+                board.set(stone, &Stone::Empty);
+            }
+            HashSet::new()
+        }
+        Err(_) => HashSet::new(),
+    }
+}
+
 /// The state of a game as used by the aga rule set
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde",
+           serde(bound(serialize = "TBoard: Serialize, TBoard::Position: Serialize",
+                       deserialize = "TBoard: Deserialize<'de>, \
+                                      TBoard::Position: Deserialize<'de>")))]
 pub struct GameState<TBoard>
-    where TBoard: Board
+    where TBoard: ZobristHash
 {
     /// The current board layout
     board: TBoard,
     /// The current number of plys in the game
     ply: u32,
+    /// The player to make the next ply
+    to_move: Player,
     /// The current game phase
     phase: GamePhase,
     /// The positions currently marked as dead
     dead_stones: Option<Vec<TBoard::Position>>,
-    /// The set of ko states that are not allowed to repeat
-    ko_states: HashSet<KoState<TBoard>>,
+    /// The hashes of the ko states that are not allowed to repeat
+    ko_hashes: HashSet<u64>,
+    /// The ko states themselves, only kept if exact checks are enabled
+    ko_states: Option<HashSet<KoState<TBoard>>>,
+    /// The position of the last play, None after other actions
+    last_move: Option<TBoard::Position>,
+    /// The player who made the last play on the board
+    last_player: Option<Player>,
+    /// The position the next player may not retake a simple ko at
+    ko_point: Option<TBoard::Position>,
+    /// The stones black has captured
+    prisoners_black: usize,
+    /// The stones white has captured
+    prisoners_white: usize,
+    /// The rules the game is played under
+    rules: RuleSet,
+    /// The points of (black, white) before komi, None until counted
+    counted: Option<(usize, usize)>,
+    /// The dead stones removed from the board when the game ended
+    removed_stones: Vec<TBoard::Position>,
+    /// The number of handicap stones black received
+    handicap: u8,
+    /// The persons playing (black, white), None if not assigned
+    colors: Option<(PlayerId, PlayerId)>,
+    /// Whether the last play repeated an earlier position
+    repetition: bool,
+    /// The last dead stones proposed by black
+    proposal_black: Option<Vec<TBoard::Position>>,
+    /// The last dead stones proposed by white
+    proposal_white: Option<Vec<TBoard::Position>>,
+    /// The number of end requests rejected or countered
+    rejections: u32,
+    /// The time left to both players
+    clock: Clock,
+    /// Whether the game is adjourned
+    paused: bool,
+    /// The player who took the button in button go
+    button: Option<Player>,
 }
 
 impl<TBoard> engine::GameState for GameState<TBoard>
-    where TBoard: Board
+    where TBoard: ZobristHash
 {
     fn new() -> Self {
         GameState {
             board: TBoard::new(),
             ply: 0,
+            to_move: Player::Black,
             phase: GamePhase::Running,
             dead_stones: Option::None,
-            ko_states: HashSet::new(),
+            ko_hashes: HashSet::new(),
+            ko_states: Option::None,
+            last_move: Option::None,
+            last_player: Option::None,
+            ko_point: Option::None,
+            prisoners_black: 0,
+            prisoners_white: 0,
+            rules: RuleSet::aga(),
+            counted: Option::None,
+            removed_stones: Vec::new(),
+            handicap: 0,
+            colors: Option::None,
+            repetition: false,
+            proposal_black: Option::None,
+            proposal_white: Option::None,
+            rejections: 0,
+            clock: Clock::new(TimeControl::Unlimited),
+            paused: false,
+            button: Option::None,
         }
     }
 }
 
+impl<TBoard> engine::PositionHash for GameState<TBoard>
+    where TBoard: ZobristHash
+{
+    fn position_hash(&self) -> u64 {
+        self.board.zobrist_hash() ^ player_key(&self.current_player())
+    }
+}
+
 impl<TBoard> GameState<TBoard>
-    where TBoard: Board
+    where TBoard: ZobristHash
 {
     /// Return the current player
     ///
-    /// Since it is not possible to make an odd number of turns
-    /// or to make an action that does not require an response
-    /// from the other player under aga rules, the current player
-    /// is black if the ply-count is even and white otherwise.
-    fn current_player(self: &Self) -> Player {
-        if self.ply % 2 == 0 {
-            Player::Black
-        } else {
-            Player::White
+    /// Black moves first unless another player was set before the
+    /// first ply. Every ply hands the turn to the other player, a
+    /// handicap hands it to white.
+    pub fn current_player(&self) -> Player {
+        self.to_move
+    }
+
+    /// Returns the person playing the given color
+    ///
+    /// Returns None if no colors were assigned.
+    pub fn player_id(&self, player: &Player) -> Option<PlayerId> {
+        self.colors.map(|(black, white)| match *player {
+            Player::Black => black,
+            Player::White => white,
+        })
+    }
+
+    /// Returns true if the last play repeated an earlier position
+    ///
+    /// Compares the whole board and the player to move. Only possible
+    /// under rules without superko, where it reveals long cycles like
+    /// triple ko or eternal life.
+    pub fn is_repetition(&self) -> bool {
+        self.repetition
+    }
+
+    /// Returns the current board layout
+    pub fn board(&self) -> &TBoard {
+        &self.board
+    }
+
+    /// Returns the number of plys made so far
+    ///
+    /// Plays, passes and the handicap count, settings and the end
+    /// negotiation do not.
+    pub fn ply(&self) -> u32 {
+        self.ply
+    }
+
+    /// Returns the stones proposed as dead by an end request
+    ///
+    /// Returns None unless an end request is pending or was accepted.
+    pub fn dead_stones(&self) -> Option<&[TBoard::Position]> {
+        self.dead_stones.as_deref()
+    }
+
+    /// Returns the dead stones last proposed by the player
+    pub fn proposal(&self, player: &Player) -> Option<&[TBoard::Position]> {
+        match *player {
+            Player::Black => self.proposal_black.as_deref(),
+            Player::White => self.proposal_white.as_deref(),
+        }
+    }
+
+    /// Returns the groups both players proposed as dead
+    ///
+    /// A group is agreed on if each of its stones is in the last
+    /// proposals of both players.
+    pub fn agreed_dead_groups(&self) -> Vec<Vec<TBoard::Position>> {
+        let (black, white) = match (self.proposal(&Player::Black),
+                                    self.proposal(&Player::White)) {
+            (Some(black), Some(white)) => (black, white),
+            _ => return Vec::new(),
+        };
+
+        let mut seen = HashSet::new();
+        let mut groups = Vec::new();
+        for position in black {
+            if self.board.at(position) == Stone::Empty || seen.contains(position) {
+                continue;
+            }
+
+            let group: Vec<_> = Group::new(&self.board, position).positions.into_iter().collect();
+            seen.extend(group.iter().cloned());
+            if group.iter().all(|stone| black.contains(stone) && white.contains(stone)) {
+                groups.push(group);
+            }
+        }
+
+        groups
+    }
+
+    /// Returns a proposal of dead stones for an end request
+    ///
+    /// Uses the default `DeadStoneEstimator`: groups alive by Benson's
+    /// algorithm are kept, the others are decided by playouts. Under
+    /// territory scoring the special cases of the Japanese rules, like
+    /// bent four in the corner, overrule the estimate. The result is
+    /// ready to be passed to `Action::RequestEnd`.
+    pub fn suggest_dead_stones(&self) -> Vec<TBoard::Position> {
+        let mut dead = DeadStoneEstimator::default().estimate(&self.board);
+        if self.rules.scoring == ScoringMethod::Territory {
+            apply_special_cases(&self.board, &mut dead);
+        }
+        dead
+    }
+
+    /// Returns how often an end request was rejected or countered
+    ///
+    /// Counts over the whole game, so servers can step in if players
+    /// keep disagreeing.
+    pub fn rejections(&self) -> u32 {
+        self.rejections
+    }
+
+    /// Returns the clocks of both players
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Counts the position by area and by territory
+    ///
+    /// Uses the dead stones under discussion while the game is ending.
+    /// Ended games are reported as counted, their dead stones are
+    /// already removed.
+    pub fn scoring_report(&self) -> ScoringReport {
+        let dead_stones = match self.dead_stones {
+            Some(ref dead_stones) if !self.is_over() => &dead_stones[..],
+            _ => &[],
+        };
+
+        scoring_report(&self.board,
+                       dead_stones,
+                       self.prisoners_black,
+                       self.prisoners_white,
+                       self.komi() + self.handicap_compensation(),
+                       self.komi())
+    }
+
+    /// Returns the player who took the button, None if nobody did yet
+    pub fn button(&self) -> Option<Player> {
+        self.button
+    }
+
+    /// Checks if the game is adjourned
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns the current game phase
+    pub fn phase(&self) -> &GamePhase {
+        &self.phase
+    }
+
+    /// Returns the rules the game is played under
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Returns the points white receives as compensation for moving second
+    pub fn komi(&self) -> f64 {
+        self.rules.komi
+    }
+
+    /// Returns the number of handicap stones black received
+    pub fn handicap(&self) -> u8 {
+        self.handicap
+    }
+
+    /// Returns the points white receives for the handicap stones
+    ///
+    /// Under area counting every handicap stone beyond the first adds
+    /// a point to black's area, which white is compensated for.
+    pub fn handicap_compensation(&self) -> f64 {
+        match self.rules.scoring {
+            ScoringMethod::Area => self.handicap.saturating_sub(1) as f64,
+            ScoringMethod::Territory => 0.0,
+        }
+    }
+
+    /// Returns true if the game ended, by counting or otherwise
+    pub fn is_over(&self) -> bool {
+        self.result().is_some()
+    }
+
+    /// Returns the result of the game, None while it is not over
+    pub fn result(&self) -> Option<GameResult> {
+        match self.phase {
+            GamePhase::Ended(result) => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Returns the lead of black including komi
+    ///
+    /// Returns None until the end of the game was accepted or a player
+    /// resigned. Forfeited games have no score.
+    pub fn score(&self) -> Option<Score> {
+        self.result().and_then(|result| result.score())
+    }
+
+    /// Returns the points of (black, white) before komi
+    ///
+    /// Returns None unless the game ended by counting.
+    pub fn counted(&self) -> Option<(usize, usize)> {
+        self.counted
+    }
+
+    /// Returns the number of stones the player holds as prisoners
+    ///
+    /// Counts captured stones, pass stones and dead stones removed at
+    /// the end of the game.
+    pub fn prisoners(&self, player: &Player) -> usize {
+        match *player {
+            Player::Black => self.prisoners_black,
+            Player::White => self.prisoners_white,
+        }
+    }
+
+    /// Returns the dead stones removed from the board at the end of the game
+    ///
+    /// Empty until the end of the game was accepted.
+    pub fn removed_stones(&self) -> &[TBoard::Position] {
+        &self.removed_stones
+    }
+
+    /// Returns the position of the last play
+    ///
+    /// Returns None if the last action was no play, e.g. a pass.
+    pub fn last_move(&self) -> Option<&TBoard::Position> {
+        self.last_move.as_ref()
+    }
+
+    /// Returns the point of a simple ko the current player may not retake
+    ///
+    /// Set when the last play captured a single stone with a single
+    /// stone that is left with that one liberty. Longer cycles are only
+    /// caught by the superko check.
+    pub fn ko_point(&self) -> Option<&TBoard::Position> {
+        self.ko_point.as_ref()
+    }
+
+    /// Returns true if the player may play at the position as far as
+    /// suicide is concerned
+    fn suicide_allowed(&self, position: &TBoard::Position, player: &Player) -> bool {
+        !self.board.would_be_suicide(position, player) || self.suicide_rule_allows(position, player)
+    }
+
+    /// Checks if the suicide rule allows a play that is suicide
+    fn suicide_rule_allows(&self, position: &TBoard::Position, player: &Player) -> bool {
+        match self.rules.suicide {
+            SuicideRule::Forbidden => false,
+            SuicideRule::MultiStone => {
+                let mut friendly = false;
+                self.board.for_each_neighbor(position,
+                                             |n| friendly |= self.board.at(&n) == player.stone());
+                friendly
+            }
+        }
+    }
+
+    /// Returns all legal game actions
+    ///
+    /// Yields the plays and the pass of the current player, requests
+    /// without dead stones, the answers to an end request, resuming,
+    /// declaring a long cycle void and resigning. Actions configuring the game,
+    /// handicap and forfeits are left out.
+    pub fn legal_actions(&self) -> Vec<Action<TBoard>> {
+        let player = self.current_player();
+        let mut actions = Vec::new();
+
+        let playing = playing_phase(self).is_ok() && my_turn(self, &player).is_ok() &&
+                      in_time(self, &player, &None).is_ok();
+        if playing {
+            // the groups are flood-filled once for all suicide checks
+            let groups = GroupCache::new(&self.board);
+            actions.extend(self.board
+                .iter_positions()
+                .filter(|position| {
+                    self.board.at(position) == Stone::Empty &&
+                    (!groups.would_be_suicide(position, &player) ||
+                     self.suicide_rule_allows(position, &player)) &&
+                    !self.would_be_ko(position, &player)
+                })
+                .map(|at| {
+                    Action::Play {
+                        player,
+                        at,
+                        time_used: None,
+                    }
+                }));
+        }
+
+        let mut candidates = vec![Action::Pass {
+                                      player,
+                                      time_used: None,
+                                  }];
+        for &player in &[Player::Black, Player::White] {
+            candidates.push(Action::RequestEnd {
+                player,
+                dead_stones: Vec::new(),
+            });
+            candidates.push(Action::RejectEnd { player });
+            candidates.push(Action::AcceptEnd { player });
+            candidates.push(Action::Resume { player });
+            candidates.push(Action::DeclareNoResult { player });
+        }
+        candidates.push(Action::Resign { player });
+
+        actions.extend(candidates.into_iter().filter(|action| action.validate(self).is_ok()));
+        actions
+    }
+
+    /// Continues the game after both players passed
+    ///
+    /// The opponent of the player who made the last play on the board
+    /// moves next. The ko states are kept.
+    fn resume(&mut self) {
+        self.phase = GamePhase::Running;
+        self.dead_stones = Option::None;
+        if let Some(player) = self.last_player {
+            self.to_move = player.other();
+        }
+    }
+
+    /// Remembers the dead stones the player proposed
+    fn propose(&mut self, player: &Player, dead_stones: &[TBoard::Position]) {
+        match *player {
+            Player::Black => self.proposal_black = Some(dead_stones.to_vec()),
+            Player::White => self.proposal_white = Some(dead_stones.to_vec()),
         }
     }
 
     /// Register the current game state as a ko state
-    fn register_ko_state(self: &mut Self) {
+    ///
+    /// Returns false if the state was registered before.
+    fn register_ko_state(&mut self) -> bool {
         let state = KoState {
             board: self.board.clone(),
             player: self.current_player(),
         };
 
-        self.ko_states.insert(state);
+        let new_hash = self.ko_hashes.insert(state.hash());
+        match self.ko_states {
+            Some(ref mut states) => states.insert(state),
+            None => new_hash,
+        }
+    }
+
+    /// Check if the ko state was registered before
+    ///
+    /// Trusts the hashes unless exact checks are enabled, in which case
+    /// a matching hash is confirmed by comparing the boards.
+    fn is_registered(&self, state: &KoState<TBoard>) -> bool {
+        if !self.ko_hashes.contains(&state.hash()) {
+            return false;
+        }
+
+        match self.ko_states {
+            Some(ref states) => states.contains(state),
+            None => true,
+        }
     }
 
     /// Check if a ply at position by player would result in ko
-    fn would_be_ko(self: &Self, position: &TBoard::Position, player: &Player) -> bool {
-        self.ko_states.contains(&KoState::from_move(&self.board, position, player))
+    fn would_be_ko(&self, position: &TBoard::Position, player: &Player) -> bool {
+        match self.rules.ko {
+            KoRule::Simple => self.ko_point.as_ref() == Some(position),
+            KoRule::SituationalSuperko => {
+                self.is_registered(&KoState::from_move(&self.board, position, player))
+            }
+            KoRule::PositionalSuperko => {
+                let mut state = KoState::from_move(&self.board, position, player);
+                if self.is_registered(&state) {
+                    return true;
+                }
+
+                state.player = state.player.other();
+                self.is_registered(&state)
+            }
+        }
     }
 }
 
 /// Possible actions in a game
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde",
+           serde(bound(serialize = "TBoard::Position: Serialize",
+                       deserialize = "TBoard::Position: Deserialize<'de>")))]
 pub enum Action<TBoard>
-    where TBoard: Board
+    where TBoard: ZobristHash
 {
+    /// Sets the rules the game is played under
+    ///
+    /// Allowed before the 1st ply, does not count as a ply.
+    SetRules { rules: RuleSet },
+
+    /// Sets whether suicide is legal
+    ///
+    /// Allowed before the 1st ply, does not count as a ply.
+    SetSuicideRule { rule: SuicideRule },
+
+    /// Sets the points white receives as compensation for moving second
+    ///
+    /// Allowed before the 1st ply, does not count as a ply.
+    SetKomi { komi: f64 },
+
+    /// Sets whether superko checks compare whole boards
+    ///
+    /// By default only 64 bit hashes of the positions are kept, which
+    /// may collide. Exact checks keep a copy of every position instead.
+    /// Allowed before the 1st ply, does not count as a ply.
+    SetExactKoCheck { exact: bool },
+
+    /// Sets the time control the game is played under
+    ///
+    /// Allowed before the 1st ply, does not count as a ply.
+    SetTimeControl { control: TimeControl },
+
+    /// Sets the player to make the first ply
+    ///
+    /// Allowed before the 1st ply, does not count as a ply.
+    SetPlayerToMove { player: Player },
+
+    /// Assigns the persons playing black and white, e.g. after nigiri
+    ///
+    /// Allowed before the 1st ply for two different persons, does not
+    /// count as a ply.
+    AssignColors { black: PlayerId, white: PlayerId },
+
+    /// Composes a position, like the SGF properties AB, AW, AE and PL
+    ///
+    /// Empties the positions in clear, then adds the black and white
+    /// stones and sets the player to move if given. Allowed before the
+    /// 1st ply, does not count as a ply.
+    Setup {
+        add_black: Vec<TBoard::Position>,
+        add_white: Vec<TBoard::Position>,
+        clear: Vec<TBoard::Position>,
+        to_move: Option<Player>,
+    },
+
     /// Sets handicap stones.
     ///
-    /// Allowed as 1st ply, stones is the number of stones to set
+    /// Allowed as 1st ply, stones is the number of stones to set. At
+    /// least two stones, at most `Board::max_handicap`.
     Handicap { stones: u8 },
 
     /// The given player passes
-    Pass { player: Player },
+    ///
+    /// The time used, if given, is charged to the player's clock. In
+    /// button go the first pass takes the button.
+    Pass {
+        player: Player,
+        time_used: Option<Duration>,
+    },
 
     /// The given player plays at the given position
+    ///
+    /// The time used, if given, is charged to the player's clock.
     Play {
         player: Player,
         at: TBoard::Position,
+        time_used: Option<Duration>,
     },
 
     /// The given player requests the game to end
@@ -131,12 +654,82 @@ pub enum Action<TBoard>
     /// The given player rejects the request to end the game
     RejectEnd { player: Player },
 
+    /// The given player answers a request to end the game with own
+    /// dead stones
+    ///
+    /// Counts as rejection, the other player has to accept or reject
+    /// the counter proposal in turn.
+    CounterProposeEnd {
+        player: Player,
+        dead_stones: Vec<TBoard::Position>,
+    },
+
     /// The given player accepts the request to end the game
     AcceptEnd { player: Player },
+
+    /// The given player wants to continue playing after both passed
+    ///
+    /// Play resumes like after a rejected end request.
+    Resume { player: Player },
+
+    /// The given player declares the game void
+    ///
+    /// Allowed if the last play repeated an earlier position.
+    DeclareNoResult { player: Player },
+
+    /// The given player adjourns the game
+    ///
+    /// Freezes the clocks and blocks all actions but resigning and
+    /// forfeiting until the game is unpaused. Allowed at any time
+    /// before the game ended, does not count as a ply.
+    Pause { player: Player },
+
+    /// The given player continues the adjourned game
+    ///
+    /// Allowed while the game is paused, does not count as a ply.
+    Unpause { player: Player },
+
+    /// The given player spent the given time on the current turn
+    ///
+    /// Charged to the player's clock, running out of time loses the
+    /// game. Allowed at any time before the game ended, does not count
+    /// as a ply.
+    SpendTime {
+        player: Player,
+        elapsed: Duration,
+    },
+
+    /// The given player resigns
+    ///
+    /// Allowed at any time before the game ended, also when it is not
+    /// the player's turn.
+    Resign { player: Player },
+
+    /// The given player forfeits the game for the given reason
+    ///
+    /// Ends the game administratively, e.g. by a server or referee.
+    /// Allowed at any time before the game ended.
+    Forfeit {
+        player: Player,
+        reason: ForfeitReason,
+    },
+}
+
+/// The reasons a player can forfeit a game
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ForfeitReason {
+    /// The player ran out of time
+    Timeout,
+    /// The player left the game and did not return
+    Disconnection,
+    /// The player was disqualified
+    Disqualification,
 }
 
 /// The set of possible game phases
-#[derive(PartialEq)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GamePhase {
     /// Tha game is running.
     ///
@@ -149,6 +742,12 @@ pub enum GamePhase {
     /// If white passes next, the game's state transitions to Ending.
     BlackPassed,
 
+    /// White has passed
+    ///
+    /// Only reached if any two passes in a row end the game. If black
+    /// passes next, the game's state transitions to Ending.
+    WhitePassed,
+
     /// The game is ending.
     ///
     /// White has passed after black passed. It is time to specify
@@ -157,120 +756,552 @@ pub enum GamePhase {
 
     /// The stored player has requested to end the game.
     ///
-    /// The other player has to accept or reject the request.
+    /// The other player has to accept or reject the request. A
+    /// rejection resumes the game with the opponent of the player who
+    /// made the last play on the board.
     EndRequested(Player),
 
     /// The game ended
     ///
-    /// The game ended with (black_score, white_score).
-    Ended(usize, usize),
+    /// The game ended with the stored result. Counted results already
+    /// include komi and the handicap compensation.
+    Ended(GameResult),
 }
 
-impl<TBoard> engine::Action for Action<TBoard>
-    where TBoard: Board
+/// The reasons an action can be rejected
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum RejectReason {
+    /// The action is only allowed before the first ply
+    GameStarted,
+    /// The game is over
+    GameOver,
+    /// The action is not allowed in the current game phase
+    WrongPhase,
+    /// It is the other player's turn
+    NotYourTurn,
+    /// The position is not on the board
+    OffBoard,
+    /// There is already a stone at the position
+    Occupied,
+    /// There is no stone at the position
+    NoStone,
+    /// The play would leave its own group without liberties
+    Suicide,
+    /// The play would repeat a position forbidden by the ko rule
+    Ko,
+    /// Both colors would be assigned to the same person
+    SamePerson,
+    /// The last play did not repeat an earlier position
+    NoRepetition,
+    /// The player ran out of time
+    OutOfTime,
+    /// The board has no fixed placement for that many handicap stones
+    InvalidHandicap,
+    /// The game is adjourned
+    Paused,
+    /// The game is not adjourned
+    NotPaused,
+    /// The rule set does not allow changing the rules
+    FixedRules,
+}
+
+impl<TBoard> fmt::Display for Action<TBoard>
+    where TBoard: ZobristHash,
+          TBoard::Position: fmt::Display
 {
-    type GameState = GameState<TBoard>;
+    /// Writes a short description like "B (3, 3)", "W pass" or "komi 6.5"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letter = |player: &Player| match *player {
+            Player::Black => "B",
+            Player::White => "W",
+        };
 
-    fn test(self: &Self, state: &Self::GameState) -> bool {
         match *self {
-            // Handicap stones are only allowed as the first ply.
-            Action::Handicap { stones: _stones } => state.ply == 0,
+            Action::SetRules { ref rules } => write!(f, "rules {:?}", rules.scoring),
+            Action::SetSuicideRule { ref rule } => write!(f, "suicide {:?}", rule),
+            Action::SetKomi { komi } => write!(f, "komi {}", komi),
+            Action::SetExactKoCheck { exact } => write!(f, "exact ko check {}", exact),
+            Action::SetTimeControl { ref control } => write!(f, "time {:?}", control),
+            Action::SetPlayerToMove { ref player } => write!(f, "{} to move", letter(player)),
+            Action::AssignColors { ref black, ref white } => {
+                write!(f, "B is {}, W is {}", black.0, white.0)
+            }
+            Action::Setup { ref add_black, ref add_white, ref clear, .. } => {
+                write!(f,
+                       "setup +{} B, +{} W, -{}",
+                       add_black.len(),
+                       add_white.len(),
+                       clear.len())
+            }
+            Action::Handicap { stones } => write!(f, "handicap {}", stones),
+            Action::Pass { ref player, .. } => write!(f, "{} pass", letter(player)),
+            Action::Play { ref player, ref at, .. } => write!(f, "{} {}", letter(player), at),
+            Action::RequestEnd { ref player, ref dead_stones } => {
+                write!(f, "{} request end, {} dead", letter(player), dead_stones.len())
+            }
+            Action::RejectEnd { ref player } => write!(f, "{} reject end", letter(player)),
+            Action::CounterProposeEnd { ref player, ref dead_stones } => {
+                write!(f, "{} counter end, {} dead", letter(player), dead_stones.len())
+            }
+            Action::AcceptEnd { ref player } => write!(f, "{} accept end", letter(player)),
+            Action::Resume { ref player } => write!(f, "{} resume", letter(player)),
+            Action::DeclareNoResult { ref player } => write!(f, "{} no result", letter(player)),
+            Action::Pause { ref player } => write!(f, "{} pause", letter(player)),
+            Action::Unpause { ref player } => write!(f, "{} unpause", letter(player)),
+            Action::SpendTime { ref player, elapsed } => {
+                write!(f, "{} spend {}s", letter(player), elapsed.as_secs())
+            }
+            Action::Resign { ref player } => write!(f, "{} resign", letter(player)),
+            Action::Forfeit { ref player, reason } => {
+                write!(f, "{} forfeit {:?}", letter(player), reason)
+            }
+        }
+    }
+}
 
-            // Passing is for the current player allowed if the game is
-            // still running or black just passed (in which case the game
-            // finishes).
-            Action::Pass { ref player } => {
-                let normal_pass = state.phase == GamePhase::Running;
-                let finishing_pass = state.phase == GamePhase::BlackPassed;
-                let my_turn = *player == state.current_player();
+impl<TBoard> Action<TBoard>
+    where TBoard: ZobristHash
+{
+    /// Checks if the action is applicable to the given state
+    ///
+    /// Returns the reason the action is rejected otherwise.
+    pub fn validate(&self, state: &GameState<TBoard>) -> Result<(), RejectReason> {
+        // Only the end of the game can be changed after it is over.
+        if state.is_over() {
+            return Err(RejectReason::GameOver);
+        }
+
+        // An adjourned game can only be continued or given up.
+        if state.paused {
+            match *self {
+                Action::Unpause { .. } |
+                Action::Resign { .. } |
+                Action::Forfeit { .. } => {}
+                _ => return Err(RejectReason::Paused),
+            }
+        }
+
+        match *self {
+            // The rules may only be changed before the game starts.
+            Action::SetRules { .. } |
+            Action::SetSuicideRule { .. } |
+            Action::SetKomi { .. } |
+            Action::SetExactKoCheck { .. } |
+            Action::SetTimeControl { .. } |
+            Action::SetPlayerToMove { .. } => before_first_ply(state),
+            Action::AssignColors { black, white } => {
+                before_first_ply(state)?;
+                if black == white {
+                    return Err(RejectReason::SamePerson);
+                }
+                Ok(())
+            }
+
+            // Setup stones have to be on the board.
+            Action::Setup { ref add_black, ref add_white, ref clear, to_move: _to_move } => {
+                before_first_ply(state)?;
+                let on_board = add_black.iter()
+                    .chain(add_white.iter())
+                    .chain(clear.iter())
+                    .all(|pos| state.board.on_board(pos));
+                if !on_board {
+                    return Err(RejectReason::OffBoard);
+                }
+                Ok(())
+            }
+
+            // Handicap stones are only allowed as the first ply and in
+            // the amounts the board has fixed placements for.
+            Action::Handicap { stones } => {
+                before_first_ply(state)?;
+                if stones < 2 || stones > state.board.max_handicap() {
+                    return Err(RejectReason::InvalidHandicap);
+                }
+                Ok(())
+            }
 
-                (normal_pass || finishing_pass) && my_turn
+            // Passing is for the current player allowed if the game is
+            // still running or the other player just passed (in which case
+            // the game may finish).
+            Action::Pass { ref player, ref time_used } => {
+                playing_phase(state)?;
+                my_turn(state, player)?;
+                in_time(state, player, time_used)
             }
 
             // A play is only allowed on the board (doh!) and at an empty
             // intersection if it is my turn and neither suicide nor ko.
-            Action::Play { ref player, at: ref position } => {
-                let valid_position = state.board.on_board(position) &&
-                                     state.board.at(&position) == Stone::Empty;
-                let valid_move = !state.board.would_be_suicide(position, player) &&
-                                 !state.would_be_ko(position, player);
-                let valid_phase = state.phase == GamePhase::Running ||
-                                  state.phase == GamePhase::BlackPassed;
-                let my_turn = *player == state.current_player();
-
-                valid_position && valid_move && valid_phase && my_turn
+            Action::Play { ref player, at: ref position, ref time_used } => {
+                playing_phase(state)?;
+                my_turn(state, player)?;
+                in_time(state, player, time_used)?;
+                if !state.board.on_board(position) {
+                    return Err(RejectReason::OffBoard);
+                }
+                if state.board.at(position) != Stone::Empty {
+                    return Err(RejectReason::Occupied);
+                }
+                if !state.suicide_allowed(position, player) {
+                    return Err(RejectReason::Suicide);
+                }
+                if state.would_be_ko(position, player) {
+                    return Err(RejectReason::Ko);
+                }
+                Ok(())
             }
 
             // Requesting the end of the game is allowed if both players
             // passed (i.e. the game is ending).
             Action::RequestEnd { player: ref _player, ref dead_stones } => {
-                let valid_phase = state.phase == GamePhase::Ending;
-                let valid_dead_stones = dead_stones.iter()
-                    .all(|pos| state.board.at(pos) != Stone::Empty && state.board.on_board(pos));
+                if state.phase != GamePhase::Ending {
+                    return Err(RejectReason::WrongPhase);
+                }
+                valid_dead_stones(state, dead_stones)
+            }
 
-                valid_phase && valid_dead_stones
+            // Rejecting, countering or accepting the end of the game is
+            // allowed if the other player requested ending the game.
+            Action::RejectEnd { ref player } |
+            Action::AcceptEnd { ref player } => answering_end_request(state, player),
+            Action::CounterProposeEnd { ref player, ref dead_stones } => {
+                answering_end_request(state, player)?;
+                valid_dead_stones(state, dead_stones)
             }
 
-            // Rejecting the end of the game is allowed if the other player
-            // requested ending the game.
-            Action::RejectEnd { ref player } => {
-                if let GamePhase::EndRequested(ref requesting_player) = state.phase {
-                    requesting_player != player
-                } else {
-                    false
+            // Either player may resume the game instead of requesting the
+            // end.
+            Action::Resume { player: _player } => {
+                if state.phase != GamePhase::Ending {
+                    return Err(RejectReason::WrongPhase);
                 }
+                Ok(())
             }
 
-            // Accepting the end of the game is allowed if the other player
-            // requested ending the game.
-            Action::AcceptEnd { ref player } => {
-                if let GamePhase::EndRequested(ref requesting_player) = state.phase {
-                    requesting_player != player
-                } else {
-                    false
+            // A long cycle may end the game without result.
+            Action::DeclareNoResult { player: _player } => {
+                if !state.repetition {
+                    return Err(RejectReason::NoRepetition);
                 }
+                Ok(())
             }
+
+            Action::Unpause { .. } => {
+                if !state.paused {
+                    return Err(RejectReason::NotPaused);
+                }
+                Ok(())
+            }
+
+            // Resigning, forfeiting, pausing and spending time is allowed
+            // as long as the game is not over.
+            Action::Pause { .. } |
+            Action::SpendTime { .. } |
+            Action::Resign { .. } |
+            Action::Forfeit { .. } => Ok(()),
+        }
+    }
+}
+
+/// Rejects actions after the first ply
+fn before_first_ply<TBoard>(state: &GameState<TBoard>) -> Result<(), RejectReason>
+    where TBoard: ZobristHash
+{
+    if state.ply != 0 {
+        return Err(RejectReason::GameStarted);
+    }
+    Ok(())
+}
+
+/// Rejects plays and passes once both players passed
+fn playing_phase<TBoard>(state: &GameState<TBoard>) -> Result<(), RejectReason>
+    where TBoard: ZobristHash
+{
+    match state.phase {
+        GamePhase::Running | GamePhase::BlackPassed | GamePhase::WhitePassed => Ok(()),
+        _ => Err(RejectReason::WrongPhase),
+    }
+}
+
+/// Rejects answers to end requests not made by the other player
+fn answering_end_request<TBoard>(state: &GameState<TBoard>,
+                                 player: &Player)
+                                 -> Result<(), RejectReason>
+    where TBoard: ZobristHash
+{
+    match state.phase {
+        GamePhase::EndRequested(ref requesting) if requesting != player => Ok(()),
+        GamePhase::EndRequested(_) => Err(RejectReason::NotYourTurn),
+        _ => Err(RejectReason::WrongPhase),
+    }
+}
+
+/// Rejects dead stones off the board or at empty intersections
+fn valid_dead_stones<TBoard>(state: &GameState<TBoard>,
+                             dead_stones: &[TBoard::Position])
+                             -> Result<(), RejectReason>
+    where TBoard: ZobristHash
+{
+    for pos in dead_stones {
+        if !state.board.on_board(pos) {
+            return Err(RejectReason::OffBoard);
         }
+        if state.board.at(pos) == Stone::Empty {
+            return Err(RejectReason::NoStone);
+        }
+    }
+    Ok(())
+}
+
+/// Rejects actions of the player not to move
+fn my_turn<TBoard>(state: &GameState<TBoard>, player: &Player) -> Result<(), RejectReason>
+    where TBoard: ZobristHash
+{
+    if *player != state.current_player() {
+        return Err(RejectReason::NotYourTurn);
     }
+    Ok(())
+}
+
+/// Rejects moves of a flagged player or moves that took too long
+fn in_time<TBoard>(state: &GameState<TBoard>,
+                   player: &Player,
+                   time_used: &Option<Duration>)
+                   -> Result<(), RejectReason>
+    where TBoard: ZobristHash
+{
+    let mut clock = state.clock;
+    let flagged = match *time_used {
+        Some(elapsed) => clock.spend(player, elapsed),
+        None => clock.is_expired(player),
+    };
+    if flagged {
+        return Err(RejectReason::OutOfTime);
+    }
+    Ok(())
+}
 
-    fn execute(self: &Self, state: &mut Self::GameState) {
+impl<TBoard> engine::Action for Action<TBoard>
+    where TBoard: ZobristHash
+{
+    type GameState = GameState<TBoard>;
+    type Reason = RejectReason;
+    type Position = TBoard::Position;
+
+    fn check(&self, state: &Self::GameState) -> Result<(), RejectReason> {
+        self.validate(state)
+    }
+
+    fn execute(&self, state: &mut Self::GameState) {
         match self {
+            &Action::SetRules { rules } => {
+                state.rules = rules;
+            }
+            &Action::SetSuicideRule { rule } => {
+                state.rules.suicide = rule;
+            }
+            &Action::SetKomi { komi } => {
+                state.rules.komi = komi;
+            }
+            &Action::SetExactKoCheck { exact } => {
+                state.ko_states = if exact { Some(HashSet::new()) } else { None };
+            }
+            &Action::SetTimeControl { control } => {
+                state.clock = Clock::new(control);
+            }
+            &Action::SetPlayerToMove { player } => {
+                state.to_move = player;
+            }
+            &Action::AssignColors { black, white } => {
+                state.colors = Some((black, white));
+            }
+            Action::Setup { add_black, add_white, clear, to_move } => {
+                for position in clear {
+                    state.board.set(position, &Stone::Empty);
+                }
+                for position in add_black {
+                    state.board.set(position, &Stone::Black);
+                }
+                for position in add_white {
+                    state.board.set(position, &Stone::White);
+                }
+                if let Some(player) = *to_move {
+                    state.to_move = player;
+                }
+            }
             &Action::Handicap { stones } => {
                 state.board.set_handicap(stones);
+                state.handicap = stones;
+                state.last_move = None;
+                state.ko_point = None;
                 state.ply += 1;
+                state.to_move = Player::White;
+                state.repetition = false;
                 state.register_ko_state();
             }
-            &Action::Pass { ref player } => {
-                if *player == Player::Black {
-                    state.phase = GamePhase::BlackPassed;
-                } else if *player == Player::White && state.phase == GamePhase::BlackPassed {
-                    state.phase = GamePhase::Ending;
+            Action::Pass { player, time_used } => {
+                if let Some(elapsed) = *time_used {
+                    state.clock.spend(player, elapsed);
+                }
+                state.phase = match (*player, &state.phase, state.rules.passes) {
+                    (Player::White, &GamePhase::BlackPassed, _) |
+                    (Player::Black, &GamePhase::WhitePassed, _) => GamePhase::Ending,
+                    (Player::Black, _, _) => GamePhase::BlackPassed,
+                    (Player::White, _, PassRule::TwoConsecutive) => GamePhase::WhitePassed,
+                    (Player::White, _, PassRule::BlackThenWhite) => GamePhase::Running,
+                };
+                if state.rules.button && state.button.is_none() {
+                    state.button = Some(*player);
                 }
+                if state.rules.pass_stones {
+                    match *player {
+                        Player::Black => state.prisoners_white += 1,
+                        Player::White => state.prisoners_black += 1,
+                    }
+                }
+                state.last_move = None;
+                state.ko_point = None;
                 state.ply += 1;
+                state.to_move = state.to_move.other();
+                state.repetition = false;
                 state.register_ko_state();
             }
-            &Action::Play { ref player, at: ref position } => {
-                let captured_stones = state.board.would_be_captured(player, position);
-                state.board.set(position, &player.stone());
-                for captured_stone in &captured_stones {
-                    state.board.set(captured_stone, &Stone::Empty);
+            Action::Play { player, at: position, time_used } => {
+                if let Some(elapsed) = *time_used {
+                    state.clock.spend(player, elapsed);
+                }
+                let captured = place(&mut state.board, player, position);
+                match *player {
+                    Player::Black => state.prisoners_black += captured.len(),
+                    Player::White => state.prisoners_white += captured.len(),
                 }
+                let group = Group::new(&state.board, position);
+                state.ko_point = if captured.len() == 1 && group.positions.len() == 1 &&
+                                    group.is_in_atari() {
+                    captured.into_iter().next()
+                } else {
+                    None
+                };
+                state.last_move = Some(*position);
+                state.last_player = Some(*player);
                 state.ply += 1;
+                state.to_move = state.to_move.other();
                 state.phase = GamePhase::Running;
-                state.register_ko_state();
+                state.repetition = !state.register_ko_state();
+            }
+            Action::RequestEnd { player, dead_stones } => {
+                state.phase = GamePhase::EndRequested(*player);
+                state.dead_stones = Option::Some(dead_stones.clone());
+                state.propose(player, dead_stones);
+            }
+            Action::RejectEnd { player: _player } => {
+                state.resume();
+                state.rejections += 1;
+            }
+            Action::Resume { player: _player } => {
+                state.resume();
             }
-            &Action::RequestEnd { ref player, ref dead_stones } => {
+            Action::CounterProposeEnd { player, dead_stones } => {
                 state.phase = GamePhase::EndRequested(*player);
                 state.dead_stones = Option::Some(dead_stones.clone());
+                state.propose(player, dead_stones);
+                state.rejections += 1;
             }
-            &Action::RejectEnd { player: ref _player } => {
-                state.phase = GamePhase::Ending;
-                state.dead_stones = Option::None;
+            Action::AcceptEnd { player: _player } => {
+                // The dead stones are taken off the board as prisoners of
+                // the player who surrounds them.
+                for position in state.dead_stones.iter().flatten() {
+                    match state.board.at(position) {
+                        Stone::Black => state.prisoners_white += 1,
+                        Stone::White => state.prisoners_black += 1,
+                        Stone::Empty => continue,
+                    }
+                    state.board.set(position, &Stone::Empty);
+                    state.removed_stones.push(*position);
+                }
+
+                let (score_black, score_white) = match state.rules.scoring {
+                    ScoringMethod::Area => state.board.area_scoring(),
+                    ScoringMethod::Territory => {
+                        state.board.territory_scoring(&[],
+                                                      state.prisoners_black,
+                                                      state.prisoners_white)
+                    }
+                };
+                let button = match state.button {
+                    Some(Player::Black) => Score::from_points(0.5),
+                    Some(Player::White) => Score::from_points(-0.5),
+                    None => Score::JIGO,
+                };
+                let score = CountResult {
+                        black: score_black,
+                        white: score_white,
+                        komi: state.rules.komi + state.handicap_compensation(),
+                    }
+                    .score() + button;
+                state.counted = Some((score_black, score_white));
+                state.phase = GamePhase::Ended(GameResult::from_score(score));
+            }
+            Action::DeclareNoResult { player: _player } => {
+                state.phase = GamePhase::Ended(GameResult::Void);
+            }
+            Action::Pause { player: _player } => {
+                state.paused = true;
+            }
+            Action::Unpause { player: _player } => {
+                state.paused = false;
             }
-            &Action::AcceptEnd { player: ref _player } => {
-                let (score_black, score_white) = state.board.area_scoring();
-                state.phase = GamePhase::Ended(score_black, score_white);
+            Action::SpendTime { player, elapsed } => {
+                if state.clock.spend(player, *elapsed) {
+                    state.phase = GamePhase::Ended(GameResult::Timeout(player.other()));
+                }
+            }
+            Action::Resign { player } => {
+                state.phase = GamePhase::Ended(GameResult::Resignation(player.other()));
+            }
+            Action::Forfeit { player, reason } => {
+                let result = match *reason {
+                    ForfeitReason::Timeout => GameResult::Timeout(player.other()),
+                    ForfeitReason::Disconnection |
+                    ForfeitReason::Disqualification => GameResult::Forfeit(player.other()),
+                };
+                state.phase = GamePhase::Ended(result);
             }
         }
     }
 }
+
+impl<TBoard> engine::Setup for Action<TBoard>
+    where TBoard: ZobristHash
+{
+    /// Sets the rules, the persons playing and the stones on the board
+    ///
+    /// The ply, prisoners, ko history, handicap and clocks start over,
+    /// the phase of the game is not kept either.
+    fn setup(state: &GameState<TBoard>) -> Vec<Self> {
+        let mut actions = Vec::new();
+        if state.rules != RuleSet::aga() {
+            actions.push(Action::SetRules { rules: state.rules });
+        }
+        if state.ko_states.is_some() {
+            actions.push(Action::SetExactKoCheck { exact: true });
+        }
+        if let Some((black, white)) = state.colors {
+            actions.push(Action::AssignColors { black, white });
+        }
+
+        let stones = |stone: Stone| {
+            state.board
+                .iter_positions()
+                .filter(|position| state.board.at(position) == stone)
+                .collect()
+        };
+        actions.push(Action::Setup {
+            add_black: stones(Stone::Black),
+            add_white: stones(Stone::White),
+            clear: Vec::new(),
+            to_move: Some(state.to_move),
+        });
+
+        actions
+    }
+}
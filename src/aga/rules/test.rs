@@ -1,27 +1,31 @@
-use engine::{Game, Path};
-use go::{Player, Stone, Board};
-use aga::{Action, GamePhase, Position19x19, Board19x19};
+use engine::{Game, Path, SharedGame};
+use go::{Player, PlayerId, Stone, Board};
+use aga::{Action, ForfeitReason, GamePhase, RejectReason, Position19x19, Board9x9, Board19x19,
+          SuicideRule};
+use go::score::GameResult;
+use rules::{PassRule, RuleSet, ScoringMethod};
 
 type AGAGame = Game<Action<Board19x19>>;
 
 #[test]
 fn create_game() {
     let game = AGAGame::new();
-    let state = game.get_state(&Path::Empty);
+    let state = game.get_state(&Path::Root).unwrap();
 
     assert!(state.ply == 0);
     assert!(state.current_player() == Player::Black);
-    assert!(state.dead_stones == Option::None);
+    assert!(state.dead_stones.is_none());
 }
 
 #[test]
 fn play() {
     let mut game = AGAGame::new();
-    assert!(game.insert(&Path::Empty,
+    assert!(game.insert(&Path::Root,
                         Action::Play {
                             player: Player::Black,
                             at: Position19x19 { x: 3, y: 3 },
-                        }) != Path::Empty);
+                            time_used: None,
+                        }).is_some());
 }
 
 #[test]
@@ -30,39 +34,633 @@ fn suicide() {
     let actions: Vec<Action<Board19x19>> = vec![Action::Play {
                                                     player: Player::Black,
                                                     at: Position19x19 { x: 0, y: 1 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::White,
                                                     at: Position19x19 { x: 0, y: 2 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::Black,
                                                     at: Position19x19 { x: 1, y: 0 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::White,
                                                     at: Position19x19 { x: 1, y: 1 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::Black,
                                                     at: Position19x19 { x: 5, y: 5 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::White,
                                                     at: Position19x19 { x: 2, y: 0 },
+                                                    time_used: None,
                                                 }];
 
-    let mut cursor = Path::Empty;
+    let mut cursor = Path::Root;
     for action in actions {
-        cursor = game.insert(&cursor, action);
-        assert!(cursor != Path::Empty);
+        cursor = game.insert(&cursor, action).unwrap().into();
     }
 
     assert!(game.insert(&cursor,
                         Action::Play {
                             player: Player::Black,
                             at: Position19x19 { x: 0, y: 0 },
-                        }) == Path::Empty);
+                            time_used: None,
+                        }).is_none());
+}
+
+#[test]
+fn multi_stone_suicide() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::SetSuicideRule { rule: SuicideRule::MultiStone }).unwrap();
+
+    // XT.    black fills its own last liberty at T
+    // XO.
+    // O..
+    let moves = [(0, 1), (0, 2), (1, 0), (1, 1), (5, 5), (2, 0)];
+    for (ply, &(x, y)) in moves.iter().enumerate() {
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        cursor = game.insert(&cursor,
+                             Action::Play {
+                                 player,
+                                 at: Position19x19 { x, y },
+                                 time_used: None,
+                             }).unwrap();
+    }
+
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::Black,
+                             at: Position19x19 { x: 0, y: 0 },
+                             time_used: None,
+                         }).unwrap();
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.board.at(&Position19x19 { x: 0, y: 0 }) == Stone::Empty);
+    assert!(state.board.at(&Position19x19 { x: 0, y: 1 }) == Stone::Empty);
+    assert!(state.board.at(&Position19x19 { x: 1, y: 0 }) == Stone::Empty);
+    assert!(state.current_player() == Player::White);
+
+    // a single stone suicide stays illegal
+    for (ply, &(x, y)) in [(1, 0), (10, 10), (0, 1)].iter().enumerate() {
+        let player = if ply % 2 == 0 { Player::White } else { Player::Black };
+        cursor = game.insert(&cursor,
+                             Action::Play {
+                                 player,
+                                 at: Position19x19 { x, y },
+                                 time_used: None,
+                             }).unwrap();
+    }
+    assert!(game.insert(&cursor,
+                        Action::Play {
+                            player: Player::Black,
+                            at: Position19x19 { x: 0, y: 0 },
+                            time_used: None,
+                        }).is_none());
+}
+
+#[test]
+fn rules_only_before_the_game() {
+    let mut game = AGAGame::new();
+    let cursor = game.insert(&Path::Root,
+                             Action::Pass {
+                                 player: Player::Black,
+                                 time_used: None,
+                             }).unwrap();
+
+    assert!(game.insert(&cursor, Action::SetSuicideRule { rule: SuicideRule::MultiStone }).is_none());
+}
+
+#[test]
+fn rule_set() {
+    let mut game = AGAGame::new();
+    assert!(game.get_state(&Path::Root).unwrap().rules() == &RuleSet::aga());
+
+    let mut cursor = game.insert(&Path::Root, Action::SetRules { rules: RuleSet::japanese() }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().rules() == &RuleSet::japanese());
+
+    // white passing first ends the game once black passes, too
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::Black,
+                             at: Position19x19 { x: 2, y: 2 },
+                             time_used: None,
+                         }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().phase == GamePhase::WhitePassed);
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().phase == GamePhase::Ending);
+
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::White,
+                             dead_stones: vec![],
+                         }).unwrap();
+    cursor = game.insert(&cursor, Action::AcceptEnd { player: Player::Black }).unwrap();
+
+    // territory scoring does not count the stone itself
+    assert!(game.get_state(&cursor).unwrap().counted() == Some((360, 0)));
+}
+
+#[test]
+fn simple_ko_rule() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root, Action::SetRules { rules: RuleSet::japanese() }).unwrap();
+
+    //  .XO.
+    //  XOTO   black captures at T, white may not retake at once
+    //  .XO.
+    let moves = [(1, 0), (2, 0), (0, 1), (3, 1), (1, 2), (2, 2), (10, 10), (1, 1), (2, 1)];
+    for (ply, &(x, y)) in moves.iter().enumerate() {
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        cursor = game.insert(&cursor,
+                             Action::Play {
+                                 player,
+                                 at: Position19x19 { x, y },
+                                 time_used: None,
+                             }).unwrap();
+    }
+
+    assert!(game.insert(&cursor,
+                        Action::Play {
+                            player: Player::White,
+                            at: Position19x19 { x: 1, y: 1 },
+                            time_used: None,
+                        }).is_none());
+}
+
+#[test]
+fn player_to_move() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root, Action::SetPlayerToMove { player: Player::White }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().current_player() == Player::White);
+
+    assert!(game.insert(&cursor,
+                        Action::Play {
+                            player: Player::Black,
+                            at: Position19x19 { x: 3, y: 3 },
+                            time_used: None,
+                        }).is_none());
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::White,
+                             at: Position19x19 { x: 3, y: 3 },
+                             time_used: None,
+                         }).unwrap();
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.ply == 1);
+    assert!(state.current_player() == Player::Black);
+
+    assert!(game.insert(&cursor, Action::SetPlayerToMove { player: Player::White }).is_none());
+}
+
+#[test]
+fn assign_colors() {
+    let mut game = AGAGame::new();
+    assert!(game.get_state(&Path::Root).unwrap().player_id(&Player::Black).is_none());
+
+    assert!(game.insert(&Path::Root,
+                        Action::AssignColors {
+                            black: PlayerId(3),
+                            white: PlayerId(3),
+                        }).is_none());
+
+    let cursor = game.insert(&Path::Root,
+                             Action::AssignColors {
+                                 black: PlayerId(3),
+                                 white: PlayerId(5),
+                             }).unwrap();
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.player_id(&Player::Black) == Some(PlayerId(3)));
+    assert!(state.player_id(&Player::White) == Some(PlayerId(5)));
+    assert!(state.ply == 0);
+}
+
+#[test]
+fn setup() {
+    let mut game = AGAGame::new();
+
+    assert!(game.insert(&Path::Root,
+                        Action::Setup {
+                            add_black: vec![Position19x19 { x: 19, y: 0 }],
+                            add_white: vec![],
+                            clear: vec![],
+                            to_move: None,
+                        }).is_none());
+
+    // X.    white to move, black's corner stone is in atari
+    // O.
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::Setup {
+                                     add_black: vec![Position19x19 { x: 0, y: 0 },
+                                                     Position19x19 { x: 5, y: 5 }],
+                                     add_white: vec![Position19x19 { x: 0, y: 1 }],
+                                     clear: vec![],
+                                     to_move: Some(Player::White),
+                                 }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::Setup {
+                             add_black: vec![],
+                             add_white: vec![],
+                             clear: vec![Position19x19 { x: 5, y: 5 }],
+                             to_move: None,
+                         }).unwrap();
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.ply == 0);
+    assert!(state.current_player() == Player::White);
+    assert!(state.board.at(&Position19x19 { x: 5, y: 5 }) == Stone::Empty);
+
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::White,
+                             at: Position19x19 { x: 1, y: 0 },
+                             time_used: None,
+                         }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().board.at(&Position19x19 { x: 0, y: 0 }) == Stone::Empty);
+}
+
+#[test]
+fn positional_superko() {
+    use aga::rules::KoState;
+
+    let mut game = AGAGame::new();
+    let situational = game.insert(&Path::Root, Action::SetRules { rules: RuleSet::aga() }).unwrap();
+    let positional = game.insert(&Path::Root, Action::SetRules { rules: RuleSet::chinese() }).unwrap();
+
+    let at = Position19x19 { x: 3, y: 3 };
+    for (cursor, forbidden) in [(situational, false), (positional, true)] {
+        let mut state = game.get_state(&cursor).unwrap();
+
+        // the board after the play, but with black to move
+        let mut board = state.board.clone();
+        board.set(&at, &Stone::Black);
+        state.ko_hashes.insert(KoState {
+                                   board,
+                                   player: Player::Black,
+                               }
+                               .hash());
+
+        assert!(state.would_be_ko(&at, &Player::Black) == forbidden);
+    }
+}
+
+#[test]
+fn exact_ko_check() {
+    use aga::rules::KoState;
+
+    let mut game = AGAGame::new();
+    let hashed = game.insert(&Path::Root,
+                             Action::Pass {
+                                 player: Player::Black,
+                                 time_used: None,
+                             }).unwrap();
+    let mut exact = game.insert(&Path::Root, Action::SetExactKoCheck { exact: true }).unwrap();
+    exact = game.insert(&exact, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+
+    // too late once the game has started
+    assert!(game.insert(&exact, Action::SetExactKoCheck { exact: false }).is_none());
+
+    let at = Position19x19 { x: 3, y: 3 };
+    for (cursor, forbidden) in [(hashed, true), (exact, false)] {
+        let mut state = game.get_state(&cursor).unwrap();
+
+        // a colliding hash for a board that never occurred
+        let mut board = state.board.clone();
+        board.set(&at, &Stone::Black);
+        state.ko_hashes.insert(KoState {
+                                   board,
+                                   player: Player::White,
+                               }
+                               .hash());
+
+        assert!(state.would_be_ko(&at, &Player::Black) == forbidden);
+    }
+}
+
+#[test]
+fn button() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::SetRules {
+                                     rules: RuleSet {
+                                         button: true,
+                                         ..RuleSet::chinese()
+                                     },
+                                 }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::Black,
+                             at: Position19x19 { x: 3, y: 3 },
+                             time_used: None,
+                         }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::Pass {
+                             player: Player::White,
+                             time_used: None,
+                         }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::Pass {
+                             player: Player::Black,
+                             time_used: None,
+                         }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().button() == Some(Player::White));
+
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::Black,
+                             dead_stones: vec![],
+                         }).unwrap();
+    cursor = game.insert(&cursor, Action::AcceptEnd { player: Player::White }).unwrap();
+
+    // the button is worth half a point for white
+    let result = game.get_state(&cursor).unwrap().result().unwrap();
+    assert!(result.margin() == Some(361.0 - 7.5 - 0.5));
+
+    // territory does not count the stone black played more
+    let report = game.get_state(&cursor).unwrap().scoring_report();
+    assert!((report.area.black, report.territory.black) == (361, 360));
+    assert!(report.discrepancy().points() == 1.0);
+}
+
+#[test]
+fn long_cycle() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::SetRules {
+                                     rules: RuleSet {
+                                         passes: PassRule::BlackThenWhite,
+                                         ..RuleSet::japanese()
+                                     },
+                                 }).unwrap();
+
+    //  .XO.
+    //  XOTO   black captures at T
+    //  .XO.
+    let moves = [(1, 0), (2, 0), (0, 1), (3, 1), (1, 2), (2, 2), (10, 10), (1, 1), (2, 1)];
+    for (ply, &(x, y)) in moves.iter().enumerate() {
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        cursor = game.insert(&cursor,
+                             Action::Play {
+                                 player,
+                                 at: Position19x19 { x, y },
+                                 time_used: None,
+                             }).unwrap();
+    }
+    assert!(game.insert(&cursor, Action::DeclareNoResult { player: Player::White }).is_none());
+
+    // after two passes white may retake, repeating the position
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+    assert!(!game.get_state(&cursor).unwrap().is_repetition());
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::White,
+                             at: Position19x19 { x: 1, y: 1 },
+                             time_used: None,
+                         }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().is_repetition());
+
+    cursor = game.insert(&cursor, Action::DeclareNoResult { player: Player::White }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().result() == Some(GameResult::Void));
+}
+
+#[test]
+fn reject_reasons() {
+    let mut game = AGAGame::new();
+    let cursor = game.insert(&Path::Root,
+                             Action::Play {
+                                 player: Player::Black,
+                                 at: Position19x19 { x: 3, y: 3 },
+                                 time_used: None,
+                             }).unwrap();
+    let state = game.get_state(&cursor).unwrap();
+
+    let play = |player, x, y| {
+        Action::Play {
+            player,
+            at: Position19x19 { x, y },
+            time_used: None,
+        }
+    };
+    assert!(play(Player::White, 4, 4).validate(&state) == Ok(()));
+    assert!(play(Player::Black, 4, 4).validate(&state) == Err(RejectReason::NotYourTurn));
+    assert!(play(Player::White, 3, 3).validate(&state) == Err(RejectReason::Occupied));
+    assert!(play(Player::White, 19, 3).validate(&state) == Err(RejectReason::OffBoard));
+    assert!(Action::SetKomi { komi: 0.5 }.validate(&state) == Err(RejectReason::GameStarted));
+    assert!(Action::AcceptEnd { player: Player::White }.validate(&state) ==
+            Err(RejectReason::WrongPhase));
+    assert!(Action::DeclareNoResult { player: Player::White }.validate(&state) ==
+            Err(RejectReason::NoRepetition));
+
+    let cursor = game.insert(&cursor, Action::Resign { player: Player::White }).unwrap();
+    let state = game.get_state(&cursor).unwrap();
+    assert!(Action::Pass { player: Player::White, time_used: None }.validate(&state) ==
+            Err(RejectReason::GameOver));
+}
+
+#[test]
+fn legal_actions() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::Play {
+                                     player: Player::Black,
+                                     at: Position19x19 { x: 3, y: 3 },
+                                     time_used: None,
+                                 }).unwrap();
+
+    // 360 plays, a pass and resigning
+    let actions = game.get_state(&cursor).unwrap().legal_actions();
+    assert!(actions.len() == 362);
+    assert!(actions.iter().all(|action| match *action {
+        Action::Play { player, at, .. } => {
+            player == Player::White && at != Position19x19 { x: 3, y: 3 }
+        }
+        Action::Pass { player, .. } | Action::Resign { player } => player == Player::White,
+        _ => false,
+    }));
+
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().legal_actions().len() == 5);
+
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::White,
+                             dead_stones: vec![],
+                         }).unwrap();
+    let actions = game.get_state(&cursor).unwrap().legal_actions();
+    assert!(actions.len() == 3);
+    assert!(actions.iter().any(|action| match *action {
+        Action::AcceptEnd { player } => player == Player::Black,
+        _ => false,
+    }));
+}
+
+#[test]
+fn accessors() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::Play {
+                                     player: Player::Black,
+                                     at: Position19x19 { x: 3, y: 3 },
+                                     time_used: None,
+                                 }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::White,
+                             dead_stones: vec![Position19x19 { x: 3, y: 3 }],
+                         }).unwrap();
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.board().at(&Position19x19 { x: 3, y: 3 }) == Stone::Black);
+    assert!(state.ply() == 4);
+    assert!(state.current_player() == Player::Black);
+    assert!(*state.phase() == GamePhase::EndRequested(Player::White));
+    assert!(state.dead_stones() == Some(&[Position19x19 { x: 3, y: 3 }][..]));
+}
+
+#[test]
+fn counter_propose_end() {
+    let mut game = AGAGame::new();
+    let mut cursor = Path::Root;
+
+    let moves = [(2, 2), (15, 15), (16, 15)];
+    for (ply, &(x, y)) in moves.iter().enumerate() {
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        cursor = game.insert(&cursor,
+                             Action::Play {
+                                 player,
+                                 at: Position19x19 { x, y },
+                                 time_used: None,
+                             }).unwrap().into();
+    }
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap().into();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap().into();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap().into();
+
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::Black,
+                             dead_stones: vec![Position19x19 { x: 15, y: 15 }],
+                         }).unwrap().into();
+    assert!(game.insert(&cursor,
+                        Action::CounterProposeEnd {
+                            player: Player::Black,
+                            dead_stones: vec![],
+                        }).is_none());
+
+    // white agrees on its own stone, but wants the black pair dead
+    cursor = game.insert(&cursor,
+                         Action::CounterProposeEnd {
+                             player: Player::White,
+                             dead_stones: vec![Position19x19 { x: 15, y: 15 },
+                                               Position19x19 { x: 2, y: 2 }],
+                         }).unwrap().into();
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.phase == GamePhase::EndRequested(Player::White));
+    assert!(state.rejections() == 1);
+    assert!(state.agreed_dead_groups() == vec![vec![Position19x19 { x: 15, y: 15 }]]);
+
+    cursor = game.insert(&cursor, Action::RejectEnd { player: Player::Black }).unwrap().into();
+    assert!(game.get_state(&cursor).unwrap().rejections() == 2);
+}
+
+#[test]
+fn suggest_dead_stones() {
+    let board: Board9x9 = ".X.X.O...
+                           XXXXOO...
+                           OOOOO....
+                           .........
+                           ...X.....
+                           .........
+                           OOOOOOOOO
+                           XXXXXXXXX
+                           .X.X.X.X."
+        .parse()
+        .unwrap();
+    let stones = |stone| board.iter_positions().filter(|p| board.at(p) == stone).collect();
+
+    let mut game = Game::<Action<Board9x9>>::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::Setup {
+                                     add_black: stones(Stone::Black),
+                                     add_white: stones(Stone::White),
+                                     clear: vec![],
+                                     to_move: None,
+                                 }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+
+    let dead_stones = game.get_state(&cursor).unwrap().suggest_dead_stones();
+    assert!(dead_stones.contains(&Position19x19 { x: 3, y: 4 }));
+    assert!(!dead_stones.contains(&Position19x19 { x: 0, y: 1 }));
+
+    assert!(game.insert(&cursor,
+                        Action::RequestEnd {
+                            player: Player::Black,
+                            dead_stones,
+                        }).is_some());
+}
+
+#[test]
+fn resume_after_rejected_end() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::Play {
+                                     player: Player::Black,
+                                     at: Position19x19 { x: 3, y: 3 },
+                                     time_used: None,
+                                 }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::White,
+                             at: Position19x19 { x: 15, y: 15 },
+                             time_used: None,
+                         }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    let ending = cursor;
+
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::White,
+                             dead_stones: vec![Position19x19 { x: 3, y: 3 }],
+                         }).unwrap();
+    cursor = game.insert(&cursor, Action::RejectEnd { player: Player::Black }).unwrap();
+
+    // white played last, so black continues
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.phase == GamePhase::Running);
+    assert!(state.current_player() == Player::Black);
+    assert!(state.dead_stones().is_none());
+    // white's pass repeats the state after white's play
+    assert!(state.ko_hashes.len() == 3);
+
+    assert!(game.insert(&cursor,
+                        Action::Play {
+                            player: Player::Black,
+                            at: Position19x19 { x: 15, y: 14 },
+                            time_used: None,
+                        }).is_some());
+
+    // resuming without an end request
+    cursor = game.insert(&ending, Action::Resume { player: Player::White }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().current_player() == Player::Black);
+    assert!(game.insert(&cursor, Action::Resume { player: Player::White }).is_none());
 }
 
 #[test]
@@ -71,115 +669,595 @@ fn capture_ko() {
     let actions: Vec<Action<Board19x19>> = vec![Action::Play {
                                                     player: Player::Black,
                                                     at: Position19x19 { x: 0, y: 0 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::White,
                                                     at: Position19x19 { x: 1, y: 0 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::Black,
                                                     at: Position19x19 { x: 2, y: 0 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::White,
                                                     at: Position19x19 { x: 0, y: 1 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::Black,
                                                     at: Position19x19 { x: 1, y: 1 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::White,
                                                     at: Position19x19 { x: 2, y: 1 },
+                                                    time_used: None,
                                                 },
                                                 Action::Play {
                                                     player: Player::Black,
                                                     at: Position19x19 { x: 0, y: 0 },
+                                                    time_used: None,
                                                 }];
     // # . . .  # O . .   # O # .   . O # .  . O # .  . O # .  # . # .  recap is ko
     // . . . .  . . . .   . . . .   O . . .  O # . .  O # O .  O # O .
     // . . . .  . . . .   . . . .   . . . .  . . . .  . . . .  . . . .
 
-    let mut cursor = Path::Empty;
+    let mut cursor = Path::Root;
     for action in actions {
-        cursor = game.insert(&cursor, action);
-        assert!(cursor != Path::Empty);
+        cursor = game.insert(&cursor, action).unwrap().into();
     }
 
     assert!(game.insert(&cursor,
                         Action::Play {
                             player: Player::White,
                             at: Position19x19 { x: 1, y: 0 },
-                        }) == Path::Empty);
+                            time_used: None,
+                        }).is_none());
 }
 
 #[test]
 fn pass() {
     let mut game = AGAGame::new();
-    let mut cursor = Path::Empty;
+    let mut cursor = Path::Root;
 
-    cursor = game.insert(&cursor, Action::Pass { player: Player::Black });
-    assert!(cursor != Path::Empty);
-    assert!(game.insert(&cursor, Action::Pass { player: Player::Black }) == Path::Empty);
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap().into();
+    assert!(game.insert(&cursor,
+                        Action::Pass {
+                            player: Player::Black,
+                            time_used: None,
+                        }).is_none());
 
-    cursor = game.insert(&cursor, Action::Pass { player: Player::White });
-    assert!(cursor != Path::Empty);
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap().into();
 
-    let state = game.get_state(&cursor);
+    let state = game.get_state(&cursor).unwrap();
     assert!(state.ply == 2);
     assert!(state.phase == GamePhase::Ending);
 }
 
+#[test]
+fn pass_stones() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::SetRules {
+                                     rules: RuleSet {
+                                         scoring: ScoringMethod::Territory,
+                                         ..RuleSet::aga()
+                                     },
+                                 }).unwrap();
+
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::Black,
+                             at: Position19x19 { x: 2, y: 2 },
+                             time_used: None,
+                         }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+
+    // white has to pass last
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.phase == GamePhase::BlackPassed);
+    assert!(state.prisoners(&Player::Black) == 1);
+    assert!(state.prisoners(&Player::White) == 1);
+
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::Black,
+                             dead_stones: vec![],
+                         }).unwrap();
+    cursor = game.insert(&cursor, Action::AcceptEnd { player: Player::White }).unwrap();
+
+    // black's territory plus two pass stones of white minus one of black
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.counted() == Some((362, 1)));
+
+    // the result already includes komi
+    let result = state.result().unwrap();
+    assert!(result.winner() == Some(Player::Black));
+    assert!(result.margin() == Some(362.0 - 1.0 - state.komi()));
+}
+
 #[test]
 fn handicap() {
     let mut game = AGAGame::new();
-    let mut cursor = Path::Empty;
+    let mut cursor = Path::Root;
 
-    cursor = game.insert(&cursor, Action::Handicap { stones: 3 });
-    let state = game.get_state(&cursor);
+    cursor = game.insert(&cursor, Action::Handicap { stones: 3 }).unwrap().into();
+    let state = game.get_state(&cursor).unwrap();
 
     assert!(state.current_player() == Player::White);
-    assert!(state.board.at(&Position19x19 { x: 14, y: 4 }) == Stone::Black);
-    assert!(state.board.at(&Position19x19 { x: 4, y: 14 }) == Stone::Black);
-    assert!(state.board.at(&Position19x19 { x: 14, y: 14 }) == Stone::Black);
+    assert!(state.board.at(&Position19x19 { x: 15, y: 3 }) == Stone::Black);
+    assert!(state.board.at(&Position19x19 { x: 3, y: 15 }) == Stone::Black);
+    assert!(state.board.at(&Position19x19 { x: 15, y: 15 }) == Stone::Black);
+}
+
+#[test]
+fn invalid_handicap() {
+    let state = AGAGame::new().get_state(&Path::Root).unwrap();
+    for &stones in &[0, 1, 10] {
+        assert!(Action::Handicap { stones }.validate(&state) ==
+                Err(RejectReason::InvalidHandicap));
+    }
+    assert!(Action::Handicap { stones: 9 }.validate(&state) == Ok(()));
+
+    // small boards allow fewer stones
+    let state = Game::<Action<Board9x9>>::new().get_state(&Path::Root).unwrap();
+    assert!(Action::Handicap { stones: 5 }.validate(&state) == Ok(()));
+    assert!(Action::Handicap { stones: 6 }.validate(&state) ==
+            Err(RejectReason::InvalidHandicap));
+}
+
+#[test]
+fn handicap_compensation() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root, Action::SetKomi { komi: 0.5 }).unwrap();
+    cursor = game.insert(&cursor, Action::Handicap { stones: 4 }).unwrap();
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.handicap() == 4);
+    assert!(state.handicap_compensation() == 3.0);
+
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::Black,
+                             dead_stones: vec![],
+                         }).unwrap();
+    cursor = game.insert(&cursor, Action::AcceptEnd { player: Player::White }).unwrap();
+
+    // black's whole board minus komi and three points for the handicap
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.counted() == Some((361, 0)));
+    assert!(state.score().unwrap().to_string() == "B+357.5");
+
+    // the compensation only applies to the area count
+    let report = state.scoring_report();
+    assert!(report.area.komi == 3.5);
+    assert!(report.territory.komi == 0.5);
+    assert!(report.agrees());
 }
 
 #[test]
 fn end() {
     let mut game = AGAGame::new();
-    let mut cursor = Path::Empty;
+    let mut cursor = Path::Root;
 
     cursor = game.insert(&cursor,
                          Action::Play {
                              player: Player::Black,
                              at: Position19x19 { x: 2, y: 2 },
-                         });
-    cursor = game.insert(&cursor, Action::Pass { player: Player::White });
-    cursor = game.insert(&cursor, Action::Pass { player: Player::Black });
+                             time_used: None,
+                         }).unwrap().into();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap().into();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap().into();
 
-    assert!(game.get_state(&cursor).phase == GamePhase::BlackPassed);
-    cursor = game.insert(&cursor, Action::Pass { player: Player::White });
-    assert!(game.get_state(&cursor).phase == GamePhase::Ending);
+    assert!(game.get_state(&cursor).unwrap().phase == GamePhase::BlackPassed);
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap().into();
+    assert!(game.get_state(&cursor).unwrap().phase == GamePhase::Ending);
 
-    assert!(game.insert(&cursor, Action::RejectEnd { player: Player::Black }) == Path::Empty);
-    assert!(game.insert(&cursor, Action::RejectEnd { player: Player::White }) == Path::Empty);
-    assert!(game.insert(&cursor, Action::AcceptEnd { player: Player::Black }) == Path::Empty);
-    assert!(game.insert(&cursor, Action::AcceptEnd { player: Player::White }) == Path::Empty);
+    assert!(game.insert(&cursor, Action::RejectEnd { player: Player::Black }).is_none());
+    assert!(game.insert(&cursor, Action::RejectEnd { player: Player::White }).is_none());
+    assert!(game.insert(&cursor, Action::AcceptEnd { player: Player::Black }).is_none());
+    assert!(game.insert(&cursor, Action::AcceptEnd { player: Player::White }).is_none());
 
     assert!(game.insert(&cursor,
                         Action::RequestEnd {
                             player: Player::Black,
                             dead_stones: vec![Position19x19 { x: 2, y: 3 }],
-                        }) == Path::Empty);
+                        }).is_none());
 
     cursor = game.insert(&cursor,
                          Action::RequestEnd {
                              player: Player::Black,
                              dead_stones: vec![],
-                         });
-    assert!(cursor != Path::Empty);
+                         }).unwrap().into();
+
+    assert!(game.insert(&cursor, Action::AcceptEnd { player: Player::Black }).is_none());
+    assert!(game.insert(&cursor, Action::AcceptEnd { player: Player::White }).is_some());
+}
+
+#[test]
+fn end_with_dead_stones() {
+    let mut game = AGAGame::new();
+    let mut cursor = Path::Root;
+
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::Black,
+                             at: Position19x19 { x: 2, y: 2 },
+                             time_used: None,
+                         }).unwrap().into();
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::White,
+                             at: Position19x19 { x: 15, y: 15 },
+                             time_used: None,
+                         }).unwrap().into();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap().into();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap().into();
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::Black,
+                             dead_stones: vec![Position19x19 { x: 15, y: 15 }],
+                         }).unwrap().into();
+    cursor = game.insert(&cursor, Action::AcceptEnd { player: Player::White }).unwrap().into();
+
+    // the white stone is removed, black owns the whole board
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.counted() == Some((361, 0)));
+    assert!(state.board.at(&Position19x19 { x: 15, y: 15 }) == Stone::Empty);
+    assert!(state.removed_stones() == [Position19x19 { x: 15, y: 15 }]);
+    // the dead stone and the pass stone of white
+    assert!(state.prisoners(&Player::Black) == 2);
+}
+
+#[test]
+fn komi() {
+    let mut game = AGAGame::new();
+    assert!(game.get_state(&Path::Root).unwrap().komi() == 7.5);
+
+    let mut cursor = game.insert(&Path::Root, Action::SetKomi { komi: 0.5 }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().komi() == 0.5);
+
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::Black,
+                             at: Position19x19 { x: 2, y: 2 },
+                             time_used: None,
+                         }).unwrap();
+    assert!(game.insert(&cursor, Action::SetKomi { komi: 6.5 }).is_none());
+
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::White,
+                             at: Position19x19 { x: 2, y: 3 },
+                             time_used: None,
+                         }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::Black,
+                             dead_stones: vec![],
+                         }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().score().is_none());
+    cursor = game.insert(&cursor, Action::AcceptEnd { player: Player::White }).unwrap();
+
+    // one stone each, the rest is dame: white wins by komi
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.score().unwrap().to_string() == "W+0.5");
+    assert!(state.result().unwrap().winner() == Some(Player::White));
+}
+
+#[test]
+fn resign() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::Play {
+                                     player: Player::Black,
+                                     at: Position19x19 { x: 3, y: 3 },
+                                     time_used: None,
+                                 }).unwrap();
+
+    // resigning does not need to wait for the player's turn
+    cursor = game.insert(&cursor, Action::Resign { player: Player::Black }).unwrap();
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.phase == GamePhase::Ended(GameResult::Resignation(Player::White)));
+    assert!(state.is_over());
+    assert!(state.score().unwrap().to_string() == "W+R");
+
+    assert!(game.insert(&cursor, Action::Resign { player: Player::White }).is_none());
+    assert!(game.insert(&cursor,
+                        Action::Pass {
+                            player: Player::White,
+                            time_used: None,
+                        }).is_none());
+}
+
+#[test]
+fn clock() {
+    use std::time::Duration;
+    use time::TimeControl;
+
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::SetTimeControl {
+                                     control: TimeControl::Absolute {
+                                         main: Duration::from_secs(60),
+                                     },
+                                 }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::SpendTime {
+                             player: Player::Black,
+                             elapsed: Duration::from_secs(50),
+                         }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::Black,
+                             at: Position19x19 { x: 3, y: 3 },
+                             time_used: None,
+                         }).unwrap();
+    assert!(game.insert(&cursor,
+                        Action::SetTimeControl { control: TimeControl::Unlimited }).is_none());
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.clock().player(&Player::Black).main == Duration::from_secs(10));
+    assert!(state.ply() == 1);
+
+    cursor = game.insert(&cursor,
+                         Action::SpendTime {
+                             player: Player::Black,
+                             elapsed: Duration::from_secs(11),
+                         }).unwrap();
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.phase == GamePhase::Ended(GameResult::Timeout(Player::White)));
+    assert!(state.score().is_none());
+}
+
+#[test]
+fn time_used() {
+    use std::time::Duration;
+    use time::TimeControl;
+
+    let mut game = AGAGame::new();
+    let start = game.insert(&Path::Root,
+                            Action::SetTimeControl {
+                                control: TimeControl::ByoYomi {
+                                    main: Duration::from_secs(60),
+                                    period: Duration::from_secs(30),
+                                    periods: 1,
+                                },
+                            }).unwrap();
+    let cursor = game.insert(&start,
+                             Action::Play {
+                                 player: Player::Black,
+                                 at: Position19x19 { x: 3, y: 3 },
+                                 time_used: Some(Duration::from_secs(70)),
+                             }).unwrap();
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.clock().remaining(&Player::Black) == Some(Duration::from_secs(30)));
+    assert!(state.clock().remaining(&Player::White) == Some(Duration::from_secs(90)));
+
+    // the earlier node still has the full time
+    let state = game.get_state(&start).unwrap();
+    assert!(state.clock().remaining(&Player::Black) == Some(Duration::from_secs(90)));
+
+    // overrunning the last period flags the player
+    let state = game.get_state(&cursor).unwrap();
+    let pass = Action::Pass {
+        player: Player::White,
+        time_used: Some(Duration::from_secs(90)),
+    };
+    assert!(pass.validate(&state) == Err(RejectReason::OutOfTime));
+    assert!(game.insert(&cursor, pass).is_none());
+}
+
+#[test]
+fn pause() {
+    use std::time::Duration;
+
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root, Action::Pause { player: Player::White }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().is_paused());
+
+    // the board and the clocks are frozen
+    let state = game.get_state(&cursor).unwrap();
+    let play = Action::Play {
+        player: Player::Black,
+        at: Position19x19 { x: 3, y: 3 },
+        time_used: None,
+    };
+    let spend = Action::SpendTime {
+        player: Player::Black,
+        elapsed: Duration::from_secs(10),
+    };
+    assert!(play.validate(&state) == Err(RejectReason::Paused));
+    assert!(spend.validate(&state) == Err(RejectReason::Paused));
+    assert!(Action::Pause { player: Player::Black }.validate(&state) ==
+            Err(RejectReason::Paused));
+
+    cursor = game.insert(&cursor, Action::Unpause { player: Player::Black }).unwrap();
+    let state = game.get_state(&cursor).unwrap();
+    assert!(!state.is_paused());
+    assert!(state.ply() == 0);
+    assert!(Action::Unpause { player: Player::Black }.validate(&state) ==
+            Err(RejectReason::NotPaused));
+    assert!(game.insert(&cursor, play).is_some());
+
+    // giving up is still possible
+    cursor = game.insert(&cursor, Action::Pause { player: Player::Black }).unwrap();
+    cursor = game.insert(&cursor, Action::Resign { player: Player::Black }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().result() == Some(GameResult::Resignation(Player::White)));
+}
+
+#[test]
+fn forfeit() {
+    let mut game = AGAGame::new();
+    let cursor = game.insert(&Path::Root,
+                                 Action::Forfeit {
+                                     player: Player::White,
+                                     reason: ForfeitReason::Timeout,
+                                 }).unwrap();
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.phase == GamePhase::Ended(GameResult::Timeout(Player::Black)));
+    assert!(state.is_over());
+    assert!(state.score().is_none());
+
+    assert!(game.insert(&cursor, Action::Resign { player: Player::Black }).is_none());
+}
+
+#[test]
+fn last_move_and_ko_point() {
+    let mut game = AGAGame::new();
+    let mut cursor = Path::Root;
+
+    //  .XO.
+    //  XOTO   black captures at T
+    //  .XO.
+    let moves = [(1, 0), (2, 0), (0, 1), (3, 1), (1, 2), (2, 2), (10, 10), (1, 1), (2, 1)];
+    for (ply, &(x, y)) in moves.iter().enumerate() {
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        cursor = game.insert(&cursor,
+                             Action::Play {
+                                 player,
+                                 at: Position19x19 { x, y },
+                                 time_used: None,
+                             }).unwrap().into();
+    }
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.last_move() == Some(&Position19x19 { x: 2, y: 1 }));
+    assert!(state.ko_point() == Some(&Position19x19 { x: 1, y: 1 }));
+
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap().into();
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.last_move().is_none());
+    assert!(state.ko_point().is_none());
+}
+
+#[test]
+fn extract() {
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root, Action::SetKomi { komi: 0.5 }).unwrap();
+    let moves = [(3, 3), (15, 15), (3, 15)];
+    for (ply, &(x, y)) in moves.iter().enumerate() {
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        let play = Action::Play {
+            player,
+            at: Position19x19 { x, y },
+            time_used: None,
+        };
+        cursor = game.insert(&cursor, play).unwrap();
+    }
+    game.insert(&cursor, Action::Resign { player: Player::White }).unwrap();
+
+    let study = game.extract(&cursor).unwrap();
+    let state = game.get_state(&cursor).unwrap();
+    let rules = study.children(&Path::Root)[0];
+    assert!(*study.action(&rules).unwrap() == Action::SetRules { rules: *state.rules() });
+    let setup = study.children(&rules)[0];
+    let setup_state = study.get_state(&setup).unwrap();
+    assert!(setup_state.board() == state.board());
+    assert!(setup_state.current_player() == Player::White);
+    assert!(setup_state.komi() == 0.5);
+    assert!(setup_state.ply() == 0);
+
+    let resign = study.children(&setup)[0];
+    let result = study.get_state(&resign).unwrap().result();
+    assert!(result == Some(GameResult::Resignation(Player::Black)));
+    assert!(study.iter_dfs().count() == 3);
+}
+
+#[test]
+fn shared() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SharedGame<Action<Board19x19>>>();
+}
+
+#[test]
+fn to_dot() {
+    let mut game = AGAGame::new();
+    let komi = game.insert(&Path::Root, Action::SetKomi { komi: 6.5 }).unwrap();
+    let play = Action::Play {
+        player: Player::Black,
+        at: Position19x19 { x: 3, y: 3 },
+        time_used: None,
+    };
+    assert_eq!(play.to_string(), "B (3, 3)");
+    let black = game.insert(&komi, play).unwrap();
+    game.insert(&black, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    game.insert(&black, Action::Resign { player: Player::White }).unwrap();
+
+    let dot = game.to_dot();
+    assert!(dot.contains("n0 [label=\"1: komi 6.5\"];"));
+    assert!(dot.contains("n1 [label=\"2: B (3, 3)\"];"));
+    assert!(dot.contains("n1 -> n2 [style=bold];"));
+    assert!(dot.contains("n3 [label=\"3: W resign\"];"));
+    assert!(dot.contains("n1 -> n3;"));
+}
+
+#[test]
+fn transpositions() {
+    let mut game = AGAGame::new();
+    let play = |player, x, y| {
+        Action::Play {
+            player,
+            at: Position19x19 { x, y },
+            time_used: None,
+        }
+    };
+
+    let mut one = game.insert(&Path::Root, play(Player::Black, 3, 3)).unwrap();
+    one = game.insert(&one, play(Player::White, 15, 15)).unwrap();
+    one = game.insert(&one, play(Player::Black, 3, 15)).unwrap();
+
+    let mut other = game.insert(&Path::Root, play(Player::Black, 3, 15)).unwrap();
+    other = game.insert(&other, play(Player::White, 15, 15)).unwrap();
+    let passed = game.insert(&other, Action::Pass { player: Player::Black, time_used: None })
+        .unwrap();
+    other = game.insert(&other, play(Player::Black, 3, 3)).unwrap();
+
+    // passing keeps the stones but hands the turn to white
+    assert_eq!(game.transpositions(), vec![vec![one, other]]);
+    assert_eq!(game.dag().canonical(&passed), Some(passed));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    use aga::rules::GameState;
+    use serde_json;
+
+    let mut game = AGAGame::new();
+    let mut cursor = game.insert(&Path::Root, Action::Handicap { stones: 2 }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::White,
+                             at: Position19x19 { x: 3, y: 3 },
+                             time_used: None,
+                         }).unwrap();
+    let state = game.get_state(&cursor).unwrap();
+
+    let json = serde_json::to_string(&state).unwrap();
+    let restored: GameState<Board19x19> = serde_json::from_str(&json).unwrap();
+
+    assert!(restored.board == state.board);
+    assert!(restored.ply == state.ply);
+    assert!(restored.phase == state.phase);
+    assert!(restored.ko_hashes == state.ko_hashes);
+    assert!(restored.current_player() == Player::Black);
 
-    assert!(game.insert(&cursor, Action::AcceptEnd { player: Player::Black }) == Path::Empty);
-    cursor = game.insert(&cursor, Action::AcceptEnd { player: Player::White });
-    assert!(cursor != Path::Empty);
+    // whole games are stored as their actions
+    let json = serde_json::to_string(&game).unwrap();
+    let restored: AGAGame = serde_json::from_str(&json).unwrap();
+    let leaf = restored.iter_leaves().next().unwrap().0;
+    assert!(restored.get_state(&leaf).unwrap().board == state.board);
 }
@@ -0,0 +1,75 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An assessment of a move, as known from game reviews
+///
+/// The emphasized variants mark very good or very bad moves.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MoveMark {
+    Good { emphasized: bool },
+    Bad { emphasized: bool },
+    Doubtful,
+    Interesting,
+}
+
+/// A shape drawn onto a point of the board
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Markup {
+    Circle,
+    Cross,
+    Square,
+    Triangle,
+    Selected,
+}
+
+/// Metadata attached to an item of the game tree
+///
+/// Annotations do not change the game state, they only describe it.
+/// The labels and markup refer to positions of the board the actions
+/// are played on, see `Action::Position`.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Annotation<TPosition> {
+    /// A free text comment
+    pub comment: Option<String>,
+    /// The assessment of the move
+    pub mark: Option<MoveMark>,
+    /// Texts shown on points of the board
+    pub labels: Vec<(TPosition, String)>,
+    /// Shapes shown on points of the board
+    pub markup: Vec<(TPosition, Markup)>,
+}
+
+impl<TPosition> Default for Annotation<TPosition> {
+    fn default() -> Self {
+        Annotation {
+            comment: None,
+            mark: None,
+            labels: Vec::new(),
+            markup: Vec::new(),
+        }
+    }
+}
+
+impl<TPosition> Annotation<TPosition> {
+    /// Creates an empty annotation
+    pub fn new() -> Self {
+        Annotation::default()
+    }
+
+    /// Creates an annotation holding just a comment
+    pub fn comment(comment: &str) -> Self {
+        Annotation {
+            comment: Some(comment.to_string()),
+            ..Annotation::default()
+        }
+    }
+
+    /// Checks if the annotation holds no data at all
+    pub fn is_empty(&self) -> bool {
+        self.comment.is_none() && self.mark.is_none() && self.labels.is_empty() &&
+        self.markup.is_empty()
+    }
+}
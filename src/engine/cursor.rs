@@ -0,0 +1,153 @@
+use engine::{Action, AsPath, Game, GameState, InsertError, NodeId, Path};
+
+/// A position in a game tree along with its state
+///
+/// Keeps the states of all items from the root to the cursor, so
+/// stepping back is cheap and moving to another variation only
+/// replays the actions after the common ancestor. The cursor does not
+/// borrow the game, the game is handed to the methods that need it.
+pub struct GameCursor<SomeAction>
+    where SomeAction: Action
+{
+    /// The items from the root to the cursor
+    line: Vec<NodeId>,
+    /// The state at the root followed by the states after each item of the line
+    states: Vec<SomeAction::GameState>,
+    /// The items left by undo, the last one is redone first
+    undone: Vec<NodeId>,
+}
+
+impl<SomeAction> Default for GameCursor<SomeAction>
+    where SomeAction: Action
+{
+    fn default() -> Self {
+        GameCursor::new()
+    }
+}
+
+impl<SomeAction> GameCursor<SomeAction>
+    where SomeAction: Action
+{
+    /// Creates a cursor at the root of a game
+    pub fn new() -> Self {
+        GameCursor {
+            line: Vec::new(),
+            states: vec![SomeAction::GameState::new()],
+            undone: Vec::new(),
+        }
+    }
+
+    /// Returns the path to the cursor
+    pub fn path(&self) -> Path {
+        match self.line.last() {
+            Some(&id) => Path::Node(id),
+            None => Path::Root,
+        }
+    }
+
+    /// Returns the state at the cursor
+    pub fn state(&self) -> &SomeAction::GameState {
+        self.states.last().unwrap()
+    }
+
+    /// Returns the number of items between the root and the cursor
+    pub fn depth(&self) -> usize {
+        self.line.len()
+    }
+
+    /// Inserts the action after the cursor and moves onto it
+    ///
+    /// Fails like `Game::try_insert` and leaves the cursor in place
+    /// then. Forgets the items left by undo.
+    pub fn advance(&mut self,
+                   game: &mut Game<SomeAction>,
+                   action: SomeAction)
+                   -> Result<NodeId, InsertError<SomeAction::Reason>> {
+        let id = game.try_insert(&self.path(), action)?;
+        self.push(game, id);
+        self.undone.clear();
+        Ok(id)
+    }
+
+    /// Moves the cursor back to the parent of its item
+    ///
+    /// Returns false at the root.
+    pub fn undo(&mut self) -> bool {
+        match self.line.pop() {
+            Some(id) => {
+                self.states.pop();
+                self.undone.push(id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the cursor forward again
+    ///
+    /// Returns to the item left by the last undo if there is one,
+    /// follows the main line otherwise. Returns false at the end of a
+    /// variation.
+    pub fn redo(&mut self, game: &Game<SomeAction>) -> bool {
+        let path = self.path();
+        let next = match self.undone.pop() {
+            Some(id) if game.parent(&id) == Some(path) => Some(id),
+            _ => {
+                self.undone.clear();
+                game.children(&path).first().cloned()
+            }
+        };
+
+        match next {
+            Some(id) => {
+                self.push(game, id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the cursor to the given path
+    ///
+    /// Replays the actions from the last item shared with the current
+    /// line on. Returns false and stays in place if the path does not
+    /// belong to the game.
+    pub fn goto<P>(&mut self, game: &Game<SomeAction>, at: &P) -> bool
+        where P: AsPath
+    {
+        let mut target = Vec::new();
+        let mut current = at.as_path();
+        while let Path::Node(id) = current {
+            match game.parent(&id) {
+                Some(parent) => {
+                    target.push(id);
+                    current = parent;
+                }
+                None => return false,
+            }
+        }
+        target.reverse();
+
+        let shared = self.line
+            .iter()
+            .zip(&target)
+            .take_while(|&(current, target)| current == target)
+            .count();
+        self.line.truncate(shared);
+        self.states.truncate(shared + 1);
+        for &id in &target[shared..] {
+            self.push(game, id);
+        }
+
+        self.undone.clear();
+        true
+    }
+
+    /// Appends the item to the line, it has to follow the cursor
+    fn push(&mut self, game: &Game<SomeAction>, id: NodeId) {
+        let mut state = self.state().clone();
+        game.action(&id).unwrap().execute(&mut state);
+        self.line.push(id);
+        self.states.push(state);
+    }
+}
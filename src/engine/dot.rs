@@ -0,0 +1,38 @@
+use std::fmt;
+
+use engine::{Action, Game};
+
+impl<SomeAction> Game<SomeAction>
+    where SomeAction: Action + fmt::Display
+{
+    /// Writes the tree as a Graphviz DOT graph
+    ///
+    /// Each item becomes a node labeled with its ply, counted from the
+    /// root, and its action. Edges point from parents to children,
+    /// the main line of an item is drawn bold.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph game {\n    root [label=\"root\"];\n");
+        let mut plies = vec![0; self.data.len()];
+
+        for (id, action) in self.iter_dfs() {
+            let parent = self.data[id.index].parent;
+            plies[id.index] = parent.map_or(0, |parent| plies[parent]) + 1;
+
+            let label = format!("{}: {}", plies[id.index], action);
+            dot.push_str(&format!("    n{} [label=\"{}\"];\n", id.index, escape(&label)));
+
+            let from = parent.map_or("root".to_string(), |parent| format!("n{}", parent));
+            let main = self.children(&self.parent(&id).unwrap()).first() == Some(&id);
+            let style = if main { " [style=bold]" } else { "" };
+            dot.push_str(&format!("    {} -> n{}{};\n", from, id.index, style));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Escapes the characters that end or escape a DOT string
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
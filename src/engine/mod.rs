@@ -1,37 +1,106 @@
 #![allow(dead_code)]
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+#[cfg(feature = "serde")]
+use serde::de::Error;
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub mod annotation;
+pub mod cursor;
+pub mod dot;
+pub mod shared;
+pub mod transposition;
+
+pub use self::annotation::{Annotation, Markup, MoveMark};
+pub use self::cursor::GameCursor;
+pub use self::shared::SharedGame;
+pub use self::transposition::{PositionDag, PositionHash};
 
 #[cfg(test)]
 mod test;
 
 /// A game state
-pub trait GameState {
+///
+/// States are cloned to cache them in the game tree. A tree can be
+/// shared between threads, see `SharedGame`, if its states are `Send`
+/// and its actions `Send + Sync`.
+pub trait GameState: Clone {
     /// constructs the initial game state
     fn new() -> Self;
 }
 
 
 /// A game action
+///
+/// Actions are stored in the game tree, they have to be `Send + Sync`
+/// to share it between threads.
 pub trait Action {
     /// The states these actions modify
     type GameState: GameState;
 
+    /// Why an action is not applicable
+    type Reason;
+
+    /// The positions annotations of these actions refer to
+    type Position: Clone + PartialEq + fmt::Debug;
+
+    /// Checks if the action is applicable to the given state
+    ///
+    /// Returns the reason the action is rejected otherwise.
+    fn check(&self, state: &Self::GameState) -> Result<(), Self::Reason>;
+
     /// Tests if the action is applicable to the given state
-    fn test(self: &Self, state: &Self::GameState) -> bool;
+    fn test(&self, state: &Self::GameState) -> bool {
+        self.check(state).is_ok()
+    }
 
     /// Executes the action on the given state
-    fn execute(self: &Self, state: &mut Self::GameState);
+    fn execute(&self, state: &mut Self::GameState);
+}
+
+/// An action that can compose a state from scratch
+///
+/// Lets `Game::extract` start a tree at any state.
+pub trait Setup: Action + Sized {
+    /// Returns the actions turning the initial state into one like the given
+    ///
+    /// The actions need to recreate what later actions depend on, like
+    /// the board and the player to move. The history leading to the
+    /// state may be lost.
+    fn setup(state: &Self::GameState) -> Vec<Self>;
 }
 
 /// An history item for use in the game tree
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde",
+           serde(bound(serialize = "SomeAction: Serialize, SomeAction::Position: Serialize",
+                       deserialize = "SomeAction: Deserialize<'de>, \
+                                      SomeAction::Position: Deserialize<'de>")))]
 struct HistoryItem<SomeAction>
     where SomeAction: Action
 {
-    /// The path to the parent item
-    parent: Path,
+    /// The index of the parent item, None for the root
+    parent: Option<usize>,
 
     /// An action to be executed after the parent iten
     action: SomeAction,
+
+    /// Comments and markup attached to the action
+    annotation: Annotation<SomeAction::Position>,
+
+    /// Data about the item like timestamps or authors, by key
+    metadata: BTreeMap<String, String>,
+
+    /// The position among the siblings, lower ranks come first
+    rank: usize,
 }
 
 /// The game tree
@@ -44,17 +113,343 @@ pub struct Game<SomeAction>
     where SomeAction: Action
 {
     data: Vec<HistoryItem<SomeAction>>,
+    generation: usize,
+    version: Version,
+    next_rank: usize,
+    cache: StateCache<SomeAction::GameState>,
+    subscribers: Subscribers<SomeAction>,
+}
+
+/// The most recently reconstructed state and the id of its item
+///
+/// Lets `Game::get_state` replay only the actions after that item if
+/// the requested path passes through it, e.g. when inserting move
+/// after move. Readers sharing the tree only lock it to swap the
+/// state, not while replaying.
+struct StateCache<State>(Mutex<Option<(usize, Arc<State>)>>);
+
+impl<State> StateCache<State> {
+    /// Creates an empty cache
+    fn new() -> Self {
+        StateCache(Mutex::new(None))
+    }
+
+    /// Returns the cached index and state
+    fn get(&self) -> Option<(usize, Arc<State>)> {
+        self.lock().clone()
+    }
+
+    /// Replaces the cached index and state, None clears the cache
+    fn set(&self, cached: Option<(usize, State)>) {
+        *self.lock() = cached.map(|(index, state)| (index, Arc::new(state)));
+    }
+
+    /// Locks the cache, it is consistent even if a holder panicked
+    fn lock(&self) -> MutexGuard<'_, Option<(usize, Arc<State>)>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<State> fmt::Debug for StateCache<State> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self.lock() {
+            Some((id, _)) => write!(f, "StateCache({})", id),
+            None => write!(f, "StateCache(None)"),
+        }
+    }
+}
+
+/// A callback notified of changes to a game tree
+type Callback<SomeAction> = Box<dyn FnMut(&GameEvent<SomeAction>) + Send + Sync>;
+
+/// The callbacks subscribed to a game tree
+struct Subscribers<SomeAction> {
+    callbacks: Vec<(Subscription, Callback<SomeAction>)>,
+    /// The subscription handed out next, never reused
+    next: usize,
+}
+
+impl<SomeAction> Subscribers<SomeAction> {
+    /// Creates an empty list of subscribers
+    fn new() -> Self {
+        Subscribers {
+            callbacks: Vec::new(),
+            next: 0,
+        }
+    }
+
+    /// Calls every callback with the event, in the order they subscribed
+    fn notify(&mut self, event: &GameEvent<SomeAction>) {
+        for (_, callback) in &mut self.callbacks {
+            callback(event);
+        }
+    }
+}
+
+impl<SomeAction> fmt::Debug for Subscribers<SomeAction> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Subscribers({})", self.callbacks.len())
+    }
+}
+
+/// The generations handed out to game trees so far
+static GENERATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a generation no other game tree uses
+fn next_generation() -> usize {
+    GENERATIONS.fetch_add(1, Ordering::Relaxed)
+}
+
+/// The id of one game tree item
+///
+/// Ids are handed out by the tree holding the item and carry its
+/// generation, so ids of other trees and ids invalidated by pruning
+/// are not mistaken for items of this tree.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NodeId {
+    index: usize,
+    generation: usize,
 }
 
 /// The path to one game tree item
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Path {
+    /// The root of the tree, before the first action
+    Root,
+    /// The item with the given id
+    Node(NodeId),
+}
+
+impl From<NodeId> for Path {
+    fn from(id: NodeId) -> Path {
+        Path::Node(id)
+    }
+}
+
+/// The serialized form of a game tree
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "Game",
+        bound(deserialize = "SomeAction: Deserialize<'de>, \
+                             SomeAction::Position: Deserialize<'de>"))]
+struct GameData<SomeAction>
+    where SomeAction: Action
+{
+    items: Vec<HistoryItem<SomeAction>>,
+    version: Version,
+    next_rank: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<SomeAction> Serialize for Game<SomeAction>
+    where SomeAction: Action + Serialize,
+          SomeAction::Position: Serialize
+{
+    /// Serializes the items in insertion order along with their parent links
+    ///
+    /// Node ids are not part of the serialized tree, the items are
+    /// found again through the root of the restored tree.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut game = serializer.serialize_struct("Game", 3)?;
+        game.serialize_field("items", &self.data)?;
+        game.serialize_field("version", &self.version)?;
+        game.serialize_field("next_rank", &self.next_rank)?;
+        game.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, SomeAction> Deserialize<'de> for Game<SomeAction>
+    where SomeAction: Action + Deserialize<'de>,
+          SomeAction::Position: Deserialize<'de>
+{
+    /// Deserializes a tree whose items come after their parents
+    ///
+    /// The actions are not replayed, so the tree is trusted to hold
+    /// applicable actions only.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let data = GameData::<SomeAction>::deserialize(deserializer)?;
+        for (index, item) in data.items.iter().enumerate() {
+            if item.parent.is_some_and(|parent| parent >= index) {
+                return Err(D::Error::custom(format!("item {} precedes its parent", index)));
+            }
+        }
+
+        Ok(Game {
+            data: data.items,
+            generation: next_generation(),
+            version: data.version,
+            next_rank: data.next_rank,
+            cache: StateCache::new(),
+            subscribers: Subscribers::new(),
+        })
+    }
+}
+
+/// Something leading to a place in the game tree
 ///
-/// Stores the path as an id to the parent item.
+/// Lets methods accept both paths and node ids.
+pub trait AsPath {
+    /// Returns the path to the place
+    fn as_path(&self) -> Path;
+}
+
+impl AsPath for Path {
+    fn as_path(&self) -> Path {
+        *self
+    }
+}
+
+impl AsPath for NodeId {
+    fn as_path(&self) -> Path {
+        Path::Node(*self)
+    }
+}
+
+/// A version of a game tree
+///
+/// Every change to the tree yields a new version. Clients keep the
+/// version of the tree they based their view on and hand it back when
+/// inserting, so inserts against an outdated view can be detected.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Version(usize);
+
+/// The reasons an insert can fail
 #[derive(Clone, PartialEq, Eq, Debug)]
-pub enum Path {
-    /// There is no parent (we mean the trees root)
-    Empty,
-    /// The parents id
-    HistoryItemId(usize),
+pub enum InsertError<Reason> {
+    /// The tree was changed since the expected version was obtained
+    Stale { expected: Version, current: Version },
+    /// The parent is not an item of this tree
+    UnknownParent,
+    /// The action is not applicable after the parent, for the given reason
+    Rejected(Reason),
+}
+
+/// A change to a game tree, handed to the callbacks subscribed to it
+#[derive(Debug)]
+pub enum GameEvent<'game, SomeAction> {
+    /// The action was inserted after the parent as the item with the given id
+    Inserted {
+        id: NodeId,
+        parent: Path,
+        action: &'game SomeAction,
+    },
+    /// The item with the given id was moved to the given place among its siblings
+    ///
+    /// Promoting a variation moves it to place 0.
+    Moved { id: NodeId, place: usize },
+    /// The item at the given path was removed along with the given number of items
+    ///
+    /// All ids obtained before, including the path, are invalid now.
+    Pruned { at: Path, removed: usize },
+}
+
+/// A handle to a callback subscribed to a game tree
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Subscription(usize);
+
+/// An iterator over the items of a game tree
+///
+/// Yields the id and the action of every item, either depth-first
+/// (each variation completely before the next) or breadth-first (all
+/// items of a ply before the next). Variations come in the order of
+/// `Game::children`.
+pub struct TreeIter<'game, SomeAction>
+    where SomeAction: Action + 'game
+{
+    game: &'game Game<SomeAction>,
+    children: Vec<Vec<usize>>,
+    pending: VecDeque<usize>,
+    depth_first: bool,
+}
+
+impl<'game, SomeAction> TreeIter<'game, SomeAction>
+    where SomeAction: Action + 'game
+{
+    /// Creates an iterator starting at the root of the tree
+    fn new(game: &'game Game<SomeAction>, depth_first: bool) -> Self {
+        let mut children = vec![Vec::new(); game.data.len()];
+        let mut roots = Vec::new();
+        for (id, item) in game.data.iter().enumerate() {
+            match item.parent {
+                Some(parent) => children[parent].push(id),
+                None => roots.push(id),
+            }
+        }
+
+        roots.sort_by_key(|&id| game.data[id].rank);
+        for siblings in &mut children {
+            siblings.sort_by_key(|&id| game.data[id].rank);
+        }
+        let pending = roots.into_iter().collect();
+
+        TreeIter {
+            game,
+            children,
+            pending,
+            depth_first,
+        }
+    }
+}
+
+impl<'game, SomeAction> Iterator for TreeIter<'game, SomeAction>
+    where SomeAction: Action + 'game
+{
+    type Item = (NodeId, &'game SomeAction);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.pending.pop_front()?;
+        if self.depth_first {
+            for &child in self.children[id].iter().rev() {
+                self.pending.push_front(child);
+            }
+        } else {
+            self.pending.extend(self.children[id].iter().cloned());
+        }
+
+        Some((self.game.node(id), &self.game.data[id].action))
+    }
+}
+
+/// An iterator replaying the actions from the root to an item
+///
+/// Yields the number of actions applied so far, the action and the
+/// state after it for every item of the line, executing each action
+/// once.
+pub struct Replay<'game, SomeAction>
+    where SomeAction: Action + 'game
+{
+    game: &'game Game<SomeAction>,
+    line: ::std::vec::IntoIter<usize>,
+    state: SomeAction::GameState,
+    ply: usize,
+}
+
+impl<'game, SomeAction> Iterator for Replay<'game, SomeAction>
+    where SomeAction: Action + 'game
+{
+    type Item = (usize, &'game SomeAction, SomeAction::GameState);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let action = &self.game.data[self.line.next()?].action;
+        action.execute(&mut self.state);
+        self.ply += 1;
+
+        Some((self.ply, action, self.state.clone()))
+    }
+}
+
+impl<SomeAction> Default for Game<SomeAction>
+    where SomeAction: Action
+{
+    fn default() -> Self {
+        Game::new()
+    }
 }
 
 impl<SomeAction> Game<SomeAction>
@@ -62,49 +457,479 @@ impl<SomeAction> Game<SomeAction>
 {
     /// Creates a new game
     pub fn new() -> Self {
-        Game { data: Vec::new() }
+        Game {
+            data: Vec::new(),
+            generation: next_generation(),
+            version: Version(0),
+            next_rank: 0,
+            cache: StateCache::new(),
+            subscribers: Subscribers::new(),
+        }
+    }
+
+    /// Returns the current version of the tree
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Registers a callback to be called after every insert, move and prune
+    ///
+    /// Lets servers push changes to spectators without wrapping every
+    /// call. The callback gets the event only, the tree is borrowed
+    /// while it runs. Subscriptions are not serialized.
+    pub fn subscribe<F>(&mut self, callback: F) -> Subscription
+        where F: FnMut(&GameEvent<SomeAction>) + Send + Sync + 'static
+    {
+        let subscription = Subscription(self.subscribers.next);
+        self.subscribers.next += 1;
+        self.subscribers.callbacks.push((subscription, Box::new(callback)));
+        subscription
+    }
+
+    /// Removes the callback of the given subscription
+    ///
+    /// Returns false if it was removed before.
+    pub fn unsubscribe(&mut self, subscription: Subscription) -> bool {
+        let callbacks = &mut self.subscribers.callbacks;
+        let count = callbacks.len();
+        callbacks.retain(|&(other, _)| other != subscription);
+        callbacks.len() != count
+    }
+
+    /// Returns the id of the item at the given index
+    fn node(&self, index: usize) -> NodeId {
+        NodeId {
+            index,
+            generation: self.generation,
+        }
+    }
+
+    /// Returns the index of the item with the given id, if it is in this tree
+    fn index(&self, id: &NodeId) -> Option<usize> {
+        if id.generation == self.generation && id.index < self.data.len() {
+            Some(id.index)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index of the item at the given path, None for the root
+    ///
+    /// Fails if the path leads to an item of another tree.
+    fn locate(&self, at: &Path) -> Result<Option<usize>, ()> {
+        match *at {
+            Path::Root => Ok(None),
+            Path::Node(ref id) => self.index(id).map(Some).ok_or(()),
+        }
+    }
+
+    /// Checks if the id belongs to an item of this tree
+    pub fn contains(&self, id: &NodeId) -> bool {
+        self.index(id).is_some()
     }
 
     /// Inserts the action after parent
     ///
-    /// Does reconstruct the game state at path and applies action
-    pub fn insert(self: &mut Self, parent: &Path, action: SomeAction) -> Path {
-        let state = self.get_state(parent);
+    /// Does reconstruct the game state at path and applies action.
+    /// Returns None if the action is rejected, use `try_insert` to
+    /// learn why.
+    pub fn insert<P>(&mut self, parent: &P, action: SomeAction) -> Option<NodeId>
+        where P: AsPath
+    {
+        self.try_insert(parent, action).ok()
+    }
 
-        if action.test(&state) {
-            self.data.push(HistoryItem {
-                parent: parent.clone(),
-                action: action,
+    /// Inserts the action after parent
+    ///
+    /// Returns the id of the new item, or why the action was rejected.
+    pub fn try_insert<P>(&mut self,
+                         parent: &P,
+                         action: SomeAction)
+                         -> Result<NodeId, InsertError<SomeAction::Reason>>
+        where P: AsPath
+    {
+        let parent = parent.as_path();
+        let mut state = self.get_state(&parent).ok_or(InsertError::UnknownParent)?;
+        let index = self.locate(&parent).unwrap();
+
+        action.check(&state).map_err(InsertError::Rejected)?;
+
+        action.execute(&mut state);
+        self.cache.set(Some((self.data.len(), state)));
+        self.data.push(HistoryItem {
+            parent: index,
+            action,
+            annotation: Annotation::new(),
+            metadata: BTreeMap::new(),
+            rank: self.next_rank,
+        });
+        self.next_rank += 1;
+        self.version = Version(self.version.0 + 1);
+
+        let id = self.node(self.data.len() - 1);
+        self.subscribers.notify(&GameEvent::Inserted {
+            id,
+            parent,
+            action: &self.data[id.index].action,
+        });
+        Ok(id)
+    }
+
+    /// Inserts the action after parent if the tree is still at the expected version
+    ///
+    /// Fails without touching the tree if someone else changed it since
+    /// the expected version was obtained or if the action is rejected.
+    pub fn insert_versioned<P>(&mut self,
+                               expected: &Version,
+                               parent: &P,
+                               action: SomeAction)
+                               -> Result<NodeId, InsertError<SomeAction::Reason>>
+        where P: AsPath
+    {
+        if *expected != self.version {
+            return Err(InsertError::Stale {
+                expected: *expected,
+                current: self.version,
             });
+        }
 
-            Path::HistoryItemId(self.data.len() - 1)
-        } else {
-            Path::Empty
+        self.try_insert(parent, action)
+    }
+
+    /// Attaches the annotation to the item with the given id
+    ///
+    /// Replaces any previous annotation. Returns false for unknown ids.
+    pub fn annotate(&mut self,
+                    at: &NodeId,
+                    annotation: Annotation<SomeAction::Position>)
+                    -> bool {
+        match self.index(at) {
+            Some(index) => {
+                self.data[index].annotation = annotation;
+                self.version = Version(self.version.0 + 1);
+                true
+            }
+            None => false,
         }
     }
 
-    /// Returns the state at the given path
+    /// Returns the annotation of the item with the given id
+    pub fn annotation(&self, at: &NodeId) -> Option<&Annotation<SomeAction::Position>> {
+        self.index(at).map(|index| &self.data[index].annotation)
+    }
+
+    /// Stores a metadata value under the given key of the item with the given id
     ///
-    /// Does reapply all previous actions
-    pub fn get_state(self: &Self, at: &Path) -> SomeAction::GameState {
-        let mut state = SomeAction::GameState::new();
+    /// Replaces any previous value. Metadata is kept for reviews and
+    /// audit trails, it does not change the version. Returns false for
+    /// unknown ids.
+    pub fn set_metadata(&mut self, at: &NodeId, key: &str, value: &str) -> bool {
+        match self.index(at) {
+            Some(index) => {
+                self.data[index].metadata.insert(key.to_string(), value.to_string());
+                true
+            }
+            None => false,
+        }
+    }
 
-        if let &Path::HistoryItemId(up_to) = at {
-            let mut path = Vec::<usize>::new();
-            let mut curr = up_to;
+    /// Returns the metadata value stored under the given key of the item
+    pub fn metadata(&self, at: &NodeId, key: &str) -> Option<&str> {
+        self.index(at)
+            .and_then(|index| self.data[index].metadata.get(key))
+            .map(|value| value.as_str())
+    }
+
+    /// Removes the metadata value stored under the given key of the item
+    pub fn remove_metadata(&mut self, at: &NodeId, key: &str) -> Option<String> {
+        match self.index(at) {
+            Some(index) => self.data[index].metadata.remove(key),
+            None => None,
+        }
+    }
+
+    /// Returns the action of the item with the given id
+    pub fn action(&self, at: &NodeId) -> Option<&SomeAction> {
+        self.index(at).map(|index| &self.data[index].action)
+    }
+
+    /// Returns the path to the parent of the item with the given id
+    ///
+    /// Items inserted at the root have the root as parent. Returns None
+    /// for unknown ids.
+    pub fn parent(&self, at: &NodeId) -> Option<Path> {
+        self.index(at).map(|index| match self.data[index].parent {
+            Some(parent) => Path::Node(self.node(parent)),
+            None => Path::Root,
+        })
+    }
+
+    /// Returns the items inserted directly after the given path
+    ///
+    /// The main line comes first, the other variations follow in the
+    /// order they were inserted unless moved with `move_variation`.
+    pub fn children<P>(&self, at: &P) -> Vec<NodeId>
+        where P: AsPath
+    {
+        let parent = match self.locate(&at.as_path()) {
+            Ok(parent) => parent,
+            Err(_) => return Vec::new(),
+        };
 
-            while let Path::HistoryItemId(next) = self.data[curr].parent {
-                path.push(curr);
-                curr = next;
+        let mut children: Vec<usize> = (0..self.data.len())
+            .filter(|&index| self.data[index].parent == parent)
+            .collect();
+        children.sort_by_key(|&index| self.data[index].rank);
+        children.into_iter().map(|index| self.node(index)).collect()
+    }
+
+    /// Moves the variation with the given id to the given place among its siblings
+    ///
+    /// Place 0 makes it the main line, places past the last sibling
+    /// make it the last variation. Ids stay valid, the version
+    /// changes. Returns false for unknown ids.
+    pub fn move_variation(&mut self, at: &NodeId, place: usize) -> bool {
+        let parent = match self.parent(at) {
+            Some(parent) => parent,
+            None => return false,
+        };
+
+        let mut siblings = self.children(&parent);
+        let ranks: Vec<usize> = siblings.iter()
+            .map(|sibling| self.data[sibling.index].rank)
+            .collect();
+
+        siblings.retain(|sibling| sibling != at);
+        let place = place.min(siblings.len());
+        siblings.insert(place, *at);
+        for (sibling, rank) in siblings.into_iter().zip(ranks) {
+            self.data[sibling.index].rank = rank;
+        }
+
+        self.version = Version(self.version.0 + 1);
+        self.subscribers.notify(&GameEvent::Moved { id: *at, place });
+        true
+    }
+
+    /// Makes the variation with the given id the main line of its parent
+    pub fn promote(&mut self, at: &NodeId) -> bool {
+        self.move_variation(at, 0)
+    }
+
+    /// Returns the other items inserted after the parent of the given item
+    pub fn siblings(&self, at: &NodeId) -> Vec<NodeId> {
+        match self.parent(at) {
+            Some(parent) => self.children(&parent).into_iter().filter(|id| id != at).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Removes the item at the given path and everything inserted after it
+    ///
+    /// Pruning the root empties the tree. The remaining items are moved
+    /// together, so ids obtained before pruning are invalid and the
+    /// version changes. Returns the number of removed items.
+    pub fn prune<P>(&mut self, at: &P) -> usize
+        where P: AsPath
+    {
+        let mut removed = vec![false; self.data.len()];
+        match self.locate(&at.as_path()) {
+            Ok(Some(index)) => removed[index] = true,
+            Ok(None) => removed.iter_mut().for_each(|r| *r = true),
+            Err(_) => return 0,
+        }
+
+        // parents are inserted before their children
+        for index in 0..self.data.len() {
+            if let Some(parent) = self.data[index].parent {
+                removed[index] = removed[index] || removed[parent];
+            }
+        }
+
+        let mut remap = vec![None; self.data.len()];
+        let mut next = 0;
+        for (index, &gone) in removed.iter().enumerate() {
+            if !gone {
+                remap[index] = Some(next);
+                next += 1;
+            }
+        }
+
+        self.cache.set(None);
+        let old = std::mem::take(&mut self.data);
+        for (index, mut item) in old.into_iter().enumerate() {
+            if removed[index] {
+                continue;
+            }
+            item.parent = item.parent.map(|parent| remap[parent].unwrap());
+            self.data.push(item);
+        }
+
+        let count = removed.len() - self.data.len();
+        if count > 0 {
+            self.generation = next_generation();
+            self.version = Version(self.version.0 + 1);
+            self.subscribers.notify(&GameEvent::Pruned {
+                at: at.as_path(),
+                removed: count,
+            });
+        }
+        count
+    }
+
+    /// Starts a new tree at the state of the given path with everything inserted after it
+    ///
+    /// The new tree begins with the setup actions recreating the state
+    /// at the path, see `Setup`, followed by copies of the items after
+    /// the path. The actions leading there, their annotations and their
+    /// variations are left out. The copied items are not checked again.
+    /// Extracting the root copies the whole tree. Returns None for
+    /// paths of other trees.
+    pub fn extract<P>(&self, at: &P) -> Option<Game<SomeAction>>
+        where P: AsPath,
+              SomeAction: Setup + Clone
+    {
+        let start = self.locate(&at.as_path()).ok()?;
+        let mut game = Game::new();
+
+        // the setup items form a line, the copied items continue its last one
+        let mut attach = None;
+        if start.is_some() {
+            for action in SomeAction::setup(&self.get_state(at)?) {
+                game.data.push(HistoryItem {
+                    parent: attach,
+                    action,
+                    annotation: Annotation::new(),
+                    metadata: BTreeMap::new(),
+                    rank: game.next_rank,
+                });
+                game.next_rank += 1;
+                attach = Some(game.data.len() - 1);
             }
+        }
+
+        // parents are inserted before their children
+        let first = start.map_or(0, |start| start + 1);
+        let mut remap = vec![None; self.data.len()];
+        for (index, item) in self.data.iter().enumerate().skip(first) {
+            let parent = if item.parent == start {
+                attach
+            } else {
+                match item.parent.and_then(|parent| remap[parent]) {
+                    Some(parent) => Some(parent),
+                    None => continue,
+                }
+            };
+
+            remap[index] = Some(game.data.len());
+            game.data.push(HistoryItem {
+                parent,
+                action: item.action.clone(),
+                annotation: item.annotation.clone(),
+                metadata: item.metadata.clone(),
+                rank: item.rank,
+            });
+        }
+        game.next_rank = game.next_rank.max(self.next_rank);
+        Some(game)
+    }
+
+    /// Returns an iterator over all items, one variation after the other
+    pub fn iter_dfs(&self) -> TreeIter<'_, SomeAction> {
+        TreeIter::new(self, true)
+    }
+
+    /// Returns an iterator over all items, ordered by their depth
+    pub fn iter_bfs(&self) -> TreeIter<'_, SomeAction> {
+        TreeIter::new(self, false)
+    }
+
+    /// Returns an iterator over the last items of all variations
+    pub fn iter_leaves<'game>(&'game self)
+                              -> impl Iterator<Item = (NodeId, &'game SomeAction)> + 'game {
+        let tree = TreeIter::new(self, true);
+        let leaves: Vec<bool> = tree.children.iter().map(|children| children.is_empty()).collect();
+        tree.filter(move |(id, _)| leaves[id.index])
+    }
+
+    /// Returns the path reached by following the given actions from the root
+    ///
+    /// Every action has to equal the action of a child of the item
+    /// reached so far, the main line is preferred. Returns None if the
+    /// actions leave the tree.
+    pub fn find_path(&self, actions: &[SomeAction]) -> Option<Path>
+        where SomeAction: PartialEq
+    {
+        let mut path = Path::Root;
+        for action in actions {
+            let next = self.children(&path)
+                .into_iter()
+                .find(|child| self.data[child.index].action == *action)?;
+            path = Path::Node(next);
+        }
+
+        Some(path)
+    }
 
-            path.push(curr);
+    /// Returns an iterator over the actions leading to the given path and the states after them
+    ///
+    /// The iterator is empty for the root and paths of other trees.
+    pub fn replay<P>(&self, at: &P) -> Replay<'_, SomeAction>
+        where P: AsPath
+    {
+        let mut line = Vec::new();
+        let mut current = self.locate(&at.as_path()).unwrap_or(None);
+        while let Some(index) = current {
+            line.push(index);
+            current = self.data[index].parent;
+        }
+        line.reverse();
 
-            for idx in path.iter().rev() {
-                self.data[*idx].action.execute(&mut state);
+        Replay {
+            game: self,
+            line: line.into_iter(),
+            state: SomeAction::GameState::new(),
+            ply: 0,
+        }
+    }
+
+    /// Returns the state at the given path
+    ///
+    /// Does reapply all previous actions, starting from the cached
+    /// state if the path passes through its item. Returns None if the
+    /// path leads to an item of another tree, e.g. one obtained before
+    /// pruning.
+    pub fn get_state<P>(&self, at: &P) -> Option<SomeAction::GameState>
+        where P: AsPath
+    {
+        let up_to = match self.locate(&at.as_path()) {
+            Ok(Some(up_to)) => up_to,
+            Ok(None) => return Some(SomeAction::GameState::new()),
+            Err(_) => return None,
+        };
+
+        let cache = self.cache.get();
+        let cached = cache.as_ref().map(|&(index, _)| index);
+
+        let mut path = Vec::<usize>::new();
+        let mut curr = Some(up_to);
+        while let Some(index) = curr {
+            if Some(index) == cached {
+                break;
             }
+            path.push(index);
+            curr = self.data[index].parent;
+        }
+
+        let mut state = match (curr, cache) {
+            (Some(_), Some((_, state))) => (*state).clone(),
+            _ => SomeAction::GameState::new(),
+        };
+        for index in path.iter().rev() {
+            self.data[*index].action.execute(&mut state);
         }
 
-        state
+        self.cache.set(Some((up_to, state.clone())));
+        Some(state)
     }
 }
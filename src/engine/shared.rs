@@ -0,0 +1,91 @@
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use engine::{Action, AsPath, Game, InsertError, NodeId, Version};
+
+/// A game tree shared between threads
+///
+/// Clones refer to the same tree. Any number of readers can
+/// reconstruct states at the same time, a writer waits for them and
+/// blocks them while changing the tree. Needs actions that are
+/// `Send + Sync` and states that are `Send` to cross threads.
+pub struct SharedGame<SomeAction>(Arc<RwLock<Game<SomeAction>>>) where SomeAction: Action;
+
+impl<SomeAction> Clone for SharedGame<SomeAction>
+    where SomeAction: Action
+{
+    fn clone(&self) -> Self {
+        SharedGame(Arc::clone(&self.0))
+    }
+}
+
+impl<SomeAction> Default for SharedGame<SomeAction>
+    where SomeAction: Action
+{
+    fn default() -> Self {
+        SharedGame::new()
+    }
+}
+
+impl<SomeAction> From<Game<SomeAction>> for SharedGame<SomeAction>
+    where SomeAction: Action
+{
+    fn from(game: Game<SomeAction>) -> Self {
+        SharedGame(Arc::new(RwLock::new(game)))
+    }
+}
+
+impl<SomeAction> SharedGame<SomeAction>
+    where SomeAction: Action
+{
+    /// Creates a new shared game
+    pub fn new() -> Self {
+        SharedGame::from(Game::new())
+    }
+
+    /// Locks the tree for reading
+    ///
+    /// The tree stays usable if a writer panicked, every change to it
+    /// is completed before subscribers are notified.
+    pub fn read(&self) -> RwLockReadGuard<'_, Game<SomeAction>> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the tree for writing
+    pub fn write(&self) -> RwLockWriteGuard<'_, Game<SomeAction>> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the current version of the tree
+    pub fn version(&self) -> Version {
+        self.read().version()
+    }
+
+    /// Returns the state at the given path, see `Game::get_state`
+    pub fn get_state<P>(&self, at: &P) -> Option<SomeAction::GameState>
+        where P: AsPath
+    {
+        self.read().get_state(at)
+    }
+
+    /// Inserts the action after parent, see `Game::try_insert`
+    pub fn insert<P>(&self,
+                     parent: &P,
+                     action: SomeAction)
+                     -> Result<NodeId, InsertError<SomeAction::Reason>>
+        where P: AsPath
+    {
+        self.write().try_insert(parent, action)
+    }
+
+    /// Inserts the action if the tree is still at the expected version, see
+    /// `Game::insert_versioned`
+    pub fn insert_versioned<P>(&self,
+                               expected: &Version,
+                               parent: &P,
+                               action: SomeAction)
+                               -> Result<NodeId, InsertError<SomeAction::Reason>>
+        where P: AsPath
+    {
+        self.write().insert_versioned(expected, parent, action)
+    }
+}
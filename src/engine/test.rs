@@ -1,5 +1,15 @@
-use super::{Game, GameState, Action, Path};
+use super::{Game, GameCursor, GameEvent, GameState, SharedGame, Setup, Action, NodeId, Path,
+            InsertError, Annotation, MoveMark, Markup, PositionHash};
+use go::board::Position;
 
+use std::fmt;
+use std::sync::mpsc;
+use std::thread;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone)]
 struct SimpleGameState {
     acc: i32,
 }
@@ -10,25 +20,50 @@ impl GameState for SimpleGameState {
     }
 }
 
+impl PositionHash for SimpleGameState {
+    fn position_hash(&self) -> u64 {
+        self.acc as u64
+    }
+}
+
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 enum SimpleAction {
     Inc,
     Dec,
 }
 
+impl fmt::Display for SimpleAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SimpleAction::Inc => write!(f, "inc"),
+            SimpleAction::Dec => write!(f, "\"dec\""),
+        }
+    }
+}
+
+impl Setup for SimpleAction {
+    fn setup(state: &SimpleGameState) -> Vec<SimpleAction> {
+        (0..state.acc).map(|_| SimpleAction::Inc).collect()
+    }
+}
+
 impl Action for SimpleAction {
     type GameState = SimpleGameState;
+    type Reason = &'static str;
+    type Position = Position;
 
-    fn test(self: &Self, state: &SimpleGameState) -> bool {
-        match self {
-            &SimpleAction::Inc => true,
-            &SimpleAction::Dec => state.acc > 0,
+    fn check(&self, state: &SimpleGameState) -> Result<(), &'static str> {
+        match *self {
+            SimpleAction::Dec if state.acc <= 0 => Err("negative"),
+            _ => Ok(()),
         }
     }
 
-    fn execute(self: &Self, state: &mut SimpleGameState) {
-        match self {
-            &SimpleAction::Inc => state.acc += 1,
-            &SimpleAction::Dec => state.acc -= 1,
+    fn execute(&self, state: &mut SimpleGameState) {
+        match *self {
+            SimpleAction::Inc => state.acc += 1,
+            SimpleAction::Dec => state.acc -= 1,
         }
     }
 }
@@ -36,19 +71,494 @@ impl Action for SimpleAction {
 #[test]
 fn tree() {
     let mut g = Game::<SimpleAction>::new();
-    let root_cursor = Path::Empty;
+    let root_cursor = Path::Root;
+
+    let parent_cursor = g.insert(&root_cursor, SimpleAction::Inc).unwrap();
+    assert!(g.get_state(&parent_cursor).unwrap().acc == 1);
+
+    assert!(g.insert(&root_cursor, SimpleAction::Dec).is_none());
+
+    let child_0 = g.insert(&parent_cursor, SimpleAction::Dec).unwrap();
+    assert!(g.get_state(&child_0).unwrap().acc == 0);
+
+    let child_1 = g.insert(&parent_cursor, SimpleAction::Inc).unwrap();
+    assert!(child_1 != child_0);
+    assert!(g.get_state(&child_1).unwrap().acc == 2);
+}
+
+#[test]
+fn try_insert() {
+    let mut g = Game::<SimpleAction>::new();
+
+    assert_eq!(g.try_insert(&Path::Root, SimpleAction::Dec),
+               Err(InsertError::Rejected("negative")));
+    let first = g.try_insert(&Path::Root, SimpleAction::Inc).unwrap();
+    assert_eq!(g.children(&Path::Root), vec![first]);
+}
+
+#[test]
+fn foreign_ids() {
+    let mut g = Game::<SimpleAction>::new();
+    let mut other = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    other.insert(&Path::Root, SimpleAction::Inc).unwrap();
+
+    // the other tree has an item at the same place
+    assert!(g.contains(&first));
+    assert!(!other.contains(&first));
+    assert_eq!(other.try_insert(&first, SimpleAction::Inc),
+               Err(InsertError::UnknownParent));
+    assert!(other.action(&first).is_none());
+    assert!(other.children(&first).is_empty());
+    assert!(other.get_state(&first).is_none());
+
+    // pruning invalidates the ids handed out before
+    let second = g.insert(&first, SimpleAction::Inc).unwrap();
+    g.prune(&second);
+    assert!(!g.contains(&first));
+    assert!(g.get_state(&first).is_none());
+    assert!(g.contains(&g.children(&Path::Root)[0]));
+}
+
+#[test]
+fn versioned_insert() {
+    let mut g = Game::<SimpleAction>::new();
+    let initial = g.version();
+
+    let first = g.insert_versioned(&initial, &Path::Root, SimpleAction::Inc);
+    assert!(first.is_ok());
+    assert!(g.version() != initial);
+
+    // the opponent's view is outdated now
+    assert_eq!(g.insert_versioned(&initial, &Path::Root, SimpleAction::Inc),
+               Err(InsertError::Stale {
+                   expected: initial,
+                   current: g.version(),
+               }));
+
+    let current = g.version();
+    assert_eq!(g.insert_versioned(&current, &Path::Root, SimpleAction::Dec),
+               Err(InsertError::Rejected("negative")));
+    assert!(g.version() == current);
+
+    let second = g.insert_versioned(&current, &first.unwrap(), SimpleAction::Dec);
+    assert!(g.get_state(&second.unwrap()).unwrap().acc == 0);
+}
+
+#[test]
+fn annotations() {
+    let mut g = Game::<SimpleAction>::new();
+    let cursor = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+
+    assert!(g.annotation(&cursor).unwrap().is_empty());
+
+    let before = g.version();
+    let annotation = Annotation {
+        mark: Some(MoveMark::Good { emphasized: true }),
+        labels: vec![(Position { x: 2, y: 2 }, "A".to_string())],
+        markup: vec![(Position { x: 3, y: 3 }, Markup::Triangle)],
+        ..Annotation::comment("a fine move")
+    };
+    assert!(g.annotate(&cursor, annotation.clone()));
+    assert!(g.version() != before);
+
+    // the annotation does not change the state
+    assert_eq!(g.annotation(&cursor), Some(&annotation));
+    assert!(g.get_state(&cursor).unwrap().acc == 1);
+}
+
+#[test]
+fn metadata() {
+    let mut g = Game::<SimpleAction>::new();
+    let cursor = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    assert!(g.metadata(&cursor, "author").is_none());
+
+    let before = g.version();
+    assert!(g.set_metadata(&cursor, "author", "alice"));
+    assert!(g.set_metadata(&cursor, "author", "bob"));
+    assert!(g.set_metadata(&cursor, "time", "2017-05-01T12:00:00Z"));
+    assert!(g.version() == before);
+    assert_eq!(g.metadata(&cursor, "author"), Some("bob"));
+
+    assert_eq!(g.remove_metadata(&cursor, "time"), Some("2017-05-01T12:00:00Z".to_string()));
+    assert!(g.metadata(&cursor, "time").is_none());
+
+    let other = Game::<SimpleAction>::new().insert(&Path::Root, SimpleAction::Inc).unwrap();
+    assert!(!g.set_metadata(&other, "author", "eve"));
+}
+
+#[test]
+fn navigation() {
+    let mut g = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let main = g.insert(&first, SimpleAction::Inc).unwrap();
+    let variation = g.insert(&first, SimpleAction::Dec).unwrap();
+    let other = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+
+    assert_eq!(g.children(&Path::Root), vec![first, other]);
+    assert_eq!(g.children(&first), vec![main, variation]);
+    assert!(g.children(&main).is_empty());
+
+    assert_eq!(g.parent(&variation), Some(Path::Node(first)));
+    assert_eq!(g.parent(&first), Some(Path::Root));
+
+    assert_eq!(g.siblings(&main), vec![variation]);
+    assert_eq!(g.siblings(&other), vec![first]);
+}
+
+#[test]
+fn traversal() {
+    let mut g = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let main = g.insert(&first, SimpleAction::Inc).unwrap();
+    let other = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let variation = g.insert(&first, SimpleAction::Dec).unwrap();
+    let deep = g.insert(&main, SimpleAction::Dec).unwrap();
+
+    let dfs: Vec<NodeId> = g.iter_dfs().map(|(id, _)| id).collect();
+    assert_eq!(dfs, vec![first, main, deep, variation, other]);
+
+    let bfs: Vec<NodeId> = g.iter_bfs().map(|(id, _)| id).collect();
+    assert_eq!(bfs, vec![first, other, main, variation, deep]);
+
+    let leaves: Vec<NodeId> = g.iter_leaves().map(|(id, _)| id).collect();
+    assert_eq!(leaves, vec![deep, variation, other]);
+
+    assert!(match g.iter_dfs().nth(2) {
+        Some((id, &SimpleAction::Dec)) => id == deep,
+        _ => false,
+    });
+    assert_eq!(Game::<SimpleAction>::new().iter_leaves().count(), 0);
+}
+
+#[test]
+fn prune() {
+    let mut g = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let main = g.insert(&first, SimpleAction::Inc).unwrap();
+    let variation = g.insert(&first, SimpleAction::Inc).unwrap();
+    g.insert(&variation, SimpleAction::Inc).unwrap();
+    g.insert(&main, SimpleAction::Dec).unwrap();
+
+    let before = g.version();
+    assert_eq!(g.prune(&variation), 2);
+    assert!(g.version() != before);
+    assert_eq!(g.iter_dfs().count(), 3);
+
+    // the main line was moved together
+    let leaf = g.iter_leaves().next().unwrap().0;
+    assert!(g.get_state(&leaf).unwrap().acc == 1);
+    let first = g.children(&Path::Root)[0];
+    assert_eq!(g.children(&first).len(), 1);
+
+    assert_eq!(g.prune(&variation), 0);
+    assert_eq!(g.prune(&Path::Root), 3);
+    assert_eq!(g.iter_dfs().count(), 0);
+}
+
+#[test]
+fn promote() {
+    let mut g = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let a = g.insert(&first, SimpleAction::Inc).unwrap();
+    let b = g.insert(&first, SimpleAction::Dec).unwrap();
+    let c = g.insert(&first, SimpleAction::Inc).unwrap();
+
+    let before = g.version();
+    assert!(g.promote(&c));
+    assert!(g.version() != before);
+    assert_eq!(g.children(&first), vec![c, a, b]);
+
+    assert!(g.move_variation(&c, 10));
+    assert_eq!(g.children(&first), vec![a, b, c]);
+
+    assert!(g.move_variation(&a, 1));
+    assert_eq!(g.children(&first), vec![b, a, c]);
+
+    // new variations come last, the traversals follow the order
+    let d = g.insert(&first, SimpleAction::Dec).unwrap();
+    assert_eq!(g.children(&first), vec![b, a, c, d]);
+    assert_eq!(g.iter_dfs().nth(1).unwrap().0, b);
+}
+
+#[test]
+fn cached_states() {
+    let mut g = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let mut main = first;
+    for _ in 0..4 {
+        main = g.insert(&main, SimpleAction::Inc).unwrap();
+    }
+    let variation = g.insert(&first, SimpleAction::Dec).unwrap();
+
+    // switching between lines does not reuse a state of the other line
+    assert!(g.get_state(&main).unwrap().acc == 5);
+    assert!(g.get_state(&variation).unwrap().acc == 0);
+    assert!(g.get_state(&main).unwrap().acc == 5);
+    assert!(g.get_state(&first).unwrap().acc == 1);
+    assert!(g.get_state(&Path::Root).unwrap().acc == 0);
+
+    let next = g.insert(&main, SimpleAction::Dec).unwrap();
+    assert!(g.get_state(&next).unwrap().acc == 4);
+
+    g.prune(&variation);
+    let leaf = g.iter_leaves().next().unwrap().0;
+    assert!(g.get_state(&leaf).unwrap().acc == 4);
+}
+
+#[test]
+fn cursor() {
+    let mut g = Game::<SimpleAction>::new();
+    let mut cursor = GameCursor::new();
+
+    let first = cursor.advance(&mut g, SimpleAction::Inc).unwrap();
+    cursor.advance(&mut g, SimpleAction::Inc).unwrap();
+    assert_eq!(cursor.advance(&mut g, SimpleAction::Inc).map(|_| cursor.depth()), Ok(3));
+    assert!(cursor.state().acc == 3);
 
-    let parent_cursor = g.insert(&root_cursor, SimpleAction::Inc);
-    assert!(g.get_state(&parent_cursor).acc == 1);
+    assert!(cursor.undo());
+    assert!(cursor.undo());
+    assert!(cursor.state().acc == 1);
+    assert_eq!(cursor.path(), Path::Node(first));
+    assert!(cursor.redo(&g));
+    assert!(cursor.state().acc == 2);
+
+    // redo retraces the undone variation
+    assert!(cursor.undo());
+    let variation = cursor.advance(&mut g, SimpleAction::Dec).unwrap();
+    assert!(cursor.undo());
+    assert!(cursor.undo());
+    assert!(!cursor.undo());
+    assert!(cursor.redo(&g));
+    assert!(cursor.redo(&g));
+    assert!(!cursor.redo(&g));
+    assert_eq!(cursor.path(), Path::Node(variation));
+
+    // without undone items redo follows the main line
+    assert!(cursor.goto(&g, &first));
+    assert!(cursor.redo(&g));
+    assert!(cursor.redo(&g));
+    assert!(cursor.state().acc == 3);
+    assert!(cursor.goto(&g, &Path::Root));
+    assert!(cursor.state().acc == 0);
+
+    assert_eq!(cursor.advance(&mut g, SimpleAction::Dec),
+               Err(InsertError::Rejected("negative")));
+    let other = Game::<SimpleAction>::new().insert(&Path::Root, SimpleAction::Inc).unwrap();
+    assert!(!cursor.goto(&g, &other));
+}
+
+#[test]
+fn replay() {
+    let mut g = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let second = g.insert(&first, SimpleAction::Inc).unwrap();
+    g.insert(&first, SimpleAction::Inc).unwrap();
+    let third = g.insert(&second, SimpleAction::Dec).unwrap();
+
+    let replay: Vec<(usize, i32)> = g.replay(&third)
+        .map(|(ply, _, state)| (ply, state.acc))
+        .collect();
+    assert_eq!(replay, vec![(1, 1), (2, 2), (3, 1)]);
+    assert!(matches!(g.replay(&third).last(), Some((3, &SimpleAction::Dec, _))));
+
+    assert_eq!(g.replay(&Path::Root).count(), 0);
+    let other = Game::<SimpleAction>::new().insert(&Path::Root, SimpleAction::Inc).unwrap();
+    assert_eq!(g.replay(&other).count(), 0);
+}
+
+#[test]
+fn find() {
+    let mut g = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let main = g.insert(&first, SimpleAction::Inc).unwrap();
+    let variation = g.insert(&first, SimpleAction::Dec).unwrap();
+    g.insert(&first, SimpleAction::Inc).unwrap();
+
+    assert_eq!(g.find_path(&[]), Some(Path::Root));
+    assert_eq!(g.find_path(&[SimpleAction::Inc, SimpleAction::Dec]),
+               Some(Path::Node(variation)));
+    assert_eq!(g.find_path(&[SimpleAction::Inc, SimpleAction::Inc]), Some(Path::Node(main)));
+    assert_eq!(g.find_path(&[SimpleAction::Dec]), None);
+
+    assert_eq!(g.find_position(0), Some(variation));
+    assert_eq!(g.find_position(2), Some(main));
+    assert_eq!(g.find_position(3), None);
+}
+
+#[test]
+fn extract() {
+    let mut g = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let second = g.insert(&first, SimpleAction::Inc).unwrap();
+    g.insert(&first, SimpleAction::Dec).unwrap();
+    let third = g.insert(&second, SimpleAction::Dec).unwrap();
+    let fourth = g.insert(&third, SimpleAction::Inc).unwrap();
+    g.insert(&third, SimpleAction::Dec).unwrap();
+    g.insert(&fourth, SimpleAction::Inc).unwrap();
+    g.annotate(&third, Annotation { comment: Some("prefix".to_string()), ..Annotation::new() });
+    g.annotate(&fourth, Annotation { comment: Some("study".to_string()), ..Annotation::new() });
+
+    // the three actions leading to the study are replaced by one setup action
+    let study = g.extract(&third).unwrap();
+    assert_eq!(study.iter_dfs().count(), 4);
+    assert_eq!(study.children(&Path::Root).len(), 1);
+    let setup = study.children(&Path::Root)[0];
+    assert_eq!(study.get_state(&setup).unwrap().acc, 1);
+    assert!(study.annotation(&setup).unwrap().comment.is_none());
+
+    let continuations = study.children(&setup);
+    assert_eq!(continuations.len(), 2);
+    assert_eq!(study.get_state(&continuations[0]).unwrap().acc, 2);
+    assert_eq!(study.get_state(&continuations[1]).unwrap().acc, 0);
+    assert_eq!(study.annotation(&continuations[0]).unwrap().comment, Some("study".to_string()));
+    assert!(!study.contains(&third));
+    assert_eq!(study.find_path(&[SimpleAction::Inc, SimpleAction::Inc, SimpleAction::Inc]),
+               Some(Path::Node(study.children(&continuations[0])[0])));
+
+    assert_eq!(g.extract(&Path::Root).unwrap().iter_dfs().count(), 7);
+    g.prune(&fourth);
+    assert!(g.extract(&third).is_none());
+}
+
+#[test]
+fn to_dot() {
+    let mut g = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    g.insert(&first, SimpleAction::Inc).unwrap();
+    g.insert(&first, SimpleAction::Dec).unwrap();
+
+    assert_eq!(g.to_dot(),
+               "digraph game {\n    root [label=\"root\"];\n    n0 [label=\"1: inc\"];\n    \
+                root -> n0 [style=bold];\n    n1 [label=\"2: inc\"];\n    \
+                n0 -> n1 [style=bold];\n    n2 [label=\"2: \\\"dec\\\"\"];\n    \
+                n0 -> n2;\n}\n");
+}
+
+#[test]
+fn events() {
+    let mut g = Game::<SimpleAction>::new();
+    let (sender, receiver) = mpsc::channel();
+    let subscription = g.subscribe(move |event: &GameEvent<SimpleAction>| {
+        let event = match *event {
+            GameEvent::Inserted { parent: Path::Root, action, .. } => format!("{} at root", action),
+            GameEvent::Inserted { action, .. } => format!("{} after item", action),
+            GameEvent::Moved { place, .. } => format!("moved to {}", place),
+            GameEvent::Pruned { removed, .. } => format!("pruned {}", removed),
+        };
+        sender.send(event).unwrap();
+    });
+
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    g.insert(&first, SimpleAction::Inc).unwrap();
+    assert!(g.insert(&Path::Root, SimpleAction::Dec).is_none());
+    let variation = g.insert(&first, SimpleAction::Dec).unwrap();
+    assert!(g.promote(&variation));
+    assert!(g.move_variation(&variation, 5));
+    assert_eq!(g.prune(&first), 3);
+
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(),
+               vec!["inc at root",
+                    "inc after item",
+                    "\"dec\" after item",
+                    "moved to 0",
+                    "moved to 1",
+                    "pruned 3"]);
+
+    assert!(g.unsubscribe(subscription));
+    assert!(!g.unsubscribe(subscription));
+    g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    assert!(receiver.try_iter().next().is_none());
+}
+
+#[test]
+fn shared() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Game<SimpleAction>>();
+    assert_send_sync::<SharedGame<SimpleAction>>();
+
+    let game = SharedGame::<SimpleAction>::new();
+    let mut line = vec![game.insert(&Path::Root, SimpleAction::Inc).unwrap()];
+    for _ in 0..20 {
+        let last = *line.last().unwrap();
+        line.push(game.insert(&last, SimpleAction::Inc).unwrap());
+    }
+
+    let readers: Vec<_> = (0..4)
+        .map(|reader| {
+            let game = game.clone();
+            let line = line.clone();
+            thread::spawn(move || {
+                for (ply, id) in line.iter().enumerate().skip(reader) {
+                    assert_eq!(game.get_state(id).unwrap().acc, ply as i32 + 1);
+                }
+            })
+        })
+        .collect();
+
+    let version = game.version();
+    let variation = game.insert_versioned(&version, &line[0], SimpleAction::Dec).unwrap();
+    assert!(matches!(game.insert_versioned(&version, &line[0], SimpleAction::Dec),
+                     Err(InsertError::Stale { .. })));
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    assert_eq!(game.get_state(&variation).unwrap().acc, 0);
+    assert_eq!(game.read().children(&line[0]).len(), 2);
+}
+
+#[test]
+fn transpositions() {
+    let mut g = Game::<SimpleAction>::new();
+    let one = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let two = g.insert(&one, SimpleAction::Inc).unwrap();
+    let three = g.insert(&two, SimpleAction::Inc).unwrap();
+    let back = g.insert(&two, SimpleAction::Dec).unwrap();
+    let again = g.insert(&back, SimpleAction::Inc).unwrap();
+    let four = g.insert(&again, SimpleAction::Inc).unwrap();
+
+    assert_eq!(g.transpositions(),
+               vec![vec![one, back], vec![two, again], vec![three, four]]);
+
+    // the continuations of both move orders are merged
+    let dag = g.dag();
+    assert_eq!(dag.canonical(&again), Some(two));
+    assert_eq!(dag.canonical(&four), Some(three));
+    assert_eq!(dag.successors(&Path::Root), &[one]);
+    assert_eq!(dag.successors(&Path::Node(again)), &[three, one]);
+    assert!(dag.successors(&Path::Node(three)).is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    use serde_json;
+
+    let mut g = Game::<SimpleAction>::new();
+    let first = g.insert(&Path::Root, SimpleAction::Inc).unwrap();
+    let main = g.insert(&first, SimpleAction::Inc).unwrap();
+    let variation = g.insert(&first, SimpleAction::Dec).unwrap();
+    g.insert(&variation, SimpleAction::Inc).unwrap();
+    g.promote(&variation);
+    g.annotate(&main, Annotation::comment("slow"));
+    g.set_metadata(&variation, "author", "alice");
 
-    let invalid_cursor = g.insert(&root_cursor, SimpleAction::Dec);
-    assert!(invalid_cursor == Path::Empty);
+    let json = serde_json::to_string(&g).unwrap();
+    let restored: Game<SimpleAction> = serde_json::from_str(&json).unwrap();
+    assert!(restored.version() == g.version());
 
-    let child_0 = g.insert(&parent_cursor, SimpleAction::Dec);
-    assert!(child_0 != Path::Empty);
-    assert!(g.get_state(&child_0).acc == 0);
+    // the order of the variations is kept
+    let first = restored.children(&Path::Root)[0];
+    let children = restored.children(&first);
+    assert_eq!(children.len(), 2);
+    assert_eq!(restored.metadata(&children[0], "author"), Some("alice"));
+    assert_eq!(restored.annotation(&children[1]).unwrap().comment,
+               Some("slow".to_string()));
+    let leaf = restored.iter_leaves().next().unwrap().0;
+    assert!(restored.get_state(&leaf).unwrap().acc == 1);
 
-    let child_1 = g.insert(&parent_cursor, SimpleAction::Inc);
-    assert!(child_1 != Path::Empty);
-    assert!(g.get_state(&child_1).acc == 2);
+    // items may not come before their parents
+    let broken = json.replacen("\"parent\":null", "\"parent\":3", 1);
+    assert!(serde_json::from_str::<Game<SimpleAction>>(&broken).is_err());
 }
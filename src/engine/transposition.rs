@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use engine::{Action, Game, NodeId, Path};
+
+/// A game state that can tell whether it shows the same position as another
+pub trait PositionHash {
+    /// Returns a hash of the stones on the board and the player to move
+    fn position_hash(&self) -> u64;
+}
+
+/// The game tree with transposed items merged
+///
+/// Items reaching the same position are represented by the first of
+/// them in depth-first order, so move orders leading to the same
+/// position share their continuations.
+#[derive(Clone, Debug)]
+pub struct PositionDag {
+    canonical: HashMap<NodeId, NodeId>,
+    successors: HashMap<Path, Vec<NodeId>>,
+}
+
+impl PositionDag {
+    /// Returns the item representing the position of the given item
+    ///
+    /// Returns None for ids of other trees.
+    pub fn canonical(&self, id: &NodeId) -> Option<NodeId> {
+        self.canonical.get(id).cloned()
+    }
+
+    /// Returns the positions reachable by one action from the given one
+    ///
+    /// Collects the children of all items transposing into the given
+    /// one, each represented by its canonical item.
+    pub fn successors(&self, at: &Path) -> &[NodeId] {
+        let at = match *at {
+            Path::Node(ref id) => self.canonical(id).map(Path::Node),
+            Path::Root => Some(Path::Root),
+        };
+
+        at.and_then(|at| self.successors.get(&at)).map_or(&[], |successors| &successors[..])
+    }
+}
+
+impl<SomeAction> Game<SomeAction>
+    where SomeAction: Action,
+          SomeAction::GameState: PositionHash
+{
+    /// Returns the groups of items that reach the same position
+    ///
+    /// Only positions reached by at least two items are returned. The
+    /// items of a group and the groups come in depth-first order.
+    pub fn transpositions(&self) -> Vec<Vec<NodeId>> {
+        let mut groups: Vec<Vec<NodeId>> = Vec::new();
+        let mut by_hash = HashMap::new();
+
+        for (id, _) in self.iter_dfs() {
+            let hash = self.get_state(&id).unwrap().position_hash();
+            let group = *by_hash.entry(hash).or_insert(groups.len());
+            if group == groups.len() {
+                groups.push(Vec::new());
+            }
+            groups[group].push(id);
+        }
+
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+
+    /// Returns the first item in depth-first order reaching the position with the given hash
+    ///
+    /// The hash is compared with `PositionHash::position_hash` of the
+    /// states after the items.
+    pub fn find_position(&self, hash: u64) -> Option<NodeId> {
+        self.iter_dfs()
+            .map(|(id, _)| id)
+            .find(|id| self.get_state(id).unwrap().position_hash() == hash)
+    }
+
+    /// Returns a view of the tree with transposed items merged
+    pub fn dag(&self) -> PositionDag {
+        let mut canonical = HashMap::new();
+        for group in self.transpositions() {
+            for id in &group {
+                canonical.insert(*id, group[0]);
+            }
+        }
+        for (id, _) in self.iter_dfs() {
+            canonical.entry(id).or_insert(id);
+        }
+
+        let mut successors: HashMap<Path, Vec<NodeId>> = HashMap::new();
+        for (id, _) in self.iter_dfs() {
+            let parent = match self.parent(&id) {
+                Some(Path::Node(parent)) => Path::Node(canonical[&parent]),
+                _ => Path::Root,
+            };
+
+            let successors = successors.entry(parent).or_default();
+            if !successors.contains(&canonical[&id]) {
+                successors.push(canonical[&id]);
+            }
+        }
+
+        PositionDag {
+            canonical,
+            successors,
+        }
+    }
+}
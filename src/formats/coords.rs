@@ -0,0 +1,182 @@
+use go::board::Position;
+use render::ascii::column_letter;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The reasons a coordinate can not be parsed
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CoordinateError {
+    /// The text is not a coordinate at all
+    Malformed,
+    /// The letter does not name a column, e.g. I
+    UnknownColumn(char),
+    /// The coordinate lies outside the board
+    OffBoard,
+}
+
+/// A GTP style vertex like "D4" or "Q16"
+///
+/// Columns are labeled from A on, skipping I, rows are numbered from
+/// 1 at the bottom. Converting to a position needs the board size, see
+/// `Coordinates`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Vertex {
+    /// The column, 0 for A
+    pub column: usize,
+    /// The row number as written, 1 for the bottom row
+    pub row: usize,
+}
+
+/// Returns the column of the given letter
+fn column(letter: char) -> Result<usize, CoordinateError> {
+    match letter.to_ascii_uppercase() {
+        c @ 'A'..='H' => Ok((c as u8 - b'A') as usize),
+        c @ 'J'..='Z' => Ok((c as u8 - b'A') as usize - 1),
+        _ => Err(CoordinateError::UnknownColumn(letter)),
+    }
+}
+
+impl FromStr for Vertex {
+    type Err = CoordinateError;
+
+    fn from_str(s: &str) -> Result<Vertex, CoordinateError> {
+        let s = s.trim();
+        let letter = s.chars().next().ok_or(CoordinateError::Malformed)?;
+        if !letter.is_ascii_alphabetic() {
+            return Err(CoordinateError::Malformed);
+        }
+
+        let digits = &s[1..];
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(CoordinateError::Malformed);
+        }
+
+        let row = digits.parse::<usize>().map_err(|_| CoordinateError::Malformed)?;
+        if row == 0 {
+            return Err(CoordinateError::OffBoard);
+        }
+
+        Ok(Vertex {
+            column: column(letter)?,
+            row,
+        })
+    }
+}
+
+impl fmt::Display for Vertex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", column_letter(self.column), self.row)
+    }
+}
+
+/// Returns the index of an SGF coordinate letter
+///
+/// Lines are lettered a to z, then A to Z for boards up to 52 lines.
+fn sgf_index(letter: char) -> Result<usize, CoordinateError> {
+    match letter {
+        'a'..='z' => Ok((letter as u8 - b'a') as usize),
+        'A'..='Z' => Ok((letter as u8 - b'A') as usize + 26),
+        _ => Err(CoordinateError::Malformed),
+    }
+}
+
+/// Returns the SGF coordinate letter of the given line
+fn sgf_letter(index: usize) -> char {
+    if index < 26 {
+        (b'a' + index as u8) as char
+    } else {
+        (b'A' + (index - 26) as u8) as char
+    }
+}
+
+/// Converts between positions and the coordinates of a board
+///
+/// Positions count rows from the top while vertices count them from
+/// the bottom, so the conversion depends on the board size.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Coordinates {
+    size: usize,
+}
+
+impl Coordinates {
+    /// Creates the coordinates of a board with the given number of lines
+    pub fn new(size: usize) -> Coordinates {
+        Coordinates { size }
+    }
+
+    /// Returns the number of lines of the board
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the vertex of the given position
+    ///
+    /// Fails with `OffBoard` for positions outside the board.
+    pub fn vertex(&self, position: &Position) -> Result<Vertex, CoordinateError> {
+        if position.x >= self.size || position.y >= self.size {
+            return Err(CoordinateError::OffBoard);
+        }
+
+        Ok(Vertex {
+            column: position.x,
+            row: self.size - position.y,
+        })
+    }
+
+    /// Returns the position of the given vertex
+    pub fn position(&self, vertex: &Vertex) -> Result<Position, CoordinateError> {
+        if vertex.column >= self.size || vertex.row == 0 || vertex.row > self.size {
+            return Err(CoordinateError::OffBoard);
+        }
+
+        Ok(Position {
+            x: vertex.column,
+            y: self.size - vertex.row,
+        })
+    }
+
+    /// Parses a GTP vertex like "D4" into a position
+    pub fn parse_gtp(&self, vertex: &str) -> Result<Position, CoordinateError> {
+        self.position(&vertex.parse()?)
+    }
+
+    /// Formats the position as a GTP vertex like "D4"
+    ///
+    /// Fails with `OffBoard` for positions outside the board.
+    pub fn to_gtp(&self, position: &Position) -> Result<String, CoordinateError> {
+        self.vertex(position).map(|vertex| vertex.to_string())
+    }
+
+    /// Parses an SGF point like "pd" into a position
+    ///
+    /// Returns None for a pass, which is the empty value or "tt" on
+    /// boards of up to 19 lines.
+    pub fn parse_sgf(&self, point: &str) -> Result<Option<Position>, CoordinateError> {
+        let letters: Vec<char> = point.trim().chars().collect();
+        if letters.is_empty() || (self.size <= 19 && letters == ['t', 't']) {
+            return Ok(None);
+        }
+        if letters.len() != 2 {
+            return Err(CoordinateError::Malformed);
+        }
+
+        let x = sgf_index(letters[0])?;
+        let y = sgf_index(letters[1])?;
+        if x >= self.size || y >= self.size {
+            return Err(CoordinateError::OffBoard);
+        }
+
+        Ok(Some(Position { x, y }))
+    }
+
+    /// Formats the position as an SGF point like "pd"
+    ///
+    /// A pass (None) is written as the empty value.
+    pub fn to_sgf(&self, position: Option<&Position>) -> String {
+        match position {
+            Some(position) => format!("{}{}", sgf_letter(position.x), sgf_letter(position.y)),
+            None => String::new(),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod coords;
+pub mod sgf;
+
+pub use self::coords::{CoordinateError, Coordinates, Vertex};
+
+#[cfg(test)]
+mod test;
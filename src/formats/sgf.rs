@@ -0,0 +1,355 @@
+use aga::Action;
+use engine::{Annotation, Game, Markup, MoveMark, NodeId, Path};
+use formats::Coordinates;
+use go::{Player, Stone, ZobristHash};
+use go::board::Position;
+use rules::RuleSet;
+use time::TimeControl;
+
+/// Information about a game that is not part of its actions
+///
+/// Written to the root node of an SGF file, absent values are left out.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct GameInfo {
+    /// The name of the game, GN
+    pub name: Option<String>,
+    /// The name of the black player, PB
+    pub black: Option<String>,
+    /// The name of the white player, PW
+    pub white: Option<String>,
+    /// The rank of the black player, BR
+    pub black_rank: Option<String>,
+    /// The rank of the white player, WR
+    pub white_rank: Option<String>,
+    /// The event the game was played at, EV
+    pub event: Option<String>,
+    /// The date the game was played on, DT
+    pub date: Option<String>,
+    /// The place the game was played at, PC
+    pub place: Option<String>,
+}
+
+/// Writes the game tree as an SGF FF[4] file
+///
+/// Variations become SGF variations, the main line comes first. The
+/// size, komi, handicap, rules, time control and result are taken
+/// from the end of the main line. Setup and handicap stones are added
+/// with AB, AW and AE, proposed dead stones are marked with MA and the
+/// agreed dead stones are written as territory of the opponent when
+/// the end is accepted. Items without an SGF counterpart, like pauses,
+/// are left out unless they start variations.
+pub fn write<TBoard>(game: &Game<Action<TBoard>>, info: &GameInfo) -> String
+    where TBoard: ZobristHash<Position = Position>
+{
+    let mut end = Path::Root;
+    while let Some(&child) = game.children(&end).first() {
+        end = Path::Node(child);
+    }
+    let state = game.get_state(&end).unwrap();
+
+    let size = state.board().iter_positions().map(|position| position.x + 1).max().unwrap_or(0);
+    let coordinates = Coordinates::new(size);
+
+    let mut sgf = String::from("(;FF[4]GM[1]CA[UTF-8]AP[rustgo:0.0.1]");
+    property(&mut sgf, "SZ", &size.to_string());
+    property(&mut sgf, "KM", &state.komi().to_string());
+    if state.handicap() > 0 {
+        property(&mut sgf, "HA", &state.handicap().to_string());
+    }
+    if let Some(name) = rules_name(state.rules()) {
+        property(&mut sgf, "RU", name);
+    }
+    time_properties(&mut sgf, state.clock().control());
+    if let Some(result) = state.result() {
+        property(&mut sgf, "RE", &result.to_string());
+    }
+
+    let texts = [("GN", &info.name),
+                 ("PB", &info.black),
+                 ("PW", &info.white),
+                 ("BR", &info.black_rank),
+                 ("WR", &info.white_rank),
+                 ("EV", &info.event),
+                 ("DT", &info.date),
+                 ("PC", &info.place)];
+    for &(identifier, value) in texts.iter() {
+        if let Some(ref value) = *value {
+            property(&mut sgf, identifier, value);
+        }
+    }
+
+    write_children(game, &Path::Root, &coordinates, &mut sgf);
+    sgf.push(')');
+    sgf
+}
+
+/// Writes the items after the given path
+///
+/// A single child continues the current sequence, several children
+/// start one variation each.
+fn write_children<TBoard>(game: &Game<Action<TBoard>>,
+                          at: &Path,
+                          coordinates: &Coordinates,
+                          sgf: &mut String)
+    where TBoard: ZobristHash<Position = Position>
+{
+    let children = game.children(at);
+    if children.len() == 1 {
+        write_sequence(game, children[0], coordinates, sgf);
+        return;
+    }
+
+    for child in children {
+        sgf.push('(');
+        write_sequence(game, child, coordinates, sgf);
+        sgf.push(')');
+    }
+}
+
+/// Writes the item with the given id and everything after it
+///
+/// Items without properties are skipped unless the sequence would
+/// end or branch there, so every variation holds at least one node.
+fn write_sequence<TBoard>(game: &Game<Action<TBoard>>,
+                          mut id: NodeId,
+                          coordinates: &Coordinates,
+                          sgf: &mut String)
+    where TBoard: ZobristHash<Position = Position>
+{
+    loop {
+        let properties = node_properties(game, &id, coordinates);
+        let children = game.children(&id);
+        if !properties.is_empty() || children.len() != 1 {
+            sgf.push(';');
+            sgf.push_str(&properties);
+        }
+
+        if children.len() == 1 {
+            id = children[0];
+        } else {
+            return write_children(game, &Path::Node(id), coordinates, sgf);
+        }
+    }
+}
+
+/// Returns the SGF properties of the item with the given id
+fn node_properties<TBoard>(game: &Game<Action<TBoard>>,
+                           id: &NodeId,
+                           coordinates: &Coordinates)
+                           -> String
+    where TBoard: ZobristHash<Position = Position>
+{
+    let mut properties = String::new();
+    let mut dead = Vec::new();
+    let parent = game.parent(id).unwrap_or(Path::Root);
+
+    match *game.action(id).unwrap() {
+        Action::SetPlayerToMove { player } => property(&mut properties, "PL", letter(player)),
+        Action::Setup { ref add_black, ref add_white, ref clear, to_move } => {
+            points(&mut properties, "AB", add_black, coordinates);
+            points(&mut properties, "AW", add_white, coordinates);
+            points(&mut properties, "AE", clear, coordinates);
+            if let Some(player) = to_move {
+                property(&mut properties, "PL", letter(player));
+            }
+        }
+        Action::Handicap { .. } => {
+            let before = game.get_state(&parent).unwrap();
+            let after = game.get_state(id).unwrap();
+            let stones: Vec<Position> = after.board()
+                .iter_positions()
+                .filter(|position| {
+                    after.board().at(position) == Stone::Black &&
+                    before.board().at(position) == Stone::Empty
+                })
+                .collect();
+            points(&mut properties, "AB", &stones, coordinates);
+        }
+        Action::Pass { player, time_used } => {
+            property(&mut properties, letter(player), "");
+            if time_used.is_some() {
+                time_left(&mut properties, game, id, player);
+            }
+        }
+        Action::Play { player, ref at, time_used } => {
+            property(&mut properties, letter(player), &coordinates.to_sgf(Some(at)));
+            if time_used.is_some() {
+                time_left(&mut properties, game, id, player);
+            }
+        }
+        Action::RequestEnd { ref dead_stones, .. } |
+        Action::CounterProposeEnd { ref dead_stones, .. } => {
+            // written with the crosses of the markup, MA may appear once
+            dead.extend(dead_stones.iter().cloned());
+        }
+        Action::AcceptEnd { .. } => {
+            let before = game.get_state(&parent).unwrap();
+            let dead = before.dead_stones().unwrap_or(&[]);
+            let of = |stone: Stone| -> Vec<Position> {
+                dead.iter()
+                    .cloned()
+                    .filter(|position| before.board().at(position) == stone)
+                    .collect()
+            };
+            points(&mut properties, "TB", &of(Stone::White), coordinates);
+            points(&mut properties, "TW", &of(Stone::Black), coordinates);
+        }
+        _ => {}
+    }
+
+    let empty = Annotation::new();
+    let annotation = game.annotation(id).unwrap_or(&empty);
+    annotation_properties(&mut properties, annotation, &dead, coordinates);
+
+    properties
+}
+
+/// Writes the comment, move mark and markup of an item
+///
+/// The proposed dead stones are marked with a cross together with the
+/// crosses of the markup.
+fn annotation_properties(properties: &mut String,
+                         annotation: &Annotation<Position>,
+                         dead: &[Position],
+                         coordinates: &Coordinates) {
+    if let Some(ref comment) = annotation.comment {
+        property(properties, "C", comment);
+    }
+
+    match annotation.mark {
+        Some(MoveMark::Good { emphasized }) => {
+            property(properties, "TE", if emphasized { "2" } else { "1" })
+        }
+        Some(MoveMark::Bad { emphasized }) => {
+            property(properties, "BM", if emphasized { "2" } else { "1" })
+        }
+        Some(MoveMark::Doubtful) => property(properties, "DO", ""),
+        Some(MoveMark::Interesting) => property(properties, "IT", ""),
+        None => {}
+    }
+
+    let shapes = [(Markup::Circle, "CR"),
+                  (Markup::Cross, "MA"),
+                  (Markup::Square, "SQ"),
+                  (Markup::Triangle, "TR"),
+                  (Markup::Selected, "SL")];
+    for &(shape, identifier) in shapes.iter() {
+        let mut marked: Vec<Position> = if shape == Markup::Cross {
+            dead.to_vec()
+        } else {
+            Vec::new()
+        };
+        for &(position, markup) in &annotation.markup {
+            if markup == shape && !marked.contains(&position) {
+                marked.push(position);
+            }
+        }
+        points(properties, identifier, &marked, coordinates);
+    }
+
+    if !annotation.labels.is_empty() {
+        properties.push_str("LB");
+        for (position, text) in &annotation.labels {
+            properties.push('[');
+            properties.push_str(&coordinates.to_sgf(Some(position)));
+            properties.push(':');
+            properties.push_str(&escape(text).replace(':', "\\:"));
+            properties.push(']');
+        }
+    }
+}
+
+/// Writes the time the player has left after the item with the given id
+fn time_left<TBoard>(properties: &mut String,
+                     game: &Game<Action<TBoard>>,
+                     id: &NodeId,
+                     player: Player)
+    where TBoard: ZobristHash<Position = Position>
+{
+    if let Some(remaining) = game.get_state(id).unwrap().clock().remaining(&player) {
+        let identifier = match player {
+            Player::Black => "BL",
+            Player::White => "WL",
+        };
+        property(properties, identifier, &remaining.as_secs().to_string());
+    }
+}
+
+/// Writes the main time as TM and describes the overtime as OT
+fn time_properties(sgf: &mut String, control: &TimeControl) {
+    let (main, overtime) = match *control {
+        TimeControl::Unlimited => return,
+        TimeControl::Absolute { main } => (main, None),
+        TimeControl::ByoYomi { main, period, periods } => {
+            (main, Some(format!("{}x{} byo-yomi", periods, period.as_secs())))
+        }
+        TimeControl::Canadian { main, period, stones } => {
+            (main, Some(format!("{}/{} Canadian", stones, period.as_secs())))
+        }
+        TimeControl::Fischer { main, increment, .. } => {
+            (main, Some(format!("{} fischer", increment.as_secs())))
+        }
+    };
+
+    property(sgf, "TM", &main.as_secs().to_string());
+    if let Some(overtime) = overtime {
+        property(sgf, "OT", &overtime);
+    }
+}
+
+/// Returns the SGF name of the rule set, None if it has none
+///
+/// The komi does not matter, it is written separately.
+fn rules_name(rules: &RuleSet) -> Option<&'static str> {
+    let presets = [(RuleSet::aga(), "AGA"),
+                   (RuleSet::chinese(), "Chinese"),
+                   (RuleSet::japanese(), "Japanese"),
+                   (RuleSet::new_zealand(), "NZ"),
+                   (RuleSet::ing(), "GOE")];
+
+    presets.iter()
+        .find(|&&(preset, _)| RuleSet { komi: preset.komi, ..*rules } == preset)
+        .map(|&(_, name)| name)
+}
+
+/// Returns the SGF letter of the player, B or W
+fn letter(player: Player) -> &'static str {
+    match player {
+        Player::Black => "B",
+        Player::White => "W",
+    }
+}
+
+/// Writes a property with a single value
+fn property(sgf: &mut String, identifier: &str, value: &str) {
+    sgf.push_str(identifier);
+    sgf.push('[');
+    sgf.push_str(&escape(value));
+    sgf.push(']');
+}
+
+/// Writes a property with one point per value, nothing if there are none
+fn points(sgf: &mut String, identifier: &str, positions: &[Position], coordinates: &Coordinates) {
+    if positions.is_empty() {
+        return;
+    }
+
+    sgf.push_str(identifier);
+    for position in positions {
+        sgf.push('[');
+        sgf.push_str(&coordinates.to_sgf(Some(position)));
+        sgf.push(']');
+    }
+}
+
+/// Escapes the characters that end or escape an SGF value
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == ']' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
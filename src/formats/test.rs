@@ -0,0 +1,176 @@
+use aga::{Action, Board9x9};
+use engine::{Annotation, Game, Markup, MoveMark, Path};
+use formats::{CoordinateError, Coordinates, Vertex};
+use formats::sgf::{self, GameInfo};
+use go::Player;
+use go::board::Position;
+use rules::RuleSet;
+
+fn play(player: Player, x: usize, y: usize) -> Action<Board9x9> {
+    Action::Play {
+        player,
+        at: Position { x, y },
+        time_used: None,
+    }
+}
+
+#[test]
+fn gtp_vertex() {
+    assert_eq!("D4".parse(), Ok(Vertex { column: 3, row: 4 }));
+    assert_eq!("q16".parse(), Ok(Vertex { column: 15, row: 16 }));
+    assert_eq!("J10".parse::<Vertex>().map(|v| v.column), Ok(8));
+    assert_eq!(Vertex { column: 8, row: 10 }.to_string(), "J10");
+
+    assert_eq!("I5".parse::<Vertex>(), Err(CoordinateError::UnknownColumn('I')));
+    assert_eq!("D".parse::<Vertex>(), Err(CoordinateError::Malformed));
+    assert_eq!("4D".parse::<Vertex>(), Err(CoordinateError::Malformed));
+    assert_eq!("".parse::<Vertex>(), Err(CoordinateError::Malformed));
+    assert_eq!("D0".parse::<Vertex>(), Err(CoordinateError::OffBoard));
+    assert_eq!("D+4".parse::<Vertex>(), Err(CoordinateError::Malformed));
+    assert_eq!("D 4".parse::<Vertex>(), Err(CoordinateError::Malformed));
+}
+
+#[test]
+fn gtp_coordinates() {
+    let coordinates = Coordinates::new(19);
+
+    assert_eq!(coordinates.parse_gtp("A19"), Ok(Position { x: 0, y: 0 }));
+    assert_eq!(coordinates.parse_gtp("T1"), Ok(Position { x: 18, y: 18 }));
+    assert_eq!(coordinates.parse_gtp("Q16"), Ok(Position { x: 15, y: 3 }));
+    assert_eq!(coordinates.to_gtp(&Position { x: 3, y: 15 }), Ok("D4".to_string()));
+    assert_eq!(coordinates.to_gtp(&Position { x: 3, y: 19 }), Err(CoordinateError::OffBoard));
+    assert_eq!(coordinates.to_gtp(&Position { x: 19, y: 3 }), Err(CoordinateError::OffBoard));
+    assert_eq!(Coordinates::new(9).parse_gtp("K1"), Err(CoordinateError::OffBoard));
+    assert_eq!(Coordinates::new(9).parse_gtp("A10"), Err(CoordinateError::OffBoard));
+
+    for size in &[9, 13, 19, 25] {
+        let coordinates = Coordinates::new(*size);
+        for x in 0..*size {
+            for y in 0..*size {
+                let position = Position { x, y };
+                assert_eq!(coordinates.parse_gtp(&coordinates.to_gtp(&position).unwrap()),
+                           Ok(position));
+            }
+        }
+    }
+}
+
+#[test]
+fn sgf_coordinates() {
+    let coordinates = Coordinates::new(19);
+
+    assert_eq!(coordinates.parse_sgf("pd"), Ok(Some(Position { x: 15, y: 3 })));
+    assert_eq!(coordinates.parse_sgf("dp"), Ok(Some(Position { x: 3, y: 15 })));
+    assert_eq!(coordinates.to_sgf(Some(&Position { x: 15, y: 3 })), "pd");
+
+    // both pass encodings are read, the empty one is written
+    assert_eq!(coordinates.parse_sgf(""), Ok(None));
+    assert_eq!(coordinates.parse_sgf("tt"), Ok(None));
+    assert_eq!(coordinates.to_sgf(None), "");
+
+    assert_eq!(coordinates.parse_sgf("zz"), Err(CoordinateError::OffBoard));
+    assert_eq!(coordinates.parse_sgf("p"), Err(CoordinateError::Malformed));
+    assert_eq!(coordinates.parse_sgf("p4"), Err(CoordinateError::Malformed));
+
+    // "tt" is a point on larger boards
+    let large = Coordinates::new(25);
+    assert_eq!(large.parse_sgf("tt"), Ok(Some(Position { x: 19, y: 19 })));
+    assert_eq!(Coordinates::new(52).to_sgf(Some(&Position { x: 51, y: 26 })), "ZA");
+
+    for x in 0..19 {
+        for y in 0..19 {
+            let position = Position { x, y };
+            assert_eq!(coordinates.parse_sgf(&coordinates.to_sgf(Some(&position))),
+                       Ok(Some(position)));
+        }
+    }
+}
+
+#[test]
+fn sgf_export() {
+    let mut game = Game::<Action<Board9x9>>::new();
+    let mut cursor = game.insert(&Path::Root, Action::SetKomi { komi: 0.5 }).unwrap();
+    cursor = game.insert(&cursor, Action::Handicap { stones: 2 }).unwrap();
+    let handicap = cursor;
+    cursor = game.insert(&cursor, play(Player::White, 2, 2)).unwrap();
+    let variation = game.insert(&handicap, play(Player::White, 4, 4)).unwrap();
+    game.annotate(&variation, Annotation::comment("too [early]"));
+
+    cursor = game.insert(&cursor, play(Player::Black, 1, 1)).unwrap();
+    game.annotate(&cursor,
+                  Annotation {
+                      mark: Some(MoveMark::Bad { emphasized: false }),
+                      markup: vec![(Position { x: 2, y: 2 }, Markup::Triangle)],
+                      labels: vec![(Position { x: 0, y: 0 }, "1:0".to_string())],
+                      ..Annotation::new()
+                  });
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::White,
+                             dead_stones: vec![Position { x: 1, y: 1 }],
+                         })
+        .unwrap();
+    game.insert(&cursor, Action::AcceptEnd { player: Player::Black }).unwrap();
+
+    let info = GameInfo {
+        black: Some("Honinbo Shusaku".to_string()),
+        ..GameInfo::default()
+    };
+    // the dead black stone counts as white territory
+    assert_eq!(sgf::write(&game, &info),
+               "(;FF[4]GM[1]CA[UTF-8]AP[rustgo:0.0.1]SZ[9]KM[0.5]HA[2]RU[AGA]RE[W+0.5]\
+                PB[Honinbo Shusaku];AB[cg][gc]\
+                (;W[cc];B[bb]BM[1]TR[cc]LB[aa:1\\:0];W[];B[];W[];MA[bb];TW[bb])\
+                (;W[ee]C[too [early\\]]))");
+}
+
+#[test]
+fn sgf_export_setup() {
+    let mut game = Game::<Action<Board9x9>>::new();
+    let mut cursor = game.insert(&Path::Root, Action::SetRules { rules: RuleSet::japanese() })
+        .unwrap();
+    cursor = game.insert(&cursor,
+                         Action::Setup {
+                             add_black: vec![Position { x: 0, y: 0 }],
+                             add_white: vec![Position { x: 8, y: 8 }, Position { x: 7, y: 8 }],
+                             clear: vec![],
+                             to_move: Some(Player::White),
+                         })
+        .unwrap();
+    game.insert(&cursor, Action::Resign { player: Player::White }).unwrap();
+
+    assert_eq!(sgf::write(&game, &GameInfo::default()),
+               "(;FF[4]GM[1]CA[UTF-8]AP[rustgo:0.0.1]SZ[9]KM[6.5]RU[Japanese]RE[B+R]\
+                ;AB[aa]AW[ii][hi]PL[W];)");
+    assert_eq!(sgf::write(&Game::<Action<Board9x9>>::new(), &GameInfo::default()),
+               "(;FF[4]GM[1]CA[UTF-8]AP[rustgo:0.0.1]SZ[9]KM[7.5]RU[AGA])");
+}
+
+#[test]
+fn sgf_export_dead_stones_with_crosses() {
+    let mut game = Game::<Action<Board9x9>>::new();
+    let mut cursor = game.insert(&Path::Root, play(Player::Black, 1, 1)).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::Black, time_used: None }).unwrap();
+    cursor = game.insert(&cursor, Action::Pass { player: Player::White, time_used: None }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::RequestEnd {
+                             player: Player::White,
+                             dead_stones: vec![Position { x: 1, y: 1 }],
+                         })
+        .unwrap();
+    game.annotate(&cursor,
+                  Annotation {
+                      markup: vec![(Position { x: 1, y: 1 }, Markup::Cross),
+                                   (Position { x: 4, y: 4 }, Markup::Cross)],
+                      ..Annotation::new()
+                  });
+
+    // a node holds a property at most once
+    let text = sgf::write(&game, &GameInfo::default());
+    assert!(text.contains(";MA[bb][ee])"));
+    assert_eq!(text.matches("MA").count(), 1);
+}
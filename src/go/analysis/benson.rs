@@ -0,0 +1,100 @@
+use go::{Board, Group, Stone};
+
+use std::collections::HashSet;
+
+/// Returns the connected sets of positions matching the predicate
+fn components<TBoard, F>(board: &TBoard, belongs: F) -> Vec<HashSet<TBoard::Position>>
+    where TBoard: Board,
+          F: Fn(Stone) -> bool
+{
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+
+    for start in board.iter_positions() {
+        if seen.contains(&start) || !belongs(board.at(&start)) {
+            continue;
+        }
+
+        let mut component = HashSet::new();
+        let mut stack = vec![start];
+        component.insert(start);
+        while let Some(top) = stack.pop() {
+            board.for_each_neighbor(&top, |n| {
+                if belongs(board.at(&n)) && component.insert(n) {
+                    stack.push(n);
+                }
+            });
+        }
+
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+
+    components
+}
+
+/// Returns the stones of the given color that are unconditionally alive
+///
+/// Implements Benson's algorithm: a region (connected intersections
+/// not occupied by the color) is vital to a chain if all of its empty
+/// intersections are liberties of the chain. Chains with less than two
+/// vital regions are dropped, as are the regions touching a dropped
+/// chain, until nothing changes. The remaining chains can not be
+/// captured, even if the other player is allowed to play every move.
+pub fn unconditionally_alive<TBoard>(board: &TBoard, stone: Stone) -> HashSet<TBoard::Position>
+    where TBoard: Board
+{
+    let chains: Vec<_> = components(board, |s| s == stone)
+        .into_iter()
+        .map(|positions| {
+            let start = *positions.iter().next().unwrap();
+            Group::new(board, &start)
+        })
+        .collect();
+    let regions = components(board, |s| s != stone);
+
+    // for every region, the chains next to it and those it is vital to
+    let mut touching = vec![HashSet::new(); regions.len()];
+    let mut vital = vec![HashSet::new(); regions.len()];
+    for (r, region) in regions.iter().enumerate() {
+        for (c, chain) in chains.iter().enumerate() {
+            let liberties = chain.liberties();
+            let touches = region.iter().any(|p| {
+                let mut next_to_chain = false;
+                board.for_each_neighbor(p, |n| next_to_chain |= chain.positions.contains(&n));
+                next_to_chain
+            });
+
+            if touches {
+                touching[r].insert(c);
+                let all_liberties = region.iter()
+                    .filter(|p| board.at(p) == Stone::Empty)
+                    .all(|p| liberties.contains(p));
+                if all_liberties {
+                    vital[r].insert(c);
+                }
+            }
+        }
+    }
+
+    let mut alive_chains: HashSet<usize> = (0..chains.len()).collect();
+    let mut healthy_regions: HashSet<usize> = (0..regions.len()).collect();
+
+    loop {
+        let dropped_chains: HashSet<usize> = alive_chains.iter()
+            .cloned()
+            .filter(|c| healthy_regions.iter().filter(|r| vital[**r].contains(c)).count() < 2)
+            .collect();
+
+        if dropped_chains.is_empty() {
+            break;
+        }
+
+        for c in &dropped_chains {
+            alive_chains.remove(c);
+        }
+        healthy_regions.retain(|r| touching[*r].is_subset(&alive_chains));
+    }
+
+    alive_chains.iter().flat_map(|c| chains[*c].positions.iter().cloned()).collect()
+}
@@ -0,0 +1,86 @@
+use go::analysis::benson::unconditionally_alive;
+use go::analysis::playout::{owners, playout, Random};
+use go::{Board, Group, Player, Stone};
+
+use std::collections::HashSet;
+
+/// Proposes dead stones for a finished game
+///
+/// Decides per group:
+/// * groups that are unconditionally alive by Benson's algorithm live
+/// * other groups are dead if the opponent owns their intersections at
+///   the end of most of a number of random playouts from the position
+/// * groups in atari are already dead if the opponent owns them in a
+///   quarter of the playouts
+///
+/// The playouts use a fixed seed, so the estimate is reproducible.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct DeadStoneEstimator {
+    playouts: usize,
+    seed: u64,
+}
+
+impl Default for DeadStoneEstimator {
+    fn default() -> Self {
+        DeadStoneEstimator::new()
+    }
+}
+
+impl DeadStoneEstimator {
+    /// Creates an estimator running 64 playouts
+    pub fn new() -> Self {
+        DeadStoneEstimator {
+            playouts: 64,
+            seed: 1,
+        }
+    }
+
+    /// Sets the number of playouts to run
+    pub fn with_playouts(self, playouts: usize) -> Self {
+        DeadStoneEstimator { playouts, ..self }
+    }
+
+    /// Sets the seed of the playouts
+    pub fn with_seed(self, seed: u64) -> Self {
+        DeadStoneEstimator { seed, ..self }
+    }
+
+    /// Returns the positions of the stones that are probably dead
+    pub fn estimate<TBoard>(&self, board: &TBoard) -> Vec<TBoard::Position>
+        where TBoard: Board
+    {
+        let mut alive = unconditionally_alive(board, Stone::Black);
+        alive.extend(unconditionally_alive(board, Stone::White));
+
+        let mut random = Random::new(self.seed);
+        let owners: Vec<TBoard> = (0..self.playouts)
+            .map(|_| owners(&playout(board, &Player::Black, &mut random)))
+            .collect();
+
+        let mut seen = HashSet::new();
+        let mut dead = Vec::new();
+        for position in board.iter_positions() {
+            let stone = board.at(&position);
+            if stone == Stone::Empty || alive.contains(&position) || seen.contains(&position) {
+                continue;
+            }
+
+            let group = Group::new(board, &position);
+            seen.extend(group.positions.iter().cloned());
+
+            let lost = owners.iter()
+                .filter(|o| {
+                    let opponent = group.positions.iter().filter(|p| o.at(p) != stone).count();
+                    2 * opponent > group.positions.len()
+                })
+                .count();
+            let in_atari = group.liberties().len() == 1;
+
+            if 2 * lost > self.playouts || (in_atari && 4 * lost >= self.playouts) {
+                dead.extend(group.positions.iter().cloned());
+            }
+        }
+
+        dead
+    }
+}
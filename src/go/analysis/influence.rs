@@ -0,0 +1,152 @@
+use go::{Board, Stone};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The value stones start out with
+const STONE_VALUE: i32 = 128;
+
+/// The number of dilations of the usual 5/21 algorithm
+pub const DILATIONS: usize = 5;
+
+/// The number of erosions of the usual 5/21 algorithm
+pub const EROSIONS: usize = 21;
+
+/// An estimate of who controls which intersection
+///
+/// Positive values mean black influence, negative ones white
+/// influence. The larger the absolute value, the stronger.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InfluenceMap<TPosition>
+    where TPosition: Eq + Hash
+{
+    values: HashMap<TPosition, i32>,
+}
+
+impl<TPosition> InfluenceMap<TPosition>
+    where TPosition: Eq + Hash
+{
+    /// Returns the influence at the position
+    pub fn value(&self, position: &TPosition) -> i32 {
+        self.values.get(position).cloned().unwrap_or(0)
+    }
+
+    /// Returns the stone controlling the position
+    ///
+    /// Returns `Stone::Empty` for neutral positions.
+    pub fn owner(&self, position: &TPosition) -> Stone {
+        match self.value(position) {
+            v if v > 0 => Stone::Black,
+            v if v < 0 => Stone::White,
+            _ => Stone::Empty,
+        }
+    }
+
+    /// Returns the number of intersections controlled by (black, white)
+    pub fn counts(&self) -> (usize, usize) {
+        let black = self.values.values().filter(|v| **v > 0).count();
+        let white = self.values.values().filter(|v| **v < 0).count();
+        (black, white)
+    }
+}
+
+/// Computes the influence of the stones using Bouzy's 5/21 algorithm
+pub fn bouzy<TBoard>(board: &TBoard) -> InfluenceMap<TBoard::Position>
+    where TBoard: Board
+{
+    influence(board, DILATIONS, EROSIONS)
+}
+
+/// Computes the influence of the stones by dilation and erosion
+///
+/// Stones start out with a value of +128 (black) or -128 (white).
+/// A dilation lets every intersection not bordering the other color
+/// grow by the number of neighbors of its own color, an erosion lets
+/// every intersection shrink towards zero by the number of neighbors
+/// that are not of its color. Dilations spread influence, erosions
+/// remove it where it is contested.
+pub fn influence<TBoard>(board: &TBoard,
+                         dilations: usize,
+                         erosions: usize)
+                         -> InfluenceMap<TBoard::Position>
+    where TBoard: Board
+{
+    let mut values: HashMap<_, _> = board.iter_positions()
+        .map(|p| {
+            let value = match board.at(&p) {
+                Stone::Black => STONE_VALUE,
+                Stone::White => -STONE_VALUE,
+                Stone::Empty => 0,
+            };
+            (p, value)
+        })
+        .collect();
+
+    for _ in 0..dilations {
+        values = dilate(board, &values);
+    }
+    for _ in 0..erosions {
+        values = erode(board, &values);
+    }
+
+    InfluenceMap { values }
+}
+
+/// Counts the neighbors whose value matches the predicate
+fn count_neighbors<TBoard, F>(board: &TBoard,
+                              values: &HashMap<TBoard::Position, i32>,
+                              position: &TBoard::Position,
+                              predicate: F)
+                              -> i32
+    where TBoard: Board,
+          F: Fn(i32) -> bool
+{
+    let mut count = 0;
+    board.for_each_neighbor(position, |n| {
+        if predicate(values[&n]) {
+            count += 1;
+        }
+    });
+    count
+}
+
+fn dilate<TBoard>(board: &TBoard,
+                  values: &HashMap<TBoard::Position, i32>)
+                  -> HashMap<TBoard::Position, i32>
+    where TBoard: Board
+{
+    values.iter()
+        .map(|(p, &value)| {
+            let positive = count_neighbors(board, values, p, |v| v > 0);
+            let negative = count_neighbors(board, values, p, |v| v < 0);
+
+            let dilated = if value >= 0 && negative == 0 {
+                value + positive
+            } else if value <= 0 && positive == 0 {
+                value - negative
+            } else {
+                value
+            };
+            (*p, dilated)
+        })
+        .collect()
+}
+
+fn erode<TBoard>(board: &TBoard,
+                 values: &HashMap<TBoard::Position, i32>)
+                 -> HashMap<TBoard::Position, i32>
+    where TBoard: Board
+{
+    values.iter()
+        .map(|(p, &value)| {
+            let eroded = if value > 0 {
+                (value - count_neighbors(board, values, p, |v| v <= 0)).max(0)
+            } else if value < 0 {
+                (value + count_neighbors(board, values, p, |v| v >= 0)).min(0)
+            } else {
+                0
+            };
+            (*p, eroded)
+        })
+        .collect()
+}
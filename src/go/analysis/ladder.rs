@@ -0,0 +1,98 @@
+use go::{Board, Group, Player, Stone};
+
+use std::collections::HashSet;
+
+/// The number of moves after which a ladder counts as escaped
+const MAX_DEPTH: usize = 200;
+
+/// Returns true if the group is captured in a ladder
+///
+/// Reads out the sequence where the defender extends or captures to
+/// escape an atari and the attacker keeps ataring at one of the two
+/// remaining liberties. A group in atari is read with its owner to
+/// move, a group with two liberties with the attacker to move. Groups
+/// with more liberties are never captured. All stones on the board
+/// take part in the reading, so ladder breakers are honored.
+pub fn is_captured_in_ladder<TBoard>(board: &TBoard, group: &Group<TBoard>) -> bool
+    where TBoard: Board
+{
+    let defender = match group.stone() {
+        Some(Stone::Black) => Player::Black,
+        Some(Stone::White) => Player::White,
+        _ => return false,
+    };
+    let prey = match group.positions.iter().next() {
+        Some(position) => *position,
+        None => return false,
+    };
+
+    let liberties = group.liberties();
+    match liberties.len() {
+        1 => defender_is_captured(board, &prey, &defender, 0),
+        2 => attacker_captures(board, &prey, &defender, &liberties, 0),
+        _ => false,
+    }
+}
+
+/// Returns true if no escape of the prey in atari works, defender to move
+fn defender_is_captured<TBoard>(board: &TBoard,
+                                prey: &TBoard::Position,
+                                defender: &Player,
+                                depth: usize)
+                                -> bool
+    where TBoard: Board
+{
+    if depth > MAX_DEPTH {
+        return false;
+    }
+
+    let group = Group::new(board, prey);
+    let mut escapes = group.liberties();
+    for position in &group.positions {
+        board.for_each_neighbor(position, |n| {
+            if board.at(&n) == defender.other().stone() {
+                let attacker = Group::new(board, &n);
+                if attacker.is_in_atari() {
+                    escapes.extend(attacker.liberties());
+                }
+            }
+        });
+    }
+
+    for escape in &escapes {
+        let mut next = board.clone();
+        if next.play(defender, escape).is_err() {
+            continue;
+        }
+
+        let liberties = Group::new(&next, prey).liberties();
+        match liberties.len() {
+            0 | 1 => {}
+            2 => {
+                if !attacker_captures(&next, prey, defender, &liberties, depth + 1) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+/// Returns true if an atari at one of the two liberties captures the prey
+fn attacker_captures<TBoard>(board: &TBoard,
+                             prey: &TBoard::Position,
+                             defender: &Player,
+                             liberties: &HashSet<TBoard::Position>,
+                             depth: usize)
+                             -> bool
+    where TBoard: Board
+{
+    liberties.iter().any(|liberty| {
+        let mut next = board.clone();
+        next.play(&defender.other(), liberty).is_ok() &&
+        Group::new(&next, prey).is_in_atari() &&
+        defender_is_captured(&next, prey, defender, depth + 1)
+    })
+}
@@ -0,0 +1,3 @@
+pub mod special_cases;
+
+pub use self::special_cases::{apply_special_cases, special_cases, Ruling, SpecialCase};
@@ -0,0 +1,117 @@
+use go::{Board, Group, Stone};
+
+use std::collections::HashSet;
+
+/// A shape whose status is decided by a ruling instead of by play
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum SpecialCase {
+    /// A bent four shaped eye space in the corner, partly filled by the
+    /// opponent. The defender can only live by ko, which the attacker
+    /// may start at will after removing all ko threats. Dead by the
+    /// Japanese rules.
+    BentFourInTheCorner,
+}
+
+/// The stones a special case decides the status of
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Ruling<TPosition> {
+    /// The special case found on the board
+    pub case: SpecialCase,
+    /// The stones that are dead by the ruling
+    pub dead: Vec<TPosition>,
+    /// The stones that are alive by the ruling
+    pub alive: Vec<TPosition>,
+}
+
+/// Returns the special cases on the board
+pub fn special_cases<TBoard>(board: &TBoard) -> Vec<Ruling<TBoard::Position>>
+    where TBoard: Board
+{
+    board.iter_positions()
+        .filter(|corner| board.neighbors(corner).len() == 2)
+        .flat_map(|corner| {
+            vec![Stone::Black, Stone::White]
+                .into_iter()
+                .filter_map(move |defender| bent_four_in_the_corner(board, &corner, defender))
+        })
+        .collect()
+}
+
+/// Corrects the dead stones by the special cases on the board
+///
+/// Adds the stones a ruling declares dead and removes those it
+/// declares alive.
+pub fn apply_special_cases<TBoard>(board: &TBoard, dead: &mut Vec<TBoard::Position>)
+    where TBoard: Board
+{
+    for ruling in special_cases(board) {
+        dead.retain(|p| !ruling.alive.contains(p));
+        for position in ruling.dead {
+            if !dead.contains(&position) {
+                dead.push(position);
+            }
+        }
+    }
+}
+
+/// Checks for a bent four of the defender in the given corner
+///
+/// The eye space holds the corner, both its neighbors and one more
+/// point along an edge. It contains stones of the attacker and is
+/// enclosed by groups of the defender without other liberties.
+fn bent_four_in_the_corner<TBoard>(board: &TBoard,
+                                   corner: &TBoard::Position,
+                                   defender: Stone)
+                                   -> Option<Ruling<TBoard::Position>>
+    where TBoard: Board
+{
+    // the eye space is connected through intersections not held by the defender
+    let mut space = vec![*corner];
+    let mut border = HashSet::new();
+    let mut next = 0;
+    while next < space.len() {
+        if board.at(&space[next]) == defender || space.len() > 4 {
+            return None;
+        }
+        for neighbor in board.neighbors(&space[next]) {
+            if board.at(&neighbor) == defender {
+                border.insert(neighbor);
+            } else if !space.contains(&neighbor) {
+                space.push(neighbor);
+            }
+        }
+        next += 1;
+    }
+
+    // bent at the corner: both arms and a far point extending one of them along the edge
+    let arms = board.neighbors(corner);
+    if space.len() != 4 || !arms.iter().all(|arm| space.contains(arm)) {
+        return None;
+    }
+    let far = space.iter().find(|p| *p != corner && !arms.contains(p))?;
+    if board.neighbors(far).len() != 3 ||
+       arms.iter().filter(|arm| board.neighbors(arm).contains(far)).count() != 1 {
+        return None;
+    }
+
+    let attacker: Vec<_> = space.iter().filter(|p| board.at(p) != Stone::Empty).cloned().collect();
+    if attacker.is_empty() || attacker.len() == space.len() {
+        return None;
+    }
+
+    // the defender has no liberties outside the eye space
+    let mut dead = HashSet::new();
+    for stone in &border {
+        let group = Group::new(board, stone);
+        if group.liberties().iter().any(|liberty| !space.contains(liberty)) {
+            return None;
+        }
+        dead.extend(group.positions.iter().cloned());
+    }
+
+    Some(Ruling {
+        case: SpecialCase::BentFourInTheCorner,
+        dead: dead.into_iter().collect(),
+        alive: attacker,
+    })
+}
@@ -0,0 +1,74 @@
+use go::{GridBoard, Player, Stone};
+
+/// Returns the position mirrored at the center of the board
+pub fn mirror<TBoard>(board: &TBoard, position: &TBoard::Position) -> TBoard::Position
+    where TBoard: GridBoard
+{
+    let (x, y) = board.coordinates(position);
+    let last = board.size() - 1;
+    board.position(last - x, last - y)
+}
+
+/// Checks if the board is point symmetric with the colors swapped
+///
+/// The center and the ignored positions are skipped, e.g. a tengen
+/// opening or a move not answered yet.
+pub fn is_mirrored<TBoard>(board: &TBoard, ignore: &[TBoard::Position]) -> bool
+    where TBoard: GridBoard
+{
+    board.iter_positions().all(|position| {
+        let opposite = mirror(board, &position);
+        if position == opposite || ignore.contains(&position) || ignore.contains(&opposite) {
+            return true;
+        }
+
+        match board.at(&position) {
+            Stone::Black => board.at(&opposite) == Stone::White,
+            Stone::White => board.at(&opposite) == Stone::Black,
+            Stone::Empty => board.at(&opposite) == Stone::Empty,
+        }
+    })
+}
+
+/// Returns the player who mirrored the plays of the other one
+///
+/// The history holds the plays of the game in order. A player mirrors
+/// if at least the given number of their latest plays answered the
+/// previous play at its mirrored position and the board is symmetric,
+/// up to a last play not answered yet.
+pub fn mirroring_player<TBoard>(board: &TBoard,
+                                history: &[(Player, TBoard::Position)],
+                                moves: usize)
+                                -> Option<Player>
+    where TBoard: GridBoard
+{
+    let mut end = history.len();
+    let (last, _) = *history.last()?;
+
+    // the latest play may still wait for its answer
+    let answered = end >= 2 && history[end - 2].0 == last.other() &&
+                   history[end - 1].1 == mirror(board, &history[end - 2].1);
+    let mut ignore = Vec::new();
+    if !answered {
+        ignore.push(history[end - 1].1);
+        end -= 1;
+    }
+    let mirroring = if answered { last } else { last.other() };
+
+    let mut count = 0;
+    while end >= 2 {
+        let (player, ref at) = history[end - 1];
+        let (other, ref played) = history[end - 2];
+        if player != mirroring || other != mirroring.other() || *at != mirror(board, played) {
+            break;
+        }
+        count += 1;
+        end -= 2;
+    }
+
+    if count >= moves && count > 0 && is_mirrored(board, &ignore) {
+        Some(mirroring)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,14 @@
+pub mod benson;
+pub mod dead_stones;
+pub mod influence;
+pub mod ladder;
+pub mod life_and_death;
+pub mod mirror;
+pub mod playout;
+pub mod score;
+
+pub use self::dead_stones::DeadStoneEstimator;
+pub use self::score::{estimate_score, ScoreEstimate};
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,99 @@
+use go::{Board, Player, Stone};
+
+/// A small linear congruential random number generator
+pub struct Random(u64);
+
+impl Random {
+    /// Creates a generator from the seed
+    pub fn new(seed: u64) -> Random {
+        Random(seed)
+    }
+
+    /// Returns a random number below the bound
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) as usize) % bound
+    }
+}
+
+/// Returns true if the position is an eye of the given color
+///
+/// All neighbors have to be of that color and at most one diagonal
+/// (none at the edge) may be of the other color.
+pub fn is_eye<TBoard>(board: &TBoard, position: &TBoard::Position, stone: Stone) -> bool
+    where TBoard: Board
+{
+    let mut surrounded = true;
+    board.for_each_neighbor(position, |n| surrounded &= board.at(&n) == stone);
+    if !surrounded {
+        return false;
+    }
+
+    let mut diagonals = 0;
+    let mut foreign = 0;
+    board.for_each_diagonal(position, |d| {
+        diagonals += 1;
+        if board.at(&d) != stone && board.at(&d) != Stone::Empty {
+            foreign += 1;
+        }
+    });
+
+    if diagonals < 4 { foreign == 0 } else { foreign <= 1 }
+}
+
+/// Plays random moves from the position until both players pass
+///
+/// The given player moves first. Players never fill their own eyes
+/// and pass if there is nothing else to do.
+pub fn playout<TBoard>(board: &TBoard, to_move: &Player, random: &mut Random) -> TBoard
+    where TBoard: Board
+{
+    let mut board = board.clone();
+    let mut player = *to_move;
+    let mut passes = 0;
+    let mut moves = 3 * board.iter_positions().count();
+
+    while passes < 2 && moves > 0 {
+        let candidates: Vec<_> = board.legal_moves(&player)
+            .filter(|p| !is_eye(&board, p, player.stone()))
+            .collect();
+
+        if candidates.is_empty() {
+            passes += 1;
+        } else {
+            let position = candidates[random.below(candidates.len())];
+            let _ = board.play(&player, &position);
+            passes = 0;
+        }
+
+        player = player.other();
+        moves -= 1;
+    }
+
+    board
+}
+
+/// Returns a board where every intersection holds its owner
+///
+/// Empty intersections belong to a color if all of their neighbors
+/// are of that color.
+pub fn owners<TBoard>(board: &TBoard) -> TBoard
+    where TBoard: Board
+{
+    let mut owners = board.clone();
+    for position in board.iter_positions() {
+        if board.at(&position) != Stone::Empty {
+            continue;
+        }
+
+        for stone in &[Stone::Black, Stone::White] {
+            let mut surrounded = true;
+            board.for_each_neighbor(&position, |n| surrounded &= board.at(&n) == *stone);
+            if surrounded {
+                owners.set(&position, stone);
+            }
+        }
+    }
+
+    owners
+}
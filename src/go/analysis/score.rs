@@ -0,0 +1,71 @@
+use go::analysis::playout::{owners, playout, Random};
+use go::{Board, Player, Stone};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The result of a Monte Carlo score estimation
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScoreEstimate<TPosition>
+    where TPosition: Eq + Hash
+{
+    /// The mean area score of black
+    pub black: f64,
+    /// The mean area score of white
+    pub white: f64,
+    /// The share of playouts black and white owned each position in
+    ownership: HashMap<TPosition, (f64, f64)>,
+}
+
+impl<TPosition> ScoreEstimate<TPosition>
+    where TPosition: Eq + Hash
+{
+    /// Returns the share of playouts (black, white) owned the position in
+    pub fn ownership(&self, position: &TPosition) -> (f64, f64) {
+        self.ownership.get(position).cloned().unwrap_or((0.0, 0.0))
+    }
+
+    /// Returns the mean lead of black over white, negative if white leads
+    pub fn lead(&self) -> f64 {
+        self.black - self.white
+    }
+}
+
+/// Estimates the score by running random playouts to completion
+///
+/// The given player moves first in every playout. The finished boards
+/// are area scored: stones and empty intersections surrounded by one
+/// color count for that color.
+pub fn estimate_score<TBoard>(board: &TBoard,
+                              to_move: &Player,
+                              playouts: usize)
+                              -> ScoreEstimate<TBoard::Position>
+    where TBoard: Board
+{
+    let mut random = Random::new(1);
+    let mut counts: HashMap<_, _> = board.iter_positions().map(|p| (p, (0, 0))).collect();
+
+    for _ in 0..playouts {
+        let owners = owners(&playout(board, to_move, &mut random));
+        for (position, count) in counts.iter_mut() {
+            match owners.at(position) {
+                Stone::Black => count.0 += 1,
+                Stone::White => count.1 += 1,
+                Stone::Empty => {}
+            }
+        }
+    }
+
+    let runs = playouts.max(1) as f64;
+    let black = counts.values().map(|c| c.0).sum::<usize>() as f64 / runs;
+    let white = counts.values().map(|c| c.1).sum::<usize>() as f64 / runs;
+    let ownership = counts.into_iter()
+        .map(|(p, (b, w))| (p, (b as f64 / runs, w as f64 / runs)))
+        .collect();
+
+    ScoreEstimate {
+        black,
+        white,
+        ownership,
+    }
+}
@@ -0,0 +1,257 @@
+use go::analysis::benson::unconditionally_alive;
+use go::analysis::{estimate_score, DeadStoneEstimator};
+use go::analysis::influence::{bouzy, influence};
+use go::analysis::ladder::is_captured_in_ladder;
+use go::analysis::mirror::{is_mirrored, mirroring_player};
+use go::analysis::life_and_death::{apply_special_cases, special_cases, SpecialCase};
+use go::board::{Position, SquareBoard};
+use go::{Board, Group, Player, Stone};
+
+type Board9x9 = SquareBoard<9>;
+
+#[test]
+fn benson_two_eyes() {
+    let board: Board9x9 = ".X.X.....
+                           XXXX.....
+                           .........
+                           .........
+                           .........
+                           .........
+                           .........
+                           ......OOO
+                           ......O.O"
+        .parse()
+        .unwrap();
+
+    let black = unconditionally_alive(&board, Stone::Black);
+    assert_eq!(black.len(), 6);
+    assert!(black.contains(&Position { x: 0, y: 1 }));
+
+    // one eye only
+    assert!(unconditionally_alive(&board, Stone::White).is_empty());
+}
+
+#[test]
+fn dead_stones() {
+    let board: Board9x9 = ".X.X.O...
+                           XXXXOO...
+                           OOOOO....
+                           .........
+                           ...X.....
+                           .........
+                           OOOOOOOOO
+                           XXXXXXXXX
+                           .X.X.X.X."
+        .parse()
+        .unwrap();
+
+    let dead = DeadStoneEstimator::new().with_playouts(16).estimate(&board);
+
+    assert!(dead.contains(&Position { x: 3, y: 4 }));
+    assert!(!dead.contains(&Position { x: 0, y: 1 }));
+    assert!(!dead.contains(&Position { x: 0, y: 7 }));
+    assert!(!dead.contains(&Position { x: 0, y: 6 }));
+}
+
+#[test]
+fn influence_map() {
+    let board: Board9x9 = "..X...O..
+                           ..X...O..
+                           ..X...O..
+                           ..X...O..
+                           ..X...O..
+                           ..X...O..
+                           ..X...O..
+                           ..X...O..
+                           ..X...O.."
+        .parse()
+        .unwrap();
+
+    let map = bouzy(&board);
+    assert_eq!(map.owner(&Position { x: 0, y: 4 }), Stone::Black);
+    assert_eq!(map.owner(&Position { x: 8, y: 4 }), Stone::White);
+
+    // the middle is contested
+    assert_eq!(map.owner(&Position { x: 3, y: 4 }), Stone::Empty);
+    assert_eq!(map.owner(&Position { x: 4, y: 4 }), Stone::Empty);
+    assert_eq!(map.counts(), (27, 27));
+
+    // without dilations and erosions only the stones have influence
+    let stones = influence(&board, 0, 0);
+    assert_eq!(stones.value(&Position { x: 2, y: 0 }), 128);
+    assert_eq!(stones.value(&Position { x: 6, y: 0 }), -128);
+    assert_eq!(stones.counts(), (9, 9));
+}
+
+#[test]
+fn score_estimate() {
+    let board: Board9x9 = ".X.O.O.O.
+                           XXXOOOOOO
+                           XXXOOOOOO
+                           XXXO.....
+                           XXXO.....
+                           XXXO.....
+                           XXXOOOOOO
+                           XXXOOOOOO
+                           .X.O.O.O."
+        .parse()
+        .unwrap();
+
+    let estimate = estimate_score(&board, &Player::Black, 8);
+
+    assert_eq!(estimate.ownership(&Position { x: 0, y: 0 }), (1.0, 0.0));
+    assert_eq!(estimate.ownership(&Position { x: 8, y: 8 }), (0.0, 1.0));
+    assert!(estimate.ownership(&Position { x: 6, y: 4 }).1 > 0.5);
+    assert!(estimate.black + estimate.white <= 81.0);
+    assert!(estimate.lead() < 0.0);
+}
+
+#[test]
+fn ladder() {
+    let works: Board9x9 = ".........
+                           .........
+                           ..X......
+                           .XO......
+                           ...X.....
+                           .O.......
+                           .........
+                           .........
+                           ........."
+        .parse()
+        .unwrap();
+    let broken: Board9x9 = ".........
+                            .....O...
+                            ..X......
+                            .XO......
+                            ...X.....
+                            .O.......
+                            .........
+                            .........
+                            ........."
+        .parse()
+        .unwrap();
+    let prey = Position { x: 2, y: 3 };
+
+    // the lower left stone breaks the ladder in that direction for both
+    assert!(is_captured_in_ladder(&works, &Group::new(&works, &prey)));
+    assert!(!is_captured_in_ladder(&broken, &Group::new(&broken, &prey)));
+}
+
+#[test]
+fn ladder_in_atari() {
+    let board: Board9x9 = ".........
+                           .........
+                           ..X......
+                           .XOX.....
+                           .........
+                           .........
+                           .........
+                           .........
+                           ........."
+        .parse()
+        .unwrap();
+    let prey = Group::new(&board, &Position { x: 2, y: 3 });
+
+    // white extends downwards and escapes, the outside stone is missing
+    assert!(!is_captured_in_ladder(&board, &prey));
+
+    let empty = Group::new(&board, &Position { x: 0, y: 0 });
+    assert!(!is_captured_in_ladder(&board, &empty));
+}
+
+#[test]
+fn bent_four_in_the_corner() {
+    let board: Board9x9 = "XX.OX....
+                           .OOOX....
+                           OOXXX....
+                           XXX......
+                           .........
+                           .........
+                           .........
+                           .........
+                           ........."
+        .parse()
+        .unwrap();
+
+    let rulings = special_cases(&board);
+    assert_eq!(rulings.len(), 1);
+    assert_eq!(rulings[0].case, SpecialCase::BentFourInTheCorner);
+    assert_eq!(rulings[0].dead.len(), 6);
+    assert!(rulings[0].alive.contains(&Position { x: 0, y: 0 }));
+
+    let mut dead = vec![Position { x: 1, y: 0 }];
+    apply_special_cases(&board, &mut dead);
+    assert_eq!(dead.len(), 6);
+    assert!(dead.contains(&Position { x: 3, y: 0 }));
+    assert!(!dead.contains(&Position { x: 1, y: 0 }));
+
+    // with a liberty outside the defender can still live
+    let outside: Board9x9 = "XX.OX....
+                             .OOOX....
+                             OOXXX....
+                             .XX......
+                             .........
+                             .........
+                             .........
+                             .........
+                             ........."
+        .parse()
+        .unwrap();
+    assert!(special_cases(&outside).is_empty());
+
+    // a straight four along the edge lives after capturing the stone inside
+    let straight: Board9x9 = "...XOX...
+                              OOOOOX...
+                              XXXXXX...
+                              .........
+                              .........
+                              .........
+                              .........
+                              .........
+                              ........."
+        .parse()
+        .unwrap();
+    assert!(special_cases(&straight).is_empty());
+
+    // so does a pyramid four in the corner
+    let pyramid: Board9x9 = "X..OX....
+                             O.OOX....
+                             OOOXX....
+                             XXX......
+                             .........
+                             .........
+                             .........
+                             .........
+                             ........."
+        .parse()
+        .unwrap();
+    assert!(special_cases(&pyramid).is_empty());
+}
+
+#[test]
+fn mirror_go() {
+    let moves = [(2, 2), (6, 6), (2, 6), (6, 2), (4, 2)];
+    let mut board = Board9x9::new();
+    let mut history = Vec::new();
+    for (ply, &(x, y)) in moves.iter().enumerate() {
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        board.play(&player, &Position { x, y }).unwrap();
+        history.push((player, Position { x, y }));
+    }
+
+    // the last play is not answered yet
+    assert!(!is_mirrored(&board, &[]));
+    assert!(is_mirrored(&board, &[Position { x: 4, y: 2 }]));
+    assert_eq!(mirroring_player(&board, &history, 2), Some(Player::White));
+    assert_eq!(mirroring_player(&board, &history, 3), None);
+
+    board.play(&Player::White, &Position { x: 4, y: 6 }).unwrap();
+    history.push((Player::White, Position { x: 4, y: 6 }));
+    assert_eq!(mirroring_player(&board, &history, 3), Some(Player::White));
+
+    board.play(&Player::Black, &Position { x: 0, y: 0 }).unwrap();
+    board.play(&Player::White, &Position { x: 7, y: 7 }).unwrap();
+    history.push((Player::Black, Position { x: 0, y: 0 }));
+    history.push((Player::White, Position { x: 7, y: 7 }));
+    assert_eq!(mirroring_player(&board, &history, 1), None);
+}
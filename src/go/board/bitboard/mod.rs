@@ -0,0 +1,394 @@
+use go::{Board, GridBoard, Player, Stone};
+use go::board::{Direction, Position};
+use go::handicap::{fixed_points, max_handicap, star_points};
+
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod test;
+
+/// The number of lines of a `BitBoard19`
+const SIZE: usize = 19;
+
+/// The number of 64 bit words in a `Bits`
+const WORDS: usize = 8;
+
+/// A set of 512 bits, one per intersection (and some unused)
+///
+/// The intersection (x, y) is stored in bit y * 19 + x.
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+struct Bits([u64; WORDS]);
+
+impl Bits {
+    /// Returns the set without any bits set
+    fn empty() -> Bits {
+        Bits([0; WORDS])
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        self.0[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn insert(&mut self, index: usize) {
+        self.0[index / 64] |= 1 << (index % 64);
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.0[index / 64] &= !(1 << (index % 64));
+    }
+
+    fn count(&self) -> u32 {
+        self.0.iter().map(|w| w.count_ones()).sum()
+    }
+
+    fn and(&self, other: &Bits) -> Bits {
+        let mut bits = *self;
+        for (w, o) in bits.0.iter_mut().zip(other.0.iter()) {
+            *w &= *o;
+        }
+        bits
+    }
+
+    fn or(&self, other: &Bits) -> Bits {
+        let mut bits = *self;
+        for (w, o) in bits.0.iter_mut().zip(other.0.iter()) {
+            *w |= *o;
+        }
+        bits
+    }
+
+    fn and_not(&self, other: &Bits) -> Bits {
+        let mut bits = *self;
+        for (w, o) in bits.0.iter_mut().zip(other.0.iter()) {
+            *w &= !*o;
+        }
+        bits
+    }
+
+    /// Moves every bit to a higher index, dropping those shifted out
+    fn shift_up(&self, by: usize) -> Bits {
+        let mut bits = Bits::empty();
+        let (words, offset) = (by / 64, by % 64);
+        for i in (words..WORDS).rev() {
+            bits.0[i] = self.0[i - words] << offset;
+            if offset != 0 && i > words {
+                bits.0[i] |= self.0[i - words - 1] >> (64 - offset);
+            }
+        }
+        bits
+    }
+
+    /// Moves every bit to a lower index, dropping those shifted out
+    fn shift_down(&self, by: usize) -> Bits {
+        let mut bits = Bits::empty();
+        let (words, offset) = (by / 64, by % 64);
+        for i in 0..(WORDS - words) {
+            bits.0[i] = self.0[i + words] >> offset;
+            if offset != 0 && i + words + 1 < WORDS {
+                bits.0[i] |= self.0[i + words + 1] << (64 - offset);
+            }
+        }
+        bits
+    }
+
+    /// Returns the indices of all set bits
+    fn indices(&self) -> Vec<usize> {
+        (0..SIZE * SIZE).filter(|i| self.contains(*i)).collect()
+    }
+}
+
+/// Returns the bits of all intersections except those on the excluded column
+///
+/// Pass `SIZE` as the excluded column to get all intersections.
+const fn mask(excluded_column: usize) -> Bits {
+    let mut words = [0; WORDS];
+    let mut index = 0;
+    while index < SIZE * SIZE {
+        if index % SIZE != excluded_column {
+            words[index / 64] |= 1 << (index % 64);
+        }
+        index += 1;
+    }
+
+    Bits(words)
+}
+
+/// All intersections of the board
+const ON_BOARD: Bits = mask(SIZE);
+
+/// All intersections but those on the first column
+const NOT_FIRST_COLUMN: Bits = mask(0);
+
+/// All intersections but those on the last column
+const NOT_LAST_COLUMN: Bits = mask(SIZE - 1);
+
+impl Bits {
+    /// Returns the intersections next to the set ones
+    ///
+    /// May contain some of the set intersections.
+    fn neighbors(&self) -> Bits {
+        let east = self.and(&NOT_LAST_COLUMN).shift_up(1);
+        let west = self.and(&NOT_FIRST_COLUMN).shift_down(1);
+        let south = self.shift_up(SIZE).and(&ON_BOARD);
+        let north = self.shift_down(SIZE);
+
+        east.or(&west).or(&south).or(&north)
+    }
+
+    /// Returns the connected intersections of `within` containing the set ones
+    fn flood_fill(&self, within: &Bits) -> Bits {
+        let mut filled = *self;
+        loop {
+            let next = filled.or(&filled.neighbors()).and(within);
+            if next == filled {
+                return filled;
+            }
+            filled = next;
+        }
+    }
+}
+
+/// A 19x19 go board storing occupancy in bitsets
+///
+/// Flood fill and capture detection work on whole bitsets at once,
+/// which is a lot faster than building `Group`s from `HashSet`s.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+pub struct BitBoard19 {
+    black: Bits,
+    white: Bits,
+}
+
+impl BitBoard19 {
+    /// Returns the bitset of the stones of the given color
+    fn stones(&self, stone: Stone) -> Bits {
+        match stone {
+            Stone::Black => self.black,
+            Stone::White => self.white,
+            Stone::Empty => ON_BOARD.and_not(&self.black.or(&self.white)),
+        }
+    }
+
+    /// Returns the neighboring groups of the given position as (color, stones, liberties)
+    fn neighboring_groups(&self, position: &Position) -> Vec<(Stone, Bits, u32)> {
+        let empty = self.stones(Stone::Empty);
+        let mut seen = Bits::empty();
+        let mut groups = Vec::new();
+
+        for neighbor in self.neighbors(position) {
+            let index = index_of(&neighbor);
+            let stone = self.at(&neighbor);
+            if stone == Stone::Empty || seen.contains(index) {
+                continue;
+            }
+
+            let mut start = Bits::empty();
+            start.insert(index);
+            let group = start.flood_fill(&self.stones(stone));
+            let liberties = group.neighbors().and(&empty).count();
+
+            seen = seen.or(&group);
+            groups.push((stone, group, liberties));
+        }
+
+        groups
+    }
+}
+
+/// Returns the bit index of a position
+fn index_of(position: &Position) -> usize {
+    position.y * SIZE + position.x
+}
+
+/// Returns the position of a bit index
+fn position_of(index: usize) -> Position {
+    Position {
+        x: index % SIZE,
+        y: index / SIZE,
+    }
+}
+
+impl Board for BitBoard19 {
+    type Position = Position;
+
+    fn new() -> Self {
+        BitBoard19 {
+            black: Bits::empty(),
+            white: Bits::empty(),
+        }
+    }
+
+    fn on_board(&self, position: &Position) -> bool {
+        position.x < SIZE && position.y < SIZE
+    }
+
+    fn at(&self, position: &Position) -> Stone {
+        let index = index_of(position);
+        if self.black.contains(index) {
+            Stone::Black
+        } else if self.white.contains(index) {
+            Stone::White
+        } else {
+            Stone::Empty
+        }
+    }
+
+    fn set(&mut self, position: &Position, stone: &Stone) {
+        let index = index_of(position);
+        self.black.remove(index);
+        self.white.remove(index);
+
+        match *stone {
+            Stone::Black => self.black.insert(index),
+            Stone::White => self.white.insert(index),
+            Stone::Empty => {}
+        }
+    }
+
+    fn set_handicap(&mut self, stones: u8) {
+        for (x, y) in fixed_points(SIZE, stones).unwrap_or_default() {
+            self.set(&Position { x, y }, &Stone::Black);
+        }
+    }
+
+    fn max_handicap(&self) -> u8 {
+        max_handicap(SIZE)
+    }
+
+    fn star_points(&self) -> Vec<Position> {
+        star_points(SIZE).into_iter().map(|(x, y)| Position { x, y }).collect()
+    }
+
+    fn iter_positions<'boardlt>(&'boardlt self)
+                                -> impl Iterator<Item = Position> + 'boardlt {
+        (0..SIZE).flat_map(move |x| (0..SIZE).map(move |y| Position { x, y }))
+    }
+
+    fn for_each_neighbor<F>(&self, position: &Position, mut f: F)
+        where F: FnMut(Position)
+    {
+        if position.x + 1 < SIZE {
+            f(Position {
+                x: position.x + 1,
+                y: position.y,
+            });
+        }
+        if position.x > 0 {
+            f(Position {
+                x: position.x - 1,
+                y: position.y,
+            });
+        }
+        if position.y + 1 < SIZE {
+            f(Position {
+                x: position.x,
+                y: position.y + 1,
+            });
+        }
+        if position.y > 0 {
+            f(Position {
+                x: position.x,
+                y: position.y - 1,
+            });
+        }
+    }
+
+    fn step(&self, position: &Position, direction: Direction) -> Option<Position> {
+        position.offset(direction).filter(|p| self.on_board(p))
+    }
+
+    fn for_each_diagonal<F>(&self, position: &Position, mut f: F)
+        where F: FnMut(Position)
+    {
+        let right = position.x + 1 < SIZE;
+        let left = position.x > 0;
+        let down = position.y + 1 < SIZE;
+        let up = position.y > 0;
+
+        if right && down {
+            f(Position {
+                x: position.x + 1,
+                y: position.y + 1,
+            });
+        }
+        if right && up {
+            f(Position {
+                x: position.x + 1,
+                y: position.y - 1,
+            });
+        }
+        if left && down {
+            f(Position {
+                x: position.x - 1,
+                y: position.y + 1,
+            });
+        }
+        if left && up {
+            f(Position {
+                x: position.x - 1,
+                y: position.y - 1,
+            });
+        }
+    }
+
+    fn would_be_captured(&self, player: &Player, position: &Position) -> HashSet<Position> {
+        if self.at(position) != Stone::Empty {
+            return HashSet::new();
+        }
+
+        let mut captured = Bits::empty();
+        for (stone, group, liberties) in self.neighboring_groups(position) {
+            if stone != player.stone() && liberties == 1 {
+                captured = captured.or(&group);
+            }
+        }
+
+        captured.indices().into_iter().map(position_of).collect()
+    }
+
+    fn would_capture_any(&self, player: &Player, position: &Position) -> bool {
+        self.at(position) == Stone::Empty &&
+        self.neighboring_groups(position)
+            .iter()
+            .any(|&(stone, _, liberties)| stone == player.other().stone() && liberties == 1)
+    }
+
+    fn would_be_suicide(&self, position: &Position, player: &Player) -> bool {
+        if self.at(position) != Stone::Empty {
+            return false;
+        }
+
+        let mut single = Bits::empty();
+        single.insert(index_of(position));
+        if single.neighbors().and(&self.stones(Stone::Empty)).count() > 0 {
+            return false; //there is a free neighbor
+        }
+
+        !self.neighboring_groups(position).iter().any(|&(stone, _, liberties)| {
+            (stone == player.other().stone() && liberties == 1) ||
+            (stone == player.stone() && liberties > 1)
+        })
+    }
+
+    /// Checks every intersection with the bitset based `would_be_suicide`
+    fn legal_moves<'boardlt>(&'boardlt self,
+                             player: &Player)
+                             -> impl Iterator<Item = Position> + 'boardlt {
+        let player = *player;
+        self.iter_positions()
+            .filter(move |p| self.at(p) == Stone::Empty && !self.would_be_suicide(p, &player))
+    }
+}
+
+impl GridBoard for BitBoard19 {
+    fn size(&self) -> usize {
+        SIZE
+    }
+
+    fn position(&self, x: usize, y: usize) -> Position {
+        Position { x, y }
+    }
+
+    fn coordinates(&self, position: &Position) -> (usize, usize) {
+        (position.x, position.y)
+    }
+}
@@ -0,0 +1,85 @@
+use aga::Board19x19;
+use go::board::{BitBoard19, Position};
+use go::{Board, Group, Player, Stone};
+
+#[test]
+fn set() {
+    let mut board = BitBoard19::new();
+
+    board.set(&Position { x: 18, y: 18 }, &Stone::Black);
+    board.set(&Position { x: 3, y: 7 }, &Stone::White);
+    assert_eq!(board.at(&Position { x: 18, y: 18 }), Stone::Black);
+    assert_eq!(board.at(&Position { x: 3, y: 7 }), Stone::White);
+
+    board.set(&Position { x: 18, y: 18 }, &Stone::Empty);
+    assert_eq!(board.at(&Position { x: 18, y: 18 }), Stone::Empty);
+}
+
+#[test]
+fn would_be_captured() {
+    let mut board = BitBoard19::new();
+
+    board.set(&Position { x: 18, y: 0 }, &Stone::White); // .OTO
+    board.set(&Position { x: 18, y: 1 }, &Stone::Black); // .XOX
+    board.set(&Position { x: 17, y: 1 }, &Stone::White); //  .X.
+    board.set(&Position { x: 17, y: 2 }, &Stone::Black); //   .
+    board.set(&Position { x: 16, y: 0 }, &Stone::White); // gonna play with X at T
+    board.set(&Position { x: 16, y: 1 }, &Stone::Black); // should capture both white stones
+
+    let captured = board.would_be_captured(&Player::Black, &Position { x: 17, y: 0 });
+    assert_eq!(captured.len(), 2);
+    assert!(captured.contains(&Position { x: 18, y: 0 }));
+    assert!(captured.contains(&Position { x: 17, y: 1 }));
+}
+
+#[test]
+fn no_capture_across_edge() {
+    let mut board = BitBoard19::new();
+
+    board.set(&Position { x: 0, y: 1 }, &Stone::White); // white at the left edge
+    board.set(&Position { x: 0, y: 0 }, &Stone::Black); // surrounded on the board,
+    board.set(&Position { x: 1, y: 1 }, &Stone::Black); // the right edge of the row
+    board.set(&Position { x: 18, y: 0 }, &Stone::Black); // above is no neighbor
+
+    assert!(board.would_be_captured(&Player::Black, &Position { x: 0, y: 2 }).len() == 1);
+    assert!(board.would_be_captured(&Player::Black, &Position { x: 18, y: 1 }).is_empty());
+}
+
+#[test]
+fn same_as_array_board() {
+    let mut bits = BitBoard19::new();
+    let mut array = Board19x19::new();
+    let mut seed: u64 = 7;
+
+    for ply in 0..600 {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let position = Position {
+            x: (seed >> 33) as usize % 19,
+            y: (seed >> 45) as usize % 19,
+        };
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+
+        assert_eq!(bits.would_be_captured(&player, &position),
+                   array.would_be_captured(&player, &position));
+        assert_eq!(bits.would_be_suicide(&position, &player),
+                   array.would_be_suicide(&position, &player));
+        assert_eq!(bits.would_capture_any(&player, &position),
+                   !array.would_be_captured(&player, &position).is_empty());
+        assert_eq!(array.would_capture_any(&player, &position),
+                   !array.would_be_captured(&player, &position).is_empty());
+
+        if bits.at(&position) == Stone::Empty && !bits.would_be_suicide(&position, &player) {
+            for captured in &bits.would_be_captured(&player, &position) {
+                bits.set(captured, &Stone::Empty);
+                array.set(captured, &Stone::Empty);
+            }
+            bits.set(&position, &player.stone());
+            array.set(&position, &player.stone());
+        }
+
+        assert_eq!(Group::new(&bits, &position).liberties(),
+                   Group::new(&array, &position).liberties());
+    }
+
+    assert_eq!(bits.area_scoring(), array.area_scoring());
+}
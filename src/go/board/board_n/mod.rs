@@ -0,0 +1,261 @@
+use go::{Board, GridBoard, HandicapTable, Stone};
+use go::handicap::{fixed_points, max_handicap, star_points};
+
+use std::cmp::Ordering;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod test;
+
+/// The smallest number of lines a `BoardN` can have
+pub const MIN_SIZE: usize = 5;
+
+/// The largest number of lines a `BoardN` can have
+pub const MAX_SIZE: usize = 25;
+
+/// A position on a `BoardN`
+///
+/// Whether it is on the board depends on the size of the board.
+#[derive(Copy, Hash, Eq, PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// One of the four directions along the lines of a board
+///
+/// Up is towards the top row (y = 0), left towards the first column.
+#[derive(Copy, Hash, Eq, PartialEq, Clone, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// All directions in the order up, down, left, right
+    pub const ALL: [Direction; 4] =
+        [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    /// Returns the direction pointing the other way
+    pub fn opposite(&self) -> Direction {
+        match *self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+impl Position {
+    /// Returns the position one step in the given direction
+    ///
+    /// Returns None when stepping above the top row or left of the first
+    /// column. The other edges depend on the board, see `Board::step`.
+    pub fn offset(&self, direction: Direction) -> Option<Position> {
+        match direction {
+            Direction::Up if self.y > 0 => Some(Position { x: self.x, y: self.y - 1 }),
+            Direction::Down => Some(Position { x: self.x, y: self.y + 1 }),
+            Direction::Left if self.x > 0 => Some(Position { x: self.x - 1, y: self.y }),
+            Direction::Right => Some(Position { x: self.x + 1, y: self.y }),
+            _ => None,
+        }
+    }
+}
+
+impl Ord for Position {
+    /// Orders positions row by row like they are read on a diagram
+    fn cmp(&self, other: &Position) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Position {
+    fn partial_cmp(&self, other: &Position) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Position {
+    /// Writes the coordinates as (x, y)
+    ///
+    /// Board notation like "D4" depends on the size of the board, see
+    /// `formats::Coordinates`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A square go board with a size chosen at runtime
+///
+/// Stores the intersections row by row.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+pub struct BoardN {
+    size: usize,
+    state: Vec<Stone>,
+}
+
+impl BoardN {
+    /// Constructs a new empty board with the given number of lines
+    ///
+    /// Returns None if the size is not between `MIN_SIZE` and `MAX_SIZE`.
+    pub fn with_size(size: usize) -> Option<BoardN> {
+        if (MIN_SIZE..=MAX_SIZE).contains(&size) {
+            Some(BoardN {
+                size,
+                state: vec![Stone::Empty; size * size],
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of lines of the board
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Sets the requested amount of handicap stones as given by the table
+    ///
+    /// Sets nothing if the table has no pattern for this board size and
+    /// that many stones.
+    pub fn set_handicap_from(&mut self, table: &HandicapTable, stones: u8) {
+        if let Some(placements) = table.placements(self.size, stones) {
+            for &(x, y) in placements {
+                self.set(&Position { x, y }, &Stone::Black);
+            }
+        }
+    }
+}
+
+impl Board for BoardN {
+    type Position = Position;
+
+    /// Constructs a new empty 19x19 board
+    fn new() -> Self {
+        BoardN::with_size(19).unwrap()
+    }
+
+    fn on_board(&self, position: &Position) -> bool {
+        position.x < self.size && position.y < self.size
+    }
+
+    /// Panics if the position is not on the board
+    fn at(&self, position: &Position) -> Stone {
+        assert!(self.on_board(position), "{} is not on the board", position);
+        self.state[position.y * self.size + position.x]
+    }
+
+    /// Panics if the position is not on the board
+    fn set(&mut self, position: &Position, stone: &Stone) {
+        assert!(self.on_board(position), "{} is not on the board", position);
+        self.state[position.y * self.size + position.x] = *stone;
+    }
+
+    fn set_handicap(&mut self, stones: u8) {
+        for (x, y) in fixed_points(self.size, stones).unwrap_or_default() {
+            self.set(&Position { x, y }, &Stone::Black);
+        }
+    }
+
+    fn max_handicap(&self) -> u8 {
+        max_handicap(self.size)
+    }
+
+    fn star_points(&self) -> Vec<Position> {
+        star_points(self.size).into_iter().map(|(x, y)| Position { x, y }).collect()
+    }
+
+    fn iter_positions<'boardlt>(&'boardlt self)
+                                -> impl Iterator<Item = Position> + 'boardlt {
+        let size = self.size;
+        (0..size).flat_map(move |x| (0..size).map(move |y| Position { x, y }))
+    }
+
+    fn for_each_neighbor<F>(&self, position: &Position, mut f: F)
+        where F: FnMut(Position)
+    {
+        if position.x + 1 < self.size {
+            f(Position {
+                x: position.x + 1,
+                y: position.y,
+            });
+        }
+        if position.x > 0 {
+            f(Position {
+                x: position.x - 1,
+                y: position.y,
+            });
+        }
+        if position.y + 1 < self.size {
+            f(Position {
+                x: position.x,
+                y: position.y + 1,
+            });
+        }
+        if position.y > 0 {
+            f(Position {
+                x: position.x,
+                y: position.y - 1,
+            });
+        }
+    }
+
+    fn step(&self, position: &Position, direction: Direction) -> Option<Position> {
+        position.offset(direction).filter(|p| self.on_board(p))
+    }
+
+    fn for_each_diagonal<F>(&self, position: &Position, mut f: F)
+        where F: FnMut(Position)
+    {
+        let right = position.x + 1 < self.size;
+        let left = position.x > 0;
+        let down = position.y + 1 < self.size;
+        let up = position.y > 0;
+
+        if right && down {
+            f(Position {
+                x: position.x + 1,
+                y: position.y + 1,
+            });
+        }
+        if right && up {
+            f(Position {
+                x: position.x + 1,
+                y: position.y - 1,
+            });
+        }
+        if left && down {
+            f(Position {
+                x: position.x - 1,
+                y: position.y + 1,
+            });
+        }
+        if left && up {
+            f(Position {
+                x: position.x - 1,
+                y: position.y - 1,
+            });
+        }
+    }
+}
+
+impl GridBoard for BoardN {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn position(&self, x: usize, y: usize) -> Position {
+        Position { x, y }
+    }
+
+    fn coordinates(&self, position: &Position) -> (usize, usize) {
+        (position.x, position.y)
+    }
+}
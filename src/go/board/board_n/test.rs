@@ -0,0 +1,83 @@
+use go::board::{BoardN, Direction, Position};
+use go::{Board, Group, Stone};
+
+#[test]
+fn with_size() {
+    assert!(BoardN::with_size(4).is_none());
+    assert!(BoardN::with_size(26).is_none());
+
+    let board = BoardN::with_size(9).unwrap();
+    assert_eq!(board.size(), 9);
+    assert_eq!(board.positions().len(), 81);
+    assert!(board.on_board(&Position { x: 8, y: 8 }));
+    assert!(!board.on_board(&Position { x: 9, y: 0 }));
+}
+
+#[test]
+#[should_panic(expected = "(9, 0) is not on the board")]
+fn at_off_board() {
+    let board = BoardN::with_size(9).unwrap();
+    board.at(&Position { x: 9, y: 0 });
+}
+
+#[test]
+#[should_panic(expected = "(9, 0) is not on the board")]
+fn set_off_board() {
+    let mut board = BoardN::with_size(9).unwrap();
+    board.set(&Position { x: 9, y: 0 }, &Stone::Black);
+}
+
+#[test]
+fn neighbors() {
+    let board = BoardN::with_size(5).unwrap();
+
+    assert_eq!(board.neighbors(&Position { x: 4, y: 2 }).len(), 3);
+    assert_eq!(board.neighbors(&Position { x: 2, y: 2 }).len(), 4);
+    assert_eq!(board.neighbors(&Position { x: 4, y: 4 }).len(), 2);
+}
+
+#[test]
+fn liberties() {
+    let mut board = BoardN::with_size(7).unwrap();
+    board.set(&Position { x: 6, y: 5 }, &Stone::White); //  .
+    board.set(&Position { x: 6, y: 6 }, &Stone::White); // .O
+    board.set(&Position { x: 5, y: 6 }, &Stone::Black); // XO
+
+    let white_group = Group::new(&board, &Position { x: 6, y: 6 });
+    assert_eq!(white_group.liberties().len(), 2);
+}
+
+#[test]
+fn position_order() {
+    let mut positions = vec![Position { x: 2, y: 1 },
+                             Position { x: 0, y: 2 },
+                             Position { x: 1, y: 1 },
+                             Position { x: 3, y: 0 }];
+    positions.sort();
+
+    assert_eq!(positions,
+               vec![Position { x: 3, y: 0 },
+                    Position { x: 1, y: 1 },
+                    Position { x: 2, y: 1 },
+                    Position { x: 0, y: 2 }]);
+    assert_eq!(Position { x: 3, y: 15 }.to_string(), "(3, 15)");
+}
+
+#[test]
+fn step() {
+    let board = BoardN::with_size(5).unwrap();
+    let corner = Position { x: 0, y: 0 };
+
+    assert_eq!(corner.offset(Direction::Up), None);
+    assert_eq!(corner.offset(Direction::Left), None);
+    assert_eq!(corner.offset(Direction::Right), Some(Position { x: 1, y: 0 }));
+    assert_eq!(board.step(&corner, Direction::Down), Some(Position { x: 0, y: 1 }));
+    assert_eq!(board.step(&Position { x: 4, y: 2 }, Direction::Right), None);
+    assert_eq!(board.step(&Position { x: 2, y: 4 }, Direction::Down), None);
+
+    for direction in &Direction::ALL {
+        let center = Position { x: 2, y: 2 };
+        let next = board.step(&center, *direction).unwrap();
+        assert_eq!(board.step(&next, direction.opposite()), Some(center));
+    }
+}
@@ -0,0 +1,191 @@
+use go::{Board, CaptureInfo, GridBoard, PlayError, Player, Stone, ZobristHash};
+use go::board::Direction;
+
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+#[cfg(test)]
+mod test;
+
+/// A board that counts the prisoners of both players
+///
+/// Every stone removed by `play` is a prisoner of the player who made
+/// the capture. Setting stones directly is not counted.
+#[derive(Clone, Debug)]
+pub struct CapturingBoard<TBoard>
+    where TBoard: Board
+{
+    board: TBoard,
+    /// The stones black has captured
+    prisoners_black: usize,
+    /// The stones white has captured
+    prisoners_white: usize,
+}
+
+impl<TBoard> CapturingBoard<TBoard>
+    where TBoard: Board
+{
+    /// Wraps the board, starting without prisoners
+    pub fn from_board(board: TBoard) -> Self {
+        CapturingBoard {
+            board,
+            prisoners_black: 0,
+            prisoners_white: 0,
+        }
+    }
+
+    /// Returns the wrapped board
+    pub fn board(&self) -> &TBoard {
+        &self.board
+    }
+
+    /// Returns the number of stones the given player has captured
+    pub fn prisoners(&self, player: Player) -> usize {
+        match player {
+            Player::Black => self.prisoners_black,
+            Player::White => self.prisoners_white,
+        }
+    }
+
+    /// Returns (black_score, white_score) using territory scoring with
+    /// the prisoners taken so far
+    pub fn territory_scoring_with_prisoners(&self,
+                                            dead_stones: &[TBoard::Position])
+                                            -> (usize, usize) {
+        self.board.territory_scoring(dead_stones, self.prisoners_black, self.prisoners_white)
+    }
+}
+
+impl<TBoard> PartialEq for CapturingBoard<TBoard>
+    where TBoard: Board
+{
+    /// Compares only the stones since prisoners do not change the position
+    fn eq(&self, other: &Self) -> bool {
+        self.board == other.board
+    }
+}
+
+impl<TBoard> Eq for CapturingBoard<TBoard> where TBoard: Board {}
+
+impl<TBoard> Hash for CapturingBoard<TBoard>
+    where TBoard: Board
+{
+    /// Hashes only the wrapped board like `eq` compares it
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.board.hash(state);
+    }
+}
+
+impl<TBoard> Board for CapturingBoard<TBoard>
+    where TBoard: Board
+{
+    type Position = TBoard::Position;
+
+    fn new() -> Self {
+        CapturingBoard::from_board(TBoard::new())
+    }
+
+    fn on_board(&self, position: &Self::Position) -> bool {
+        self.board.on_board(position)
+    }
+
+    fn at(&self, position: &Self::Position) -> Stone {
+        self.board.at(position)
+    }
+
+    fn set(&mut self, position: &Self::Position, stone: &Stone) {
+        self.board.set(position, stone);
+    }
+
+    fn set_handicap(&mut self, stones: u8) {
+        self.board.set_handicap(stones);
+    }
+
+    fn max_handicap(&self) -> u8 {
+        self.board.max_handicap()
+    }
+
+    fn star_points(&self) -> Vec<Self::Position> {
+        self.board.star_points()
+    }
+
+    fn iter_positions<'boardlt>(&'boardlt self)
+                                -> impl Iterator<Item = Self::Position> + 'boardlt {
+        self.board.iter_positions()
+    }
+
+    fn for_each_neighbor<F>(&self, position: &Self::Position, f: F)
+        where F: FnMut(Self::Position)
+    {
+        self.board.for_each_neighbor(position, f);
+    }
+
+    fn step(&self, position: &Self::Position, direction: Direction) -> Option<Self::Position> {
+        self.board.step(position, direction)
+    }
+
+    fn for_each_diagonal<F>(&self, position: &Self::Position, f: F)
+        where F: FnMut(Self::Position)
+    {
+        self.board.for_each_diagonal(position, f);
+    }
+
+    fn would_capture_any(&self, player: &Player, position: &Self::Position) -> bool {
+        self.board.would_capture_any(player, position)
+    }
+
+    fn would_be_captured(&self,
+                         player: &Player,
+                         position: &Self::Position)
+                         -> HashSet<Self::Position> {
+        self.board.would_be_captured(player, position)
+    }
+
+    fn would_be_suicide(&self, position: &Self::Position, player: &Player) -> bool {
+        self.board.would_be_suicide(position, player)
+    }
+
+    fn legal_moves<'boardlt>(&'boardlt self,
+                             player: &Player)
+                             -> impl Iterator<Item = Self::Position> + 'boardlt {
+        self.board.legal_moves(player)
+    }
+
+    /// Plays on the wrapped board and counts the captured stones
+    fn play(&mut self,
+            player: &Player,
+            position: &Self::Position)
+            -> Result<CaptureInfo<Self::Position>, PlayError> {
+        let info = self.board.play(player, position)?;
+        match *player {
+            Player::Black => self.prisoners_black += info.captured.len(),
+            Player::White => self.prisoners_white += info.captured.len(),
+        }
+
+        Ok(info)
+    }
+}
+
+impl<TBoard> GridBoard for CapturingBoard<TBoard>
+    where TBoard: GridBoard
+{
+    fn size(&self) -> usize {
+        self.board.size()
+    }
+
+    fn position(&self, x: usize, y: usize) -> Self::Position {
+        self.board.position(x, y)
+    }
+
+    fn coordinates(&self, position: &Self::Position) -> (usize, usize) {
+        self.board.coordinates(position)
+    }
+}
+
+impl<TBoard> ZobristHash for CapturingBoard<TBoard>
+    where TBoard: ZobristHash
+{
+    fn zobrist_hash(&self) -> u64 {
+        self.board.zobrist_hash()
+    }
+}
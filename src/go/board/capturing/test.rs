@@ -0,0 +1,24 @@
+use aga::{Board9x9, Position19x19};
+use go::board::CapturingBoard;
+use go::{Board, Player};
+
+#[test]
+fn prisoners() {
+    let board: Board9x9 = ".XO......
+                           XO.......
+                           O........"
+        .parse()
+        .unwrap();
+    let mut board = CapturingBoard::from_board(board);
+
+    // capturing the corner stone and then the stone at 1-0
+    board.play(&Player::White, &Position19x19 { x: 0, y: 0 }).unwrap();
+    assert_eq!(board.prisoners(Player::White), 2);
+    assert_eq!(board.prisoners(Player::Black), 0);
+
+    assert!(board.play(&Player::Black, &Position19x19 { x: 0, y: 0 }).is_err());
+    assert_eq!(board.prisoners(Player::Black), 0);
+
+    // white surrounds every empty intersection and holds two prisoners
+    assert_eq!(board.territory_scoring_with_prisoners(&[]), (0, 79));
+}
@@ -0,0 +1,81 @@
+use go::Board;
+
+/// A rectangle of intersections given by coordinates
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Rect {
+    /// The column of the upper left corner
+    pub x: usize,
+    /// The row of the upper left corner
+    pub y: usize,
+    /// The number of columns
+    pub width: usize,
+    /// The number of rows
+    pub height: usize,
+}
+
+impl Rect {
+    /// Returns true if the coordinates lie inside the rectangle
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        self.x <= x && x < self.x + self.width && self.y <= y && y < self.y + self.height
+    }
+}
+
+/// One of the four corners of a board
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Corner {
+    UpperLeft,
+    UpperRight,
+    LowerLeft,
+    LowerRight,
+}
+
+/// A board whose intersections can be addressed by (x, y) coordinates
+///
+/// Implementing this makes a board usable as a `DynBoard` by
+/// wrapping it into an `ErasedBoard`.
+pub trait GridBoard: Board {
+    /// Returns the number of lines of the board
+    fn size(&self) -> usize;
+
+    /// Returns the position at the given coordinates
+    fn position(&self, x: usize, y: usize) -> Self::Position;
+
+    /// Returns the coordinates of the given position
+    fn coordinates(&self, position: &Self::Position) -> (usize, usize);
+
+    /// Returns an iterator over the positions inside the rectangle
+    fn region<'boardlt>(&'boardlt self,
+                        region: Rect)
+                        -> impl Iterator<Item = Self::Position> + 'boardlt {
+        self.iter_positions().filter(move |p| {
+            let (x, y) = self.coordinates(p);
+            region.contains(x, y)
+        })
+    }
+
+    /// Returns an iterator over the positions in the quarter of the board
+    /// at the given corner
+    ///
+    /// A quadrant spans half of the lines, rounded up. The middle lines
+    /// of boards with an odd size are part of both adjacent quadrants.
+    fn quadrant<'boardlt>(&'boardlt self,
+                          corner: Corner)
+                          -> impl Iterator<Item = Self::Position> + 'boardlt {
+        let size = self.size();
+        let half = size.div_ceil(2);
+        let far = size - half;
+        let (x, y) = match corner {
+            Corner::UpperLeft => (0, 0),
+            Corner::UpperRight => (far, 0),
+            Corner::LowerLeft => (0, far),
+            Corner::LowerRight => (far, far),
+        };
+
+        self.region(Rect {
+            x,
+            y,
+            width: half,
+            height: half,
+        })
+    }
+}
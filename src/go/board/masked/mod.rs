@@ -0,0 +1,153 @@
+use go::{Board, GridBoard, Stone};
+use go::board::Direction;
+
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+#[cfg(test)]
+mod test;
+
+/// A board with holes or an irregular outline
+///
+/// Wraps a base board and a set of masked intersections that are
+/// treated as if they were not part of the board: they are neither
+/// on the board, nor neighbors of anything, nor ever occupied.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MaskedBoard<TBoard>
+    where TBoard: Board
+{
+    board: TBoard,
+    mask: HashSet<TBoard::Position>,
+}
+
+impl<TBoard> MaskedBoard<TBoard>
+    where TBoard: Board
+{
+    /// Constructs a masked board from a base board and the masked positions
+    ///
+    /// Removes stones the base board has on masked positions.
+    pub fn with_mask(board: TBoard, mask: HashSet<TBoard::Position>) -> Self {
+        let mut masked = MaskedBoard { board, mask };
+        masked.clear_mask();
+        masked
+    }
+
+    /// Returns the set of masked positions
+    pub fn mask(&self) -> &HashSet<TBoard::Position> {
+        &self.mask
+    }
+
+    /// Returns true if the position is masked
+    pub fn is_masked(&self, position: &TBoard::Position) -> bool {
+        self.mask.contains(position)
+    }
+
+    /// Removes all stones on masked positions from the base board
+    fn clear_mask(&mut self) {
+        for position in &self.mask {
+            self.board.set(position, &Stone::Empty);
+        }
+    }
+}
+
+impl<TBoard> Hash for MaskedBoard<TBoard>
+    where TBoard: Board
+{
+    /// Hashes only the base board since the mask can not be hashed
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.board.hash(state);
+    }
+}
+
+impl<TBoard> Board for MaskedBoard<TBoard>
+    where TBoard: Board
+{
+    type Position = TBoard::Position;
+
+    /// Constructs a new empty board without any masked positions
+    fn new() -> Self {
+        MaskedBoard {
+            board: TBoard::new(),
+            mask: HashSet::new(),
+        }
+    }
+
+    fn on_board(&self, position: &Self::Position) -> bool {
+        self.board.on_board(position) && !self.is_masked(position)
+    }
+
+    fn at(&self, position: &Self::Position) -> Stone {
+        self.board.at(position)
+    }
+
+    /// Sets the stone at the given position
+    ///
+    /// Does nothing if the position is masked.
+    fn set(&mut self, position: &Self::Position, stone: &Stone) {
+        if !self.is_masked(position) {
+            self.board.set(position, stone);
+        }
+    }
+
+    /// Sets the requested amount of handicap stones
+    ///
+    /// Handicap stones of the base board that fall on masked positions
+    /// are left out.
+    fn set_handicap(&mut self, stones: u8) {
+        self.board.set_handicap(stones);
+        self.clear_mask();
+    }
+
+    fn max_handicap(&self) -> u8 {
+        self.board.max_handicap()
+    }
+
+    fn star_points(&self) -> Vec<Self::Position> {
+        self.board.star_points().into_iter().filter(|p| !self.is_masked(p)).collect()
+    }
+
+    fn iter_positions<'boardlt>(&'boardlt self)
+                                -> impl Iterator<Item = Self::Position> + 'boardlt {
+        self.board.iter_positions().filter(move |p| !self.is_masked(p))
+    }
+
+    fn for_each_neighbor<F>(&self, position: &Self::Position, mut f: F)
+        where F: FnMut(Self::Position)
+    {
+        self.board.for_each_neighbor(position, |p| {
+            if !self.is_masked(&p) {
+                f(p);
+            }
+        });
+    }
+
+    fn step(&self, position: &Self::Position, direction: Direction) -> Option<Self::Position> {
+        self.board.step(position, direction).filter(|p| !self.is_masked(p))
+    }
+
+    fn for_each_diagonal<F>(&self, position: &Self::Position, mut f: F)
+        where F: FnMut(Self::Position)
+    {
+        self.board.for_each_diagonal(position, |p| {
+            if !self.is_masked(&p) {
+                f(p);
+            }
+        });
+    }
+}
+
+impl<TBoard> GridBoard for MaskedBoard<TBoard>
+    where TBoard: GridBoard
+{
+    fn size(&self) -> usize {
+        self.board.size()
+    }
+
+    fn position(&self, x: usize, y: usize) -> Self::Position {
+        self.board.position(x, y)
+    }
+
+    fn coordinates(&self, position: &Self::Position) -> (usize, usize) {
+        self.board.coordinates(position)
+    }
+}
@@ -0,0 +1,61 @@
+use go::board::{Direction, MaskedBoard, Position, SquareBoard};
+use go::{Board, Group, Stone};
+
+type Board5x5 = SquareBoard<5>;
+
+fn with_hole() -> MaskedBoard<Board5x5> {
+    // .....
+    // .....
+    // ..#..
+    // .....
+    // .....
+    let mask = vec![Position { x: 2, y: 2 }].into_iter().collect();
+    MaskedBoard::with_mask(Board5x5::new(), mask)
+}
+
+#[test]
+fn positions() {
+    let board = with_hole();
+
+    assert_eq!(board.positions().len(), 24);
+    assert!(!board.on_board(&Position { x: 2, y: 2 }));
+    assert!(board.on_board(&Position { x: 2, y: 1 }));
+    assert_eq!(board.neighbors(&Position { x: 2, y: 1 }).len(), 3);
+    assert_eq!(board.diagonals(&Position { x: 1, y: 1 }).len(), 3);
+}
+
+#[test]
+fn set() {
+    let mut board = with_hole();
+
+    board.set(&Position { x: 2, y: 2 }, &Stone::Black);
+    assert_eq!(board.at(&Position { x: 2, y: 2 }), Stone::Empty);
+}
+
+#[test]
+fn liberties() {
+    let mut board = with_hole();
+    board.set(&Position { x: 2, y: 1 }, &Stone::White);
+    board.set(&Position { x: 1, y: 1 }, &Stone::Black);
+
+    let white_group = Group::new(&board, &Position { x: 2, y: 1 });
+    assert_eq!(white_group.liberties().len(), 2);
+}
+
+#[test]
+fn star_points() {
+    let mask = vec![Position { x: 4, y: 4 }].into_iter().collect();
+    let board = MaskedBoard::with_mask(SquareBoard::<9>::new(), mask);
+
+    assert_eq!(board.star_points().len(), 4);
+    assert!(!board.star_points().contains(&Position { x: 4, y: 4 }));
+}
+
+#[test]
+fn step_into_hole() {
+    let board = with_hole();
+
+    assert_eq!(board.step(&Position { x: 2, y: 1 }, Direction::Down), None);
+    assert_eq!(board.step(&Position { x: 2, y: 1 }, Direction::Up),
+               Some(Position { x: 2, y: 0 }));
+}
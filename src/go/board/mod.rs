@@ -1,17 +1,79 @@
-use go::{Group, Player, Stone};
+use go::{Group, GroupCache, Player, Stone};
+use go::group;
 
+use std::fmt::Debug;
 use std::hash::Hash;
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+
+pub mod bitboard;
+pub mod board_n;
+pub mod capturing;
+pub mod grid;
+pub mod masked;
+pub mod square;
+pub mod torus;
+pub mod tracked;
+
+pub use self::bitboard::BitBoard19;
+pub use self::board_n::{BoardN, Direction, Position, MIN_SIZE, MAX_SIZE};
+pub use self::capturing::CapturingBoard;
+pub use self::grid::{Corner, GridBoard, Rect};
+pub use self::masked::MaskedBoard;
+pub use self::square::SquareBoard;
+pub use self::torus::TorusBoard;
+pub use self::tracked::TrackedBoard;
 
 #[cfg(test)]
 mod test;
 
+/// The stones captured by a play
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CaptureInfo<TPosition>
+    where TPosition: Eq + Hash
+{
+    /// The positions of the removed stones
+    pub captured: HashSet<TPosition>,
+}
+
+/// The reasons a play can be rejected by the board
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PlayError {
+    /// The position is not on the board
+    OffBoard,
+    /// There is already a stone at the position
+    Occupied,
+    /// The stone would have no liberties
+    Suicide,
+}
+
+/// The reasons an encoded board can not be decoded
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DecodeError {
+    /// The number of bytes does not match the number of intersections
+    WrongLength,
+    /// An intersection holds the unused bit pattern 0b11
+    InvalidStone,
+}
+
+/// Aggregates over the groups of one color
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct GroupStatistics {
+    /// The number of groups
+    pub groups: usize,
+    /// The number of groups with exactly one liberty
+    pub in_atari: usize,
+    /// The fewest liberties of any group, 0 if there are no groups
+    pub min_liberties: usize,
+    /// The most liberties of any group, 0 if there are no groups
+    pub max_liberties: usize,
+}
+
 /// The board trait
 ///
 /// If something implements this, go can be played on it
 pub trait Board: Sized + Eq + Hash + Clone {
     /// The Position the board uses
-    type Position: Sized + Eq + Hash + Copy + Clone;
+    type Position: Sized + Eq + Hash + Copy + Clone + Debug;
 
     /// Constructs a new empty board
     fn new() -> Self;
@@ -25,35 +87,250 @@ pub trait Board: Sized + Eq + Hash + Clone {
     /// Sets the stone at the given position
     fn set(&mut self, position: &Self::Position, stone: &Stone);
 
+    /// Sets the stones at the given positions
+    fn set_many(&mut self, stones: &[(Self::Position, Stone)]) {
+        for (position, stone) in stones {
+            self.set(position, stone);
+        }
+    }
+
+    /// Sets every intersection of the board to the given stone
+    fn fill(&mut self, stone: Stone) {
+        for position in self.positions() {
+            self.set(&position, &stone);
+        }
+    }
+
+    /// Removes all stones inside the rectangle
+    fn clear_region(&mut self, region: &Rect)
+        where Self: GridBoard
+    {
+        let positions: Vec<_> = self.region(*region).collect();
+
+        for position in &positions {
+            self.set(position, &Stone::Empty);
+        }
+    }
+
     /// Sets the requested amount of handicap stones
+    ///
+    /// Sets nothing for more stones than `max_handicap`.
     fn set_handicap(&mut self, stones: u8);
 
+    /// Returns the largest handicap `set_handicap` can place
+    ///
+    /// Zero for boards without fixed handicap placements.
+    fn max_handicap(&self) -> u8 {
+        0
+    }
+
+    /// Returns the star points (hoshi) of the board
+    ///
+    /// Empty for boards without star points.
+    fn star_points(&self) -> Vec<Self::Position> {
+        Vec::new()
+    }
+
+    /// Returns an iterator over all positions
+    fn iter_positions<'boardlt>(&'boardlt self)
+                                -> impl Iterator<Item = Self::Position> + 'boardlt;
+
     /// Returns all positions.
-    fn positions(&self) -> Vec<Self::Position>;
+    ///
+    /// Allocates, prefer `iter_positions` where a vector is not needed.
+    fn positions(&self) -> Vec<Self::Position> {
+        self.iter_positions().collect()
+    }
+
+    /// Calls f with every position next to the given position
+    ///
+    /// Does not only visit occupied fields but also empty ones.
+    fn for_each_neighbor<F>(&self, position: &Self::Position, f: F)
+        where F: FnMut(Self::Position);
+
+    /// Returns the position next to the given one in the given direction
+    ///
+    /// Returns None if that step leaves the board.
+    fn step(&self, position: &Self::Position, direction: Direction) -> Option<Self::Position>;
+
+    /// Returns the number of steps from the position to the nearest edge
+    ///
+    /// Positions on the first line have a distance of 0. Masked
+    /// intersections count as edges. Returns None if the board has no
+    /// edges, like a torus.
+    fn distance_to_edge(&self, position: &Self::Position) -> Option<usize> {
+        let limit = self.iter_positions().count();
+
+        Direction::ALL
+            .iter()
+            .filter_map(|&direction| {
+                let mut current = *position;
+                let mut steps = 0;
+                while let Some(next) = self.step(&current, direction) {
+                    steps += 1;
+                    if steps > limit {
+                        return None;
+                    }
+                    current = next;
+                }
+                Some(steps)
+            })
+            .min()
+    }
+
+    /// Returns the line the position is on, 1 for the first line
+    ///
+    /// Returns None if the board has no edges.
+    fn line_of(&self, position: &Self::Position) -> Option<usize> {
+        self.distance_to_edge(position).map(|distance| distance + 1)
+    }
 
     /// Returns the vector of stone next to the given position
     ///
     /// Does not only return occupied fields but also empty ones.
-    fn neighbors(&self, position: &Self::Position) -> Vec<Self::Position>;
+    /// Allocates, prefer `for_each_neighbor` in hot loops.
+    fn neighbors(&self, position: &Self::Position) -> Vec<Self::Position> {
+        let mut n = Vec::<Self::Position>::new();
+        self.for_each_neighbor(position, |p| n.push(p));
+        n
+    }
 
-    /// Returns the vector of groups that have a liberty at the given position
-    fn groups_with_liberty_at<'boardlt>(&'boardlt self,
-                                        position: &Self::Position)
-                                        -> Vec<Group<'boardlt, Self>> {
-        if self.at(position) != Stone::Empty {
-            return Vec::new();
+    /// Calls f with every position diagonally next to the given position
+    ///
+    /// Respects the edges and shape of the board like `for_each_neighbor`.
+    fn for_each_diagonal<F>(&self, position: &Self::Position, f: F)
+        where F: FnMut(Self::Position);
+
+    /// Returns the vector of positions diagonally next to the given position
+    fn diagonals(&self, position: &Self::Position) -> Vec<Self::Position> {
+        let mut n = Vec::<Self::Position>::new();
+        self.for_each_diagonal(position, |p| n.push(p));
+        n
+    }
+
+    /// Packs the stones into two bits per intersection
+    ///
+    /// Empty is 0b00, black 0b01 and white 0b10. The intersections are
+    /// taken in the order of `iter_positions`, four per byte starting
+    /// at the lowest bits. The board size is not part of the encoding.
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for (index, position) in self.iter_positions().enumerate() {
+            if index % 4 == 0 {
+                bytes.push(0);
+            }
+
+            let bits = match self.at(&position) {
+                Stone::Empty => 0b00,
+                Stone::Black => 0b01,
+                Stone::White => 0b10,
+            };
+            if let Some(byte) = bytes.last_mut() {
+                *byte |= bits << (2 * (index % 4));
+            }
         }
 
-        let mut found_groups = Vec::<Group<Self>>::new();
-        for pos in &self.neighbors(position) {
-            if found_groups.iter().any(|g| g.positions.contains(pos)) {
+        bytes
+    }
+
+    /// Sets the stones from an encoding created by `encode`
+    ///
+    /// The board has to have the shape of the encoded one. Leaves the
+    /// board unchanged if the encoding does not fit.
+    fn decode(&mut self, bytes: &[u8]) -> Result<(), DecodeError> {
+        let positions = self.positions();
+        if bytes.len() != positions.len().div_ceil(4) {
+            return Err(DecodeError::WrongLength);
+        }
+
+        let stones = (0..positions.len())
+            .map(|index| match (bytes[index / 4] >> (2 * (index % 4))) & 0b11 {
+                0b00 => Ok(Stone::Empty),
+                0b01 => Ok(Stone::Black),
+                0b10 => Ok(Stone::White),
+                _ => Err(DecodeError::InvalidStone),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (position, stone) in positions.iter().zip(stones.iter()) {
+            self.set(position, stone);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of (black, white, empty) intersections
+    fn stone_counts(&self) -> (usize, usize, usize) {
+        self.iter_positions().fold((0, 0, 0), |(black, white, empty), p| {
+            match self.at(&p) {
+                Stone::Black => (black + 1, white, empty),
+                Stone::White => (black, white + 1, empty),
+                Stone::Empty => (black, white, empty + 1),
+            }
+        })
+    }
+
+    /// Returns aggregates over the groups of the given color
+    fn group_statistics(&self, stone: Stone) -> GroupStatistics {
+        let mut seen = HashSet::new();
+        let mut statistics = GroupStatistics::default();
+
+        for position in self.iter_positions() {
+            if self.at(&position) != stone || stone == Stone::Empty || seen.contains(&position) {
                 continue;
             }
 
-            found_groups.push(Group::new(self, pos));
+            let group = Group::new(self, &position);
+            let liberties = group.liberties().len();
+            seen.extend(group.positions.iter().cloned());
+
+            if statistics.groups == 0 || liberties < statistics.min_liberties {
+                statistics.min_liberties = liberties;
+            }
+            if liberties > statistics.max_liberties {
+                statistics.max_liberties = liberties;
+            }
+            if liberties == 1 {
+                statistics.in_atari += 1;
+            }
+            statistics.groups += 1;
         }
 
-        found_groups
+        statistics
+    }
+
+    /// Returns the group at the given position
+    ///
+    /// Flood-fills on every call, use a `GroupCache` for repeated lookups.
+    fn group_at<'boardlt>(&'boardlt self, position: &Self::Position) -> Group<'boardlt, Self> {
+        Group::new(self, position)
+    }
+
+    /// Returns the groups of the given player that have exactly one liberty
+    fn groups_in_atari<'boardlt>(&'boardlt self, player: &Player) -> Vec<Group<'boardlt, Self>> {
+        let mut seen = HashSet::new();
+        let mut groups = Vec::new();
+
+        for position in self.iter_positions() {
+            if self.at(&position) != player.stone() || seen.contains(&position) {
+                continue;
+            }
+
+            let group = Group::new(self, &position);
+            seen.extend(group.positions.iter().cloned());
+            if group.is_in_atari() {
+                groups.push(group);
+            }
+        }
+
+        groups
+    }
+
+    /// Returns the vector of groups that have a liberty at the given position
+    fn groups_with_liberty_at<'boardlt>(&'boardlt self,
+                                        position: &Self::Position)
+                                        -> Vec<Group<'boardlt, Self>> {
+        group::groups_with_liberty_at(self, position, |pos| Group::new(self, pos))
     }
 
     /// Returns the set of stones that would be captured if the given player plays at
@@ -62,76 +339,128 @@ pub trait Board: Sized + Eq + Hash + Clone {
                          player: &Player,
                          position: &Self::Position)
                          -> HashSet<Self::Position> {
-        self.groups_with_liberty_at(position)
-            .iter()
-            .filter(|g| {
-                g.stone().unwrap_or(Stone::Empty) != player.stone() && g.liberties().len() == 1
-            })
-            .flat_map(|g| g.positions.iter())
-            .cloned()
-            .collect()
+        group::would_be_captured(self, player, position, |pos| Group::new(self, pos))
     }
 
-    /// Returns if a play here would be suicide
+    /// Returns true if the given player would capture at least one stone by
+    /// playing at the given position
     ///
-    /// Returns false if a play at position by player would:
-    /// * kill something
-    /// * connect own groups that have at least two remaining liberties
-    ///
-    /// If none of those match, returns true if a friendly neighboring group looses
-    /// its last liberty. Note that it returns true if it is a suicidal move.
-    fn would_be_suicide(&self, position: &Self::Position, player: &Player) -> bool {
-        //  OOOO   consider X to play in the middle
-        // .X.XO   the left X has still a remaining liberty
-        //  OOOO   => no group of X can die
-        let mut friendly_looses_last_liberty = false;
-
-        for group in self.groups_with_liberty_at(position).iter() {
-            let liberties = group.liberties();
-            let group_owner = group.stone().unwrap_or(Stone::Empty);
+    /// Cheaper than `would_be_captured`: stops flood-filling an enemy group
+    /// as soon as it has a second liberty and returns on the first capture.
+    fn would_capture_any(&self, player: &Player, position: &Self::Position) -> bool {
+        if self.at(position) != Stone::Empty {
+            return false;
+        }
 
-            if liberties.len() == 1 && group_owner == player.other().stone() {
-                return false; //we kill something
+        let enemy = player.other().stone();
+        let mut captures = false;
+        self.for_each_neighbor(position, |start| {
+            if captures || self.at(&start) != enemy {
+                return;
             }
 
-            if liberties.len() == 1 && group_owner == player.stone() {
-                friendly_looses_last_liberty = true;
+            let mut seen = HashSet::new();
+            let mut stack = vec![start];
+            let mut free = false;
+            seen.insert(start);
+            while let Some(top) = stack.pop() {
+                self.for_each_neighbor(&top, |n| match self.at(&n) {
+                    Stone::Empty => free |= n != *position,
+                    stone if stone == enemy && seen.insert(n) => stack.push(n),
+                    _ => {}
+                });
+                if free {
+                    break;
+                }
             }
 
-            if liberties.len() > 1 && group_owner == player.stone() {
-                return false; //a friendly stone has a remaining liberty
-            }
-        }
+            captures = !free;
+        });
 
-        friendly_looses_last_liberty
+        captures
     }
 
-    /// Fills all empty intersections that neighbor a stone with the given color by
-    /// stones of that color. Repeats until nothing changes.
-    fn erode(&mut self, stone: Stone) {
-        let mut change = true;
-        let positions = self.positions();
+    /// Returns if a play here would be suicide
+    ///
+    /// See `group::would_be_suicide`, flood-fills the neighboring
+    /// groups on every call.
+    fn would_be_suicide(&self, position: &Self::Position, player: &Player) -> bool {
+        group::would_be_suicide(self, position, player, |pos| Group::new(self, pos))
+    }
 
-        while change {
-            change = false;
+    /// Returns the positions the given player may play at
+    ///
+    /// Yields all empty intersections where a play would not be suicide.
+    /// Ko depends on the history of the game and is left to the rules.
+    /// The groups are flood-filled once through a `GroupCache`, boards
+    /// with a faster `would_be_suicide` use that instead.
+    fn legal_moves<'boardlt>(&'boardlt self,
+                             player: &Player)
+                             -> impl Iterator<Item = Self::Position> + 'boardlt {
+        let player = *player;
+        let cache = GroupCache::new(self);
+        self.iter_positions()
+            .filter(move |p| self.at(p) == Stone::Empty && !cache.would_be_suicide(p, &player))
+    }
+
+    /// Plays a stone of the given player at the given position
+    ///
+    /// Checks that the position is a free intersection and that the play
+    /// is no suicide, then places the stone and removes the stones it
+    /// captures. Ko has to be checked by the caller.
+    fn play(&mut self,
+            player: &Player,
+            position: &Self::Position)
+            -> Result<CaptureInfo<Self::Position>, PlayError> {
+        if !self.on_board(position) {
+            return Err(PlayError::OffBoard);
+        }
+        if self.at(position) != Stone::Empty {
+            return Err(PlayError::Occupied);
+        }
+        if self.would_be_suicide(position, player) {
+            return Err(PlayError::Suicide);
+        }
 
-            let empty_positions: Vec<_> = positions.iter()
-                .filter(|pos| self.at(pos) == Stone::Empty)
-                .collect();
+        let captured = self.would_be_captured(player, position);
+        self.set(position, &player.stone());
+        for captured_stone in &captured {
+            self.set(captured_stone, &Stone::Empty);
+        }
 
-            for empty_position in empty_positions {
-                let any_set = self.neighbors(empty_position)
-                    .iter()
-                    .any(|pos| self.at(pos) == stone);
+        Ok(CaptureInfo { captured })
+    }
 
-                if any_set {
-                    self.set(empty_position, &stone);
-                    change = true;
+    /// Fills all empty intersections that neighbor a stone with the given color by
+    /// stones of that color. Repeats until nothing changes.
+    ///
+    /// Grows from the stones in a breadth first search, so every
+    /// intersection is visited once.
+    fn erode(&mut self, stone: Stone) {
+        let mut frontier: VecDeque<_> = self.iter_positions()
+            .filter(|pos| self.at(pos) == stone)
+            .collect();
+
+        while let Some(position) = frontier.pop_front() {
+            let mut empty = Vec::new();
+            self.for_each_neighbor(&position, |pos| {
+                if self.at(&pos) == Stone::Empty {
+                    empty.push(pos);
                 }
+            });
+
+            for pos in empty {
+                self.set(&pos, &stone);
+                frontier.push_back(pos);
             }
         }
     }
 
+    /// Returns (black_score, white_score) using area scoring
+    ///
+    /// Counts the stones of a player and the empty intersections only
+    /// that player's stones reach. Intersections reached by both, like
+    /// the shared liberties of a seki or dame, count for nobody.
     fn area_scoring(&self) -> (usize, usize) {
         let mut white_board = self.clone();
         let mut black_board = self.clone();
@@ -142,23 +471,81 @@ pub trait Board: Sized + Eq + Hash + Clone {
         // A position is either:
         // + played by me (me_board = me, other_board = me),
         // + my territory (me_board = me, other_board = empty),
-        // ~ seki (me_board = me, other_board = other),
-        // ~ not mine (me_board != me).
+        // - shared, e.g. in seki (me_board = me, other_board = other),
+        // - not mine (me_board != me).
 
-        let white_score = self.positions()
-            .iter()
+        let white_score = self.iter_positions()
             .filter(|pos| {
-                white_board.at(pos) == Stone::White || black_board.at(pos) != Stone::Black
+                white_board.at(pos) == Stone::White && black_board.at(pos) != Stone::Black
             })
             .count();
 
-        let black_score = self.positions()
-            .iter()
+        let black_score = self.iter_positions()
             .filter(|pos| {
-                black_board.at(pos) == Stone::Black || white_board.at(pos) != Stone::White
+                black_board.at(pos) == Stone::Black && white_board.at(pos) != Stone::White
             })
             .count();
 
         (black_score, white_score)
     }
+
+    /// Returns (black_score, white_score) using area scoring after removing
+    /// the given dead stones
+    ///
+    /// The intersections of the dead stones count for the player who
+    /// surrounds them like any other empty intersection.
+    fn area_scoring_with_dead(&self, dead: &[Self::Position]) -> (usize, usize) {
+        let mut board = self.clone();
+        for position in dead {
+            board.set(position, &Stone::Empty);
+        }
+
+        board.area_scoring()
+    }
+
+    /// Returns (black_score, white_score) using territory scoring
+    ///
+    /// Removes the dead stones first, counting them as prisoners of the
+    /// other player. A player's score is the number of empty intersections
+    /// only that player's stones reach plus the prisoners held.
+    /// `prisoners_black` are the stones black has captured during the
+    /// game, `prisoners_white` those white has captured.
+    fn territory_scoring(&self,
+                         dead_stones: &[Self::Position],
+                         prisoners_black: usize,
+                         prisoners_white: usize)
+                         -> (usize, usize) {
+        let mut board = self.clone();
+        let mut prisoners_black = prisoners_black;
+        let mut prisoners_white = prisoners_white;
+
+        for dead_stone in dead_stones {
+            match board.at(dead_stone) {
+                Stone::Black => prisoners_white += 1,
+                Stone::White => prisoners_black += 1,
+                Stone::Empty => {}
+            }
+            board.set(dead_stone, &Stone::Empty);
+        }
+
+        let mut white_board = board.clone();
+        let mut black_board = board.clone();
+
+        white_board.erode(Stone::White);
+        black_board.erode(Stone::Black);
+
+        let territory = |me: Stone, me_board: &Self, other: Stone, other_board: &Self| {
+            board.iter_positions()
+                .filter(|pos| {
+                    board.at(pos) == Stone::Empty && me_board.at(pos) == me &&
+                    other_board.at(pos) != other
+                })
+                .count()
+        };
+
+        let black_territory = territory(Stone::Black, &black_board, Stone::White, &white_board);
+        let white_territory = territory(Stone::White, &white_board, Stone::Black, &black_board);
+
+        (black_territory + prisoners_black, white_territory + prisoners_white)
+    }
 }
@@ -0,0 +1,181 @@
+use go::{Board, GridBoard, HandicapTable, Stone};
+use go::board::{Direction, Position};
+use go::handicap::{fixed_points, max_handicap, star_points};
+
+#[cfg(feature = "serde")]
+use serde::de::Error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(test)]
+mod test;
+
+/// A square go board with N lines known at compile time
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+pub struct SquareBoard<const N: usize> {
+    state: [[Stone; N]; N],
+}
+
+impl<const N: usize> SquareBoard<N> {
+    /// Sets the requested amount of handicap stones as given by the table
+    ///
+    /// Sets nothing if the table has no pattern for this board size and
+    /// that many stones.
+    pub fn set_handicap_from(&mut self, table: &HandicapTable, stones: u8) {
+        if let Some(placements) = table.placements(N, stones) {
+            for &(x, y) in placements {
+                self.set(&Position { x, y }, &Stone::Black);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> Serialize for SquareBoard<N> {
+    /// Serializes the board as a sequence of rows
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.collect_seq(self.state.iter().map(|row| &row[..]))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> Deserialize<'de> for SquareBoard<N> {
+    /// Deserializes a sequence of N rows with N stones each
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let rows = Vec::<Vec<Stone>>::deserialize(deserializer)?;
+        if rows.len() != N || rows.iter().any(|row| row.len() != N) {
+            return Err(D::Error::custom(format!("expected {0} rows of {0} stones", N)));
+        }
+
+        let mut board = SquareBoard::new();
+        for (y, row) in rows.iter().enumerate() {
+            board.state[y].copy_from_slice(row);
+        }
+
+        Ok(board)
+    }
+}
+
+impl<const N: usize> Board for SquareBoard<N> {
+    type Position = Position;
+
+    fn new() -> Self {
+        SquareBoard { state: [[Stone::Empty; N]; N] }
+    }
+
+    fn on_board(&self, position: &Position) -> bool {
+        position.x < N && position.y < N
+    }
+
+    fn at(&self, position: &Position) -> Stone {
+        self.state[position.y][position.x]
+    }
+
+    fn set(&mut self, position: &Position, stone: &Stone) {
+        self.state[position.y][position.x] = *stone;
+    }
+
+    fn set_handicap(&mut self, stones: u8) {
+        for (x, y) in fixed_points(N, stones).unwrap_or_default() {
+            self.set(&Position { x, y }, &Stone::Black);
+        }
+    }
+
+    fn max_handicap(&self) -> u8 {
+        max_handicap(N)
+    }
+
+    fn star_points(&self) -> Vec<Position> {
+        star_points(N).into_iter().map(|(x, y)| Position { x, y }).collect()
+    }
+
+    fn iter_positions<'boardlt>(&'boardlt self)
+                                -> impl Iterator<Item = Position> + 'boardlt {
+        (0..N).flat_map(move |x| (0..N).map(move |y| Position { x, y }))
+    }
+
+    fn for_each_neighbor<F>(&self, position: &Position, mut f: F)
+        where F: FnMut(Position)
+    {
+        if position.x + 1 < N {
+            f(Position {
+                x: position.x + 1,
+                y: position.y,
+            });
+        }
+        if position.x > 0 {
+            f(Position {
+                x: position.x - 1,
+                y: position.y,
+            });
+        }
+        if position.y + 1 < N {
+            f(Position {
+                x: position.x,
+                y: position.y + 1,
+            });
+        }
+        if position.y > 0 {
+            f(Position {
+                x: position.x,
+                y: position.y - 1,
+            });
+        }
+    }
+
+    fn step(&self, position: &Position, direction: Direction) -> Option<Position> {
+        position.offset(direction).filter(|p| self.on_board(p))
+    }
+
+    fn for_each_diagonal<F>(&self, position: &Position, mut f: F)
+        where F: FnMut(Position)
+    {
+        let right = position.x + 1 < N;
+        let left = position.x > 0;
+        let down = position.y + 1 < N;
+        let up = position.y > 0;
+
+        if right && down {
+            f(Position {
+                x: position.x + 1,
+                y: position.y + 1,
+            });
+        }
+        if right && up {
+            f(Position {
+                x: position.x + 1,
+                y: position.y - 1,
+            });
+        }
+        if left && down {
+            f(Position {
+                x: position.x - 1,
+                y: position.y + 1,
+            });
+        }
+        if left && up {
+            f(Position {
+                x: position.x - 1,
+                y: position.y - 1,
+            });
+        }
+    }
+}
+
+impl<const N: usize> GridBoard for SquareBoard<N> {
+    fn size(&self) -> usize {
+        N
+    }
+
+    fn position(&self, x: usize, y: usize) -> Position {
+        Position { x, y }
+    }
+
+    fn coordinates(&self, position: &Position) -> (usize, usize) {
+        (position.x, position.y)
+    }
+}
@@ -0,0 +1,100 @@
+use go::board::{Position, SquareBoard};
+use go::{Board, GridBoard, Stone};
+
+#[test]
+fn size() {
+    let small = SquareBoard::<9>::new();
+    let large = SquareBoard::<19>::new();
+
+    assert_eq!(small.size(), 9);
+    assert_eq!(small.positions().len(), 81);
+    assert_eq!(large.positions().len(), 361);
+    assert!(!small.on_board(&Position { x: 9, y: 0 }));
+    assert!(large.on_board(&Position { x: 9, y: 0 }));
+}
+
+#[test]
+fn neighbors() {
+    let board = SquareBoard::<9>::new();
+
+    assert_eq!(board.neighbors(&Position { x: 8, y: 4 }).len(), 3);
+    assert_eq!(board.neighbors(&Position { x: 4, y: 4 }).len(), 4);
+    assert_eq!(board.neighbors(&Position { x: 8, y: 8 }).len(), 2);
+}
+
+#[test]
+fn area_scoring() {
+    let mut board = SquareBoard::<5>::new();
+    for y in 0..5 {
+        board.set(&Position { x: 1, y }, &Stone::Black); // .X.O.
+        board.set(&Position { x: 3, y }, &Stone::White); // .X.O.
+    }
+
+    // the middle column is shared
+    assert_eq!(board.area_scoring(), (10, 10));
+}
+
+#[test]
+fn handicap() {
+    let mut board = SquareBoard::<13>::new();
+    board.set_handicap(4);
+
+    assert_eq!(board.at(&Position { x: 3, y: 3 }), Stone::Black);
+    assert_eq!(board.at(&Position { x: 9, y: 3 }), Stone::Black);
+    assert_eq!(board.at(&Position { x: 3, y: 9 }), Stone::Black);
+    assert_eq!(board.at(&Position { x: 9, y: 9 }), Stone::Black);
+    assert_eq!(board.at(&Position { x: 6, y: 6 }), Stone::Empty);
+}
+
+#[test]
+fn handicap_above_maximum() {
+    let mut board = SquareBoard::<9>::new();
+    assert_eq!(board.max_handicap(), 5);
+
+    board.set_handicap(9);
+    assert_eq!(board, SquareBoard::<9>::new());
+}
+
+#[test]
+fn star_points() {
+    let board = SquareBoard::<9>::new();
+    let star_points = board.star_points();
+
+    assert_eq!(star_points.len(), 5);
+    assert!(star_points.contains(&Position { x: 2, y: 2 }));
+    assert!(star_points.contains(&Position { x: 4, y: 4 }));
+    assert!(!star_points.contains(&Position { x: 6, y: 4 }));
+
+    let star_points = SquareBoard::<13>::new().star_points();
+    assert_eq!(star_points.len(), 5);
+    assert!(star_points.contains(&Position { x: 9, y: 3 }));
+    assert!(!star_points.contains(&Position { x: 3, y: 6 }));
+
+    let star_points = SquareBoard::<19>::new().star_points();
+    assert_eq!(star_points.len(), 9);
+    assert!(star_points.contains(&Position { x: 3, y: 3 }));
+    assert!(star_points.contains(&Position { x: 9, y: 9 }));
+    assert!(star_points.contains(&Position { x: 15, y: 9 }));
+    assert!(!star_points.contains(&Position { x: 4, y: 4 }));
+
+    assert!(SquareBoard::<7>::new().star_points().is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    use go::Player;
+    use serde_json;
+
+    let mut board = SquareBoard::<5>::new();
+    board.set(&Position { x: 1, y: 3 }, &Stone::Black);
+    board.set(&Position { x: 4, y: 0 }, &Stone::White);
+
+    let json = serde_json::to_string(&board).unwrap();
+    assert_eq!(serde_json::from_str::<SquareBoard<5>>(&json).unwrap(), board);
+    assert!(serde_json::from_str::<SquareBoard<9>>(&json).is_err());
+
+    assert_eq!(serde_json::to_string(&Position { x: 1, y: 3 }).unwrap(),
+               "{\"x\":1,\"y\":3}");
+    assert_eq!(serde_json::from_str::<Player>("\"White\"").unwrap(), Player::White);
+}
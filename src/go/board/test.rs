@@ -1,5 +1,5 @@
-use aga::{Board19x19, Position19x19};
-use go::{Board, Stone, Player};
+use aga::{Board9x9, Board19x19, Position19x19};
+use go::{Board, Corner, DecodeError, GridBoard, GroupStatistics, PlayError, Rect, Stone, Player};
 
 #[test]
 fn groups_with_liberty_at() {
@@ -22,6 +22,11 @@ fn board_neighbors() {
     assert_eq!(board.neighbors(&Position19x19 { x: 0, y: 5 }).len(), 3);
     assert_eq!(board.neighbors(&Position19x19 { x: 9, y: 9 }).len(), 4);
     assert_eq!(board.neighbors(&Position19x19 { x: 0, y: 0 }).len(), 2);
+
+    let mut visited = Vec::new();
+    board.for_each_neighbor(&Position19x19 { x: 18, y: 3 }, |p| visited.push(p));
+    assert_eq!(visited, board.neighbors(&Position19x19 { x: 18, y: 3 }));
+    assert_eq!(visited.len(), 3);
 }
 
 #[test]
@@ -35,6 +40,273 @@ fn board_would_be_captured() {
     board.set(&Position19x19 { x: 2, y: 0 }, &Stone::White); // gonna play with X at T
     board.set(&Position19x19 { x: 2, y: 1 }, &Stone::Black); // should capture both white stones
 
-    assert_eq!(board.would_be_captured(&Player::Black, (&Position19x19 { x: 1, y: 0 })).len(),
+    assert_eq!(board.would_be_captured(&Player::Black, &Position19x19 { x: 1, y: 0 }).len(),
                2);
+    assert!(board.would_capture_any(&Player::Black, &Position19x19 { x: 1, y: 0 }));
+    assert!(!board.would_capture_any(&Player::White, &Position19x19 { x: 1, y: 0 }));
+    assert!(!board.would_capture_any(&Player::Black, &Position19x19 { x: 3, y: 0 }));
+}
+
+#[test]
+fn board_play() {
+    let mut board = Board19x19::new();
+
+    board.set(&Position19x19 { x: 0, y: 0 }, &Stone::White); // OT
+    board.set(&Position19x19 { x: 0, y: 1 }, &Stone::Black); // X.
+
+    assert_eq!(board.play(&Player::Black, &Position19x19 { x: 19, y: 0 }),
+               Err(PlayError::OffBoard));
+    assert_eq!(board.play(&Player::Black, &Position19x19 { x: 0, y: 1 }),
+               Err(PlayError::Occupied));
+
+    let info = board.play(&Player::Black, &Position19x19 { x: 1, y: 0 }).unwrap();
+    assert_eq!(info.captured.len(), 1);
+    assert!(info.captured.contains(&Position19x19 { x: 0, y: 0 }));
+    assert_eq!(board.at(&Position19x19 { x: 0, y: 0 }), Stone::Empty);
+    assert_eq!(board.at(&Position19x19 { x: 1, y: 0 }), Stone::Black);
+
+    board.set(&Position19x19 { x: 1, y: 1 }, &Stone::Black);
+    assert_eq!(board.play(&Player::White, &Position19x19 { x: 0, y: 0 }),
+               Err(PlayError::Suicide));
+}
+
+#[test]
+fn board_legal_moves() {
+    let mut board = Board19x19::new();
+    assert_eq!(board.legal_moves(&Player::Black).count(), 361);
+
+    board.set(&Position19x19 { x: 1, y: 0 }, &Stone::Black); // .X
+    board.set(&Position19x19 { x: 0, y: 1 }, &Stone::Black); // X
+    board.set(&Position19x19 { x: 5, y: 5 }, &Stone::White);
+
+    let white_moves: Vec<_> = board.legal_moves(&Player::White).collect();
+    assert_eq!(white_moves.len(), 361 - 4);
+    assert!(!white_moves.contains(&Position19x19 { x: 0, y: 0 }));
+    assert_eq!(board.legal_moves(&Player::Black).count(), 361 - 3);
+}
+
+#[test]
+fn board_iter_positions() {
+    let board = Board19x19::new();
+
+    assert_eq!(board.iter_positions().count(), 361);
+    assert_eq!(board.iter_positions().collect::<Vec<_>>(), board.positions());
+}
+
+#[test]
+fn board_diagonals() {
+    let board = Board19x19::new();
+
+    assert_eq!(board.diagonals(&Position19x19 { x: 0, y: 5 }).len(), 2);
+    assert_eq!(board.diagonals(&Position19x19 { x: 9, y: 9 }).len(), 4);
+    assert_eq!(board.diagonals(&Position19x19 { x: 18, y: 18 }),
+               vec![Position19x19 { x: 17, y: 17 }]);
+}
+
+#[test]
+fn board_area_scoring_seki() {
+    // the black stone in the corner and the white one below share their
+    // two liberties, none of them can approach without being captured
+    let board: Board9x9 = ".XO......
+                           O.O......
+                           XXO......
+                           XXO......
+                           XXO......
+                           XXO......
+                           XXO......
+                           XXO......
+                           XXO......"
+        .parse()
+        .unwrap();
+
+    assert_eq!(board.area_scoring(), (15, 64));
+}
+
+#[test]
+fn board_territory_scoring() {
+    let board: Board9x9 = ".X.O.....
+                           XX.O.....
+                           ..XO..X..
+                           ..XO.....
+                           ..XO.....
+                           ..XO.....
+                           ..XO.....
+                           ..XO.....
+                           ..XO....."
+        .parse()
+        .unwrap();
+
+    // the shared point next to the top stones is no territory
+    assert_eq!(board.territory_scoring(&[], 0, 0), (15, 0));
+    assert_eq!(board.territory_scoring(&[], 3, 2), (18, 2));
+
+    // the dead black stone is a prisoner and leaves territory behind
+    assert_eq!(board.territory_scoring(&[Position19x19 { x: 6, y: 2 }], 0, 0),
+               (15, 46));
+}
+
+#[test]
+fn board_statistics() {
+    let board: Board9x9 = "XO.......
+                           XX.......
+                           ....XX...
+                           ........."
+        .parse()
+        .unwrap();
+
+    assert_eq!(board.stone_counts(), (5, 1, 75));
+    assert_eq!(board.group_statistics(Stone::Black),
+               GroupStatistics {
+                   groups: 2,
+                   in_atari: 0,
+                   min_liberties: 3,
+                   max_liberties: 6,
+               });
+    assert_eq!(board.group_statistics(Stone::White),
+               GroupStatistics {
+                   groups: 1,
+                   in_atari: 1,
+                   min_liberties: 1,
+                   max_liberties: 1,
+               });
+    assert_eq!(board.group_statistics(Stone::Empty), GroupStatistics::default());
+}
+
+#[test]
+fn board_groups_in_atari() {
+    let board: Board9x9 = "XO...
+                           ....X
+                           ...XOX
+                           ....."
+        .parse()
+        .unwrap();
+
+    let black = board.groups_in_atari(&Player::Black);
+    let white = board.groups_in_atari(&Player::White);
+
+    assert_eq!(black.len(), 1);
+    assert!(black[0].positions.contains(&Position19x19 { x: 0, y: 0 }));
+    assert_eq!(white.len(), 1);
+    assert_eq!(white[0].positions.len(), 1);
+    assert!(white[0].positions.contains(&Position19x19 { x: 4, y: 2 }));
+}
+
+#[test]
+fn board_encode() {
+    let board: Board19x19 = "XO.
+                             .X.
+                             ..O"
+        .parse()
+        .unwrap();
+
+    let bytes = board.encode();
+    assert_eq!(bytes.len(), 91);
+
+    let mut decoded = Board19x19::new();
+    assert_eq!(decoded.decode(&bytes), Ok(()));
+    assert_eq!(decoded, board);
+
+    let mut small = Board9x9::new();
+    assert_eq!(small.decode(&bytes), Err(DecodeError::WrongLength));
+    assert_eq!(small.decode(&[0xff; 21]), Err(DecodeError::InvalidStone));
+    assert_eq!(small, Board9x9::new());
+}
+
+#[test]
+fn board_editing() {
+    let mut board = Board9x9::new();
+    board.set_many(&[(Position19x19 { x: 0, y: 0 }, Stone::Black),
+                     (Position19x19 { x: 1, y: 0 }, Stone::White),
+                     (Position19x19 { x: 0, y: 0 }, Stone::White)]);
+
+    assert_eq!(board.stone_counts(), (0, 2, 79));
+
+    board.fill(Stone::Black);
+    assert_eq!(board.stone_counts(), (81, 0, 0));
+
+    board.clear_region(&Rect {
+        x: 2,
+        y: 3,
+        width: 4,
+        height: 2,
+    });
+    assert_eq!(board.stone_counts(), (73, 0, 8));
+    assert_eq!(board.at(&Position19x19 { x: 5, y: 4 }), Stone::Empty);
+    assert_eq!(board.at(&Position19x19 { x: 6, y: 4 }), Stone::Black);
+    assert_eq!(board.at(&Position19x19 { x: 5, y: 5 }), Stone::Black);
+
+    board.fill(Stone::Empty);
+    assert_eq!(board, Board9x9::new());
+}
+
+#[test]
+fn board_erode() {
+    let board: Board9x9 = "O...X....
+                           ....X....
+                           ....X....
+                           ....X....
+                           ....X....
+                           ....X....
+                           ....X....
+                           ....X....
+                           ....X...."
+        .parse()
+        .unwrap();
+
+    let mut white = board.clone();
+    white.erode(Stone::White);
+    assert_eq!(white.stone_counts(), (9, 36, 36));
+    assert_eq!(white.at(&Position19x19 { x: 3, y: 8 }), Stone::White);
+
+    let mut black = board.clone();
+    black.erode(Stone::Black);
+    assert_eq!(black.stone_counts(), (80, 1, 0));
+}
+
+#[test]
+fn board_area_scoring_with_dead() {
+    let board: Board9x9 = ".X.O.....
+                           XX.......
+                           ........."
+        .parse()
+        .unwrap();
+
+    assert_eq!(board.area_scoring(), (4, 1));
+    assert_eq!(board.area_scoring_with_dead(&[Position19x19 { x: 3, y: 0 }]), (81, 0));
+}
+
+#[test]
+fn board_edge_distance() {
+    let board = Board19x19::new();
+
+    assert_eq!(board.distance_to_edge(&Position19x19 { x: 3, y: 3 }), Some(3));
+    assert_eq!(board.line_of(&Position19x19 { x: 3, y: 3 }), Some(4));
+    assert_eq!(board.line_of(&Position19x19 { x: 5, y: 18 }), Some(1));
+    assert_eq!(board.line_of(&Position19x19 { x: 9, y: 9 }), Some(10));
+    assert_eq!(board.line_of(&Position19x19 { x: 16, y: 9 }), Some(3));
+}
+
+#[test]
+fn board_regions() {
+    let board = Board9x9::new();
+    let rect = Rect {
+        x: 7,
+        y: 1,
+        width: 4,
+        height: 2,
+    };
+
+    let mut region: Vec<_> = board.region(rect).collect();
+    region.sort();
+    assert_eq!(region,
+               vec![Position19x19 { x: 7, y: 1 },
+                    Position19x19 { x: 8, y: 1 },
+                    Position19x19 { x: 7, y: 2 },
+                    Position19x19 { x: 8, y: 2 }]);
+
+    assert_eq!(board.quadrant(Corner::UpperLeft).count(), 25);
+    assert!(board.quadrant(Corner::LowerRight).all(|p| p.x >= 4 && p.y >= 4));
+    assert!(board.quadrant(Corner::UpperRight).any(|p| p == Position19x19 { x: 8, y: 0 }));
+    assert!(board.quadrant(Corner::LowerLeft).any(|p| p == Position19x19 { x: 4, y: 4 }));
+    assert_eq!(Board19x19::new().quadrant(Corner::LowerLeft).count(), 100);
 }
@@ -0,0 +1,110 @@
+use go::{Board, GridBoard, Stone};
+use go::board::{Direction, Position, SquareBoard};
+
+#[cfg(test)]
+mod test;
+
+/// A square go board with N lines whose edges wrap around
+///
+/// Every intersection has four neighbors: leaving the board on one
+/// side enters it on the opposite one. Thus all positions are alike
+/// and there are no corners or sides.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+pub struct TorusBoard<const N: usize> {
+    board: SquareBoard<N>,
+}
+
+impl<const N: usize> Board for TorusBoard<N> {
+    type Position = Position;
+
+    fn new() -> Self {
+        TorusBoard { board: SquareBoard::new() }
+    }
+
+    fn on_board(&self, position: &Position) -> bool {
+        self.board.on_board(position)
+    }
+
+    fn at(&self, position: &Position) -> Stone {
+        self.board.at(position)
+    }
+
+    fn set(&mut self, position: &Position, stone: &Stone) {
+        self.board.set(position, stone);
+    }
+
+    fn set_handicap(&mut self, stones: u8) {
+        self.board.set_handicap(stones);
+    }
+
+    fn max_handicap(&self) -> u8 {
+        self.board.max_handicap()
+    }
+
+    fn star_points(&self) -> Vec<Position> {
+        self.board.star_points()
+    }
+
+    fn iter_positions<'boardlt>(&'boardlt self)
+                                -> impl Iterator<Item = Position> + 'boardlt {
+        self.board.iter_positions()
+    }
+
+    fn for_each_neighbor<F>(&self, position: &Position, mut f: F)
+        where F: FnMut(Position)
+    {
+        f(Position {
+            x: (position.x + 1) % N,
+            y: position.y,
+        });
+        f(Position {
+            x: (position.x + N - 1) % N,
+            y: position.y,
+        });
+        f(Position {
+            x: position.x,
+            y: (position.y + 1) % N,
+        });
+        f(Position {
+            x: position.x,
+            y: (position.y + N - 1) % N,
+        });
+    }
+
+    fn step(&self, position: &Position, direction: Direction) -> Option<Position> {
+        Some(match direction {
+            Direction::Up => Position { x: position.x, y: (position.y + N - 1) % N },
+            Direction::Down => Position { x: position.x, y: (position.y + 1) % N },
+            Direction::Left => Position { x: (position.x + N - 1) % N, y: position.y },
+            Direction::Right => Position { x: (position.x + 1) % N, y: position.y },
+        })
+    }
+
+    fn for_each_diagonal<F>(&self, position: &Position, mut f: F)
+        where F: FnMut(Position)
+    {
+        let right = (position.x + 1) % N;
+        let left = (position.x + N - 1) % N;
+        let down = (position.y + 1) % N;
+        let up = (position.y + N - 1) % N;
+
+        f(Position { x: right, y: down });
+        f(Position { x: right, y: up });
+        f(Position { x: left, y: down });
+        f(Position { x: left, y: up });
+    }
+}
+
+impl<const N: usize> GridBoard for TorusBoard<N> {
+    fn size(&self) -> usize {
+        N
+    }
+
+    fn position(&self, x: usize, y: usize) -> Position {
+        Position { x, y }
+    }
+
+    fn coordinates(&self, position: &Position) -> (usize, usize) {
+        (position.x, position.y)
+    }
+}
@@ -0,0 +1,60 @@
+use go::board::{Direction, Position, TorusBoard};
+use go::{Board, Player, Stone};
+
+#[test]
+fn neighbors() {
+    let board = TorusBoard::<9>::new();
+
+    let corner = board.neighbors(&Position { x: 0, y: 0 });
+    assert_eq!(corner.len(), 4);
+    assert!(corner.contains(&Position { x: 8, y: 0 }));
+    assert!(corner.contains(&Position { x: 0, y: 8 }));
+
+    let diagonals = board.diagonals(&Position { x: 0, y: 0 });
+    assert_eq!(diagonals.len(), 4);
+    assert!(diagonals.contains(&Position { x: 8, y: 8 }));
+}
+
+#[test]
+fn capture_across_edge() {
+    let mut board = TorusBoard::<9>::new();
+
+    board.set(&Position { x: 0, y: 0 }, &Stone::White); // O.......X
+    board.set(&Position { x: 8, y: 0 }, &Stone::Black); // X........
+    board.set(&Position { x: 0, y: 1 }, &Stone::Black); // .........
+    board.set(&Position { x: 1, y: 0 }, &Stone::Black); // gonna play with X
+                                                        // at the bottom left
+    let captured = board.would_be_captured(&Player::Black, &Position { x: 0, y: 8 });
+    assert_eq!(captured.len(), 1);
+    assert!(captured.contains(&Position { x: 0, y: 0 }));
+}
+
+#[test]
+fn area_scoring() {
+    let mut board = TorusBoard::<5>::new();
+    for y in 0..5 {
+        board.set(&Position { x: 0, y }, &Stone::Black); // X.O..
+        board.set(&Position { x: 2, y }, &Stone::White); // X.O..
+    }
+
+    // the last two columns neighbor both colors through the edge
+    assert_eq!(board.area_scoring(), (5, 5));
+}
+
+#[test]
+fn step_wraps() {
+    let board = TorusBoard::<5>::new();
+
+    assert_eq!(board.step(&Position { x: 0, y: 0 }, Direction::Up),
+               Some(Position { x: 0, y: 4 }));
+    assert_eq!(board.step(&Position { x: 4, y: 2 }, Direction::Right),
+               Some(Position { x: 0, y: 2 }));
+}
+
+#[test]
+fn no_edges() {
+    let board = TorusBoard::<5>::new();
+
+    assert_eq!(board.distance_to_edge(&Position { x: 0, y: 0 }), None);
+    assert_eq!(board.line_of(&Position { x: 2, y: 2 }), None);
+}
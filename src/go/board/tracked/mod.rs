@@ -0,0 +1,357 @@
+use go::{Board, GridBoard, Player, Stone, ZobristHash};
+use go::board::Direction;
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+#[cfg(test)]
+mod test;
+
+/// A board that keeps track of its groups
+///
+/// Maintains a union-find structure over the stones of the wrapped
+/// board, where every root stores the members and liberties of its
+/// group. Placing a stone merges it with its friendly neighbors,
+/// removing one rebuilds just the group it was part of. Looking up
+/// the group or liberty count of a stone thus does not need a flood
+/// fill.
+#[derive(Clone, Debug)]
+pub struct TrackedBoard<TBoard>
+    where TBoard: Board
+{
+    board: TBoard,
+    /// The positions of the board, indexed by their id
+    positions: Vec<TBoard::Position>,
+    /// The ids of the positions of the board
+    ids: HashMap<TBoard::Position, usize>,
+    /// The union-find parent of every id
+    parents: Vec<usize>,
+    /// The union-find rank of every id
+    ranks: Vec<u8>,
+    /// The stones of the group, stored at the root
+    members: Vec<Vec<usize>>,
+    /// The liberties of the group, stored at the root
+    liberties: Vec<HashSet<usize>>,
+}
+
+impl<TBoard> TrackedBoard<TBoard>
+    where TBoard: Board
+{
+    /// Wraps the board, building the groups of its stones
+    pub fn from_board(board: TBoard) -> Self {
+        let positions = board.positions();
+        let count = positions.len();
+        let ids = positions.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+
+        let mut tracked = TrackedBoard {
+            board,
+            positions,
+            ids,
+            parents: (0..count).collect(),
+            ranks: vec![0; count],
+            members: vec![Vec::new(); count],
+            liberties: vec![HashSet::new(); count],
+        };
+        tracked.rebuild(&(0..count).collect::<Vec<_>>());
+
+        tracked
+    }
+
+    /// Returns the wrapped board
+    pub fn board(&self) -> &TBoard {
+        &self.board
+    }
+
+    /// Returns an identifier of the group of the stone at the position
+    ///
+    /// All stones of a group share the identifier. Returns None if there
+    /// is no stone at the position.
+    pub fn group_of(&self, position: &TBoard::Position) -> Option<usize> {
+        match self.board.at(position) {
+            Stone::Empty => None,
+            _ => Some(self.find(self.ids[position])),
+        }
+    }
+
+    /// Returns the positions of the group of the stone at the position
+    pub fn members_of(&self, position: &TBoard::Position) -> Vec<TBoard::Position> {
+        self.group_of(position)
+            .map(|root| self.members[root].iter().map(|id| self.positions[*id]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of liberties of the group of the stone at the position
+    ///
+    /// Returns 0 if there is no stone at the position.
+    pub fn liberties_of(&self, position: &TBoard::Position) -> usize {
+        self.group_of(position).map(|root| self.liberties[root].len()).unwrap_or(0)
+    }
+
+    /// Returns the root of the id without compressing the path
+    ///
+    /// Union by rank keeps the paths logarithmic.
+    fn find(&self, id: usize) -> usize {
+        let mut root = id;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        root
+    }
+
+    /// Returns the root of the id and compresses the path to it
+    fn find_mut(&mut self, id: usize) -> usize {
+        let root = self.find(id);
+        let mut curr = id;
+        while self.parents[curr] != root {
+            let next = self.parents[curr];
+            self.parents[curr] = root;
+            curr = next;
+        }
+        root
+    }
+
+    /// Merges the groups of the two ids
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find_mut(a), self.find_mut(b));
+        if a == b {
+            return;
+        }
+
+        let (root, child) = if self.ranks[a] < self.ranks[b] { (b, a) } else { (a, b) };
+        if self.ranks[root] == self.ranks[child] {
+            self.ranks[root] += 1;
+        }
+        self.parents[child] = root;
+
+        let members = ::std::mem::take(&mut self.members[child]);
+        self.members[root].extend(members);
+        let liberties = ::std::mem::take(&mut self.liberties[child]);
+        self.liberties[root].extend(liberties);
+    }
+
+    /// Returns the ids of the neighbors of the id
+    fn neighbor_ids(&self, id: usize) -> Vec<usize> {
+        let mut ids = Vec::with_capacity(4);
+        self.board.for_each_neighbor(&self.positions[id], |p| ids.push(self.ids[&p]));
+        ids
+    }
+
+    /// Adds the stone at the id to the tracked groups
+    ///
+    /// Merges it with the already tracked friendly neighbors.
+    fn track(&mut self, id: usize) {
+        let stone = self.board.at(&self.positions[id]);
+        self.parents[id] = id;
+        self.ranks[id] = 0;
+        self.members[id] = vec![id];
+        self.liberties[id] = HashSet::new();
+
+        for neighbor in self.neighbor_ids(id) {
+            match self.board.at(&self.positions[neighbor]) {
+                Stone::Empty => {
+                    let root = self.find_mut(id);
+                    self.liberties[root].insert(neighbor);
+                }
+                owner => {
+                    // stones that are not tracked yet are roots without members
+                    let root = self.find_mut(neighbor);
+                    self.liberties[root].remove(&id);
+                    if owner == stone && !self.members[root].is_empty() {
+                        self.union(id, neighbor);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Forgets the given stones and tracks the ones still on the board anew
+    fn rebuild(&mut self, ids: &[usize]) {
+        for &id in ids {
+            self.parents[id] = id;
+            self.ranks[id] = 0;
+            self.members[id].clear();
+            self.liberties[id].clear();
+        }
+
+        for &id in ids {
+            if self.board.at(&self.positions[id]) != Stone::Empty {
+                self.track(id);
+            }
+        }
+    }
+
+    /// Removes the stone at the id from the tracked groups
+    ///
+    /// Has to be called after the stone was removed from the board.
+    fn untrack(&mut self, id: usize) {
+        let root = self.find_mut(id);
+        let members = ::std::mem::take(&mut self.members[root]);
+        self.rebuild(&members);
+
+        for neighbor in self.neighbor_ids(id) {
+            if self.board.at(&self.positions[neighbor]) != Stone::Empty {
+                let root = self.find_mut(neighbor);
+                self.liberties[root].insert(id);
+            }
+        }
+    }
+}
+
+impl<TBoard> PartialEq for TrackedBoard<TBoard>
+    where TBoard: Board
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.board == other.board
+    }
+}
+
+impl<TBoard> Eq for TrackedBoard<TBoard> where TBoard: Board {}
+
+impl<TBoard> Hash for TrackedBoard<TBoard>
+    where TBoard: Board
+{
+    /// Hashes only the wrapped board since the groups follow from it
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.board.hash(state);
+    }
+}
+
+impl<TBoard> Board for TrackedBoard<TBoard>
+    where TBoard: Board
+{
+    type Position = TBoard::Position;
+
+    fn new() -> Self {
+        TrackedBoard::from_board(TBoard::new())
+    }
+
+    fn on_board(&self, position: &Self::Position) -> bool {
+        self.board.on_board(position)
+    }
+
+    fn at(&self, position: &Self::Position) -> Stone {
+        self.board.at(position)
+    }
+
+    fn set(&mut self, position: &Self::Position, stone: &Stone) {
+        let id = self.ids[position];
+        let previous = self.board.at(position);
+        if previous == *stone {
+            return;
+        }
+
+        if previous != Stone::Empty {
+            self.board.set(position, &Stone::Empty);
+            self.untrack(id);
+        }
+
+        if *stone != Stone::Empty {
+            self.board.set(position, stone);
+            self.track(id);
+        }
+    }
+
+    fn set_handicap(&mut self, stones: u8) {
+        self.board.set_handicap(stones);
+        let ids: Vec<_> = (0..self.positions.len()).collect();
+        self.rebuild(&ids);
+    }
+
+    fn max_handicap(&self) -> u8 {
+        self.board.max_handicap()
+    }
+
+    fn star_points(&self) -> Vec<Self::Position> {
+        self.board.star_points()
+    }
+
+    fn iter_positions<'boardlt>(&'boardlt self)
+                                -> impl Iterator<Item = Self::Position> + 'boardlt {
+        self.positions.iter().cloned()
+    }
+
+    fn for_each_neighbor<F>(&self, position: &Self::Position, f: F)
+        where F: FnMut(Self::Position)
+    {
+        self.board.for_each_neighbor(position, f);
+    }
+
+    fn step(&self, position: &Self::Position, direction: Direction) -> Option<Self::Position> {
+        self.board.step(position, direction)
+    }
+
+    fn for_each_diagonal<F>(&self, position: &Self::Position, f: F)
+        where F: FnMut(Self::Position)
+    {
+        self.board.for_each_diagonal(position, f);
+    }
+
+    fn would_be_captured(&self,
+                         player: &Player,
+                         position: &Self::Position)
+                         -> HashSet<Self::Position> {
+        if self.at(position) != Stone::Empty {
+            return HashSet::new();
+        }
+
+        self.neighbors(position)
+            .iter()
+            .filter(|p| self.at(p) == player.other().stone() && self.liberties_of(p) == 1)
+            .flat_map(|p| self.members_of(p))
+            .collect()
+    }
+
+    fn would_capture_any(&self, player: &Player, position: &Self::Position) -> bool {
+        self.at(position) == Stone::Empty &&
+        self.neighbors(position)
+            .iter()
+            .any(|p| self.at(p) == player.other().stone() && self.liberties_of(p) == 1)
+    }
+
+    fn would_be_suicide(&self, position: &Self::Position, player: &Player) -> bool {
+        if self.at(position) != Stone::Empty {
+            return false;
+        }
+
+        !self.neighbors(position).iter().any(|neighbor| {
+            let liberties = self.liberties_of(neighbor);
+            let owner = self.at(neighbor);
+
+            owner == Stone::Empty || (owner == player.other().stone() && liberties == 1) ||
+            (owner == player.stone() && liberties > 1)
+        })
+    }
+
+    /// Checks every intersection with the tracked liberties
+    fn legal_moves<'boardlt>(&'boardlt self,
+                             player: &Player)
+                             -> impl Iterator<Item = Self::Position> + 'boardlt {
+        let player = *player;
+        self.iter_positions()
+            .filter(move |p| self.at(p) == Stone::Empty && !self.would_be_suicide(p, &player))
+    }
+}
+
+impl<TBoard> GridBoard for TrackedBoard<TBoard>
+    where TBoard: GridBoard
+{
+    fn size(&self) -> usize {
+        self.board.size()
+    }
+
+    fn position(&self, x: usize, y: usize) -> Self::Position {
+        self.board.position(x, y)
+    }
+
+    fn coordinates(&self, position: &Self::Position) -> (usize, usize) {
+        self.board.coordinates(position)
+    }
+}
+
+impl<TBoard> ZobristHash for TrackedBoard<TBoard>
+    where TBoard: ZobristHash
+{
+    fn zobrist_hash(&self) -> u64 {
+        self.board.zobrist_hash()
+    }
+}
@@ -0,0 +1,81 @@
+use aga::{Board19x19, Position19x19};
+use go::board::TrackedBoard;
+use go::{Board, Group, Player, Stone};
+
+#[test]
+fn groups() {
+    let mut board = TrackedBoard::<Board19x19>::new();
+    board.set(&Position19x19 { x: 7, y: 8 }, &Stone::White); //   .
+    board.set(&Position19x19 { x: 8, y: 7 }, &Stone::Black); //  .O.
+    board.set(&Position19x19 { x: 8, y: 9 }, &Stone::White); //  XOO.
+    board.set(&Position19x19 { x: 8, y: 8 }, &Stone::White); //   ..
+
+    let white = board.group_of(&Position19x19 { x: 8, y: 8 });
+    assert!(white.is_some());
+    assert_eq!(white, board.group_of(&Position19x19 { x: 7, y: 8 }));
+    assert_eq!(white, board.group_of(&Position19x19 { x: 8, y: 9 }));
+    assert!(white != board.group_of(&Position19x19 { x: 8, y: 7 }));
+    assert_eq!(board.group_of(&Position19x19 { x: 0, y: 0 }), None);
+
+    assert_eq!(board.liberties_of(&Position19x19 { x: 8, y: 8 }), 6);
+    assert_eq!(board.liberties_of(&Position19x19 { x: 8, y: 7 }), 3);
+    assert_eq!(board.members_of(&Position19x19 { x: 7, y: 8 }).len(), 3);
+}
+
+#[test]
+fn remove() {
+    let mut board = TrackedBoard::<Board19x19>::new();
+    board.set(&Position19x19 { x: 0, y: 0 }, &Stone::Black); // XXX
+    board.set(&Position19x19 { x: 1, y: 0 }, &Stone::Black); // O..
+    board.set(&Position19x19 { x: 2, y: 0 }, &Stone::Black);
+    board.set(&Position19x19 { x: 0, y: 1 }, &Stone::White);
+
+    assert_eq!(board.liberties_of(&Position19x19 { x: 0, y: 0 }), 3);
+    assert_eq!(board.liberties_of(&Position19x19 { x: 0, y: 1 }), 2);
+
+    // splits the black group in two
+    board.set(&Position19x19 { x: 1, y: 0 }, &Stone::Empty);
+    assert!(board.group_of(&Position19x19 { x: 0, y: 0 }) !=
+            board.group_of(&Position19x19 { x: 2, y: 0 }));
+    assert_eq!(board.liberties_of(&Position19x19 { x: 0, y: 0 }), 1);
+    assert_eq!(board.liberties_of(&Position19x19 { x: 2, y: 0 }), 3);
+
+    board.set(&Position19x19 { x: 0, y: 1 }, &Stone::Black);
+    assert_eq!(board.members_of(&Position19x19 { x: 0, y: 0 }).len(), 2);
+    assert_eq!(board.liberties_of(&Position19x19 { x: 0, y: 0 }), 3);
+}
+
+#[test]
+fn same_as_group() {
+    let mut tracked = TrackedBoard::<Board19x19>::new();
+    let mut seed: u64 = 11;
+
+    for ply in 0..600 {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let position = Position19x19 {
+            x: (seed >> 33) as usize % 19,
+            y: (seed >> 45) as usize % 19,
+        };
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        let plain = tracked.board().clone();
+
+        assert_eq!(tracked.would_be_captured(&player, &position),
+                   plain.would_be_captured(&player, &position));
+        assert_eq!(tracked.would_be_suicide(&position, &player),
+                   plain.would_be_suicide(&position, &player));
+        assert_eq!(tracked.would_capture_any(&player, &position),
+                   plain.would_capture_any(&player, &position));
+
+        if tracked.at(&position) == Stone::Empty &&
+           !tracked.would_be_suicide(&position, &player) {
+            for captured in &tracked.would_be_captured(&player, &position) {
+                tracked.set(captured, &Stone::Empty);
+            }
+            tracked.set(&position, &player.stone());
+        }
+
+        let group = Group::new(tracked.board(), &position);
+        assert_eq!(tracked.liberties_of(&position), group.liberties().len());
+        assert_eq!(tracked.members_of(&position).len(), group.positions.len());
+    }
+}
@@ -0,0 +1,127 @@
+use go::{GridBoard, Stone};
+use go::board::{BitBoard19, BoardN, SquareBoard, TorusBoard, MIN_SIZE};
+
+use std::str::FromStr;
+
+#[cfg(test)]
+mod test;
+
+/// The reasons a diagram can not be parsed
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DiagramError {
+    /// The diagram contains a character that is no stone
+    UnknownCharacter(char),
+    /// The diagram does not fit on the board
+    TooLarge,
+}
+
+/// Returns the stone a diagram character stands for
+fn stone(c: char) -> Result<Stone, DiagramError> {
+    match c {
+        'X' | 'x' | '#' => Ok(Stone::Black),
+        'O' | 'o' => Ok(Stone::White),
+        '.' | '+' => Ok(Stone::Empty),
+        _ => Err(DiagramError::UnknownCharacter(c)),
+    }
+}
+
+/// Returns true if the line is a row of column letters
+fn is_column_header(line: &str) -> bool {
+    line.chars().any(|c| c.is_ascii_uppercase() && c != 'X' && c != 'O')
+}
+
+/// Returns the rows of stones of a diagram
+///
+/// Ignores whitespace, row numbers, column headers and empty lines.
+fn rows(diagram: &str) -> Result<Vec<Vec<Stone>>, DiagramError> {
+    diagram.lines()
+        .filter(|line| !is_column_header(line))
+        .map(|line| {
+            line.chars()
+                .filter(|c| !c.is_whitespace() && !c.is_ascii_digit())
+                .map(stone)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .filter(|row| row.as_ref().map(|r| !r.is_empty()).unwrap_or(true))
+        .collect()
+}
+
+/// Sets the stones of the diagram on the board
+///
+/// The diagram is placed in the upper left corner, so it may cover
+/// just a part of the board.
+fn place<TBoard>(board: &mut TBoard, rows: &[Vec<Stone>]) -> Result<(), DiagramError>
+    where TBoard: GridBoard
+{
+    for (y, row) in rows.iter().enumerate() {
+        for (x, stone) in row.iter().enumerate() {
+            let position = board.position(x, y);
+            if x >= board.size() || y >= board.size() || !board.on_board(&position) {
+                return Err(DiagramError::TooLarge);
+            }
+            board.set(&position, stone);
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds a board from a diagram
+///
+/// Black stones are written as `X` or `#`, white stones as `O` and
+/// empty intersections as `.` or `+`. Whitespace is ignored, so
+/// diagrams like
+///
+/// ```text
+/// # O . .
+/// O # . .
+/// ```
+///
+/// as well as the output of `render::ascii` can be read. The diagram
+/// is placed in the upper left corner of an empty board.
+pub fn parse<TBoard>(diagram: &str) -> Result<TBoard, DiagramError>
+    where TBoard: GridBoard
+{
+    let mut board = TBoard::new();
+    place(&mut board, &rows(diagram)?)?;
+    Ok(board)
+}
+
+impl<const N: usize> FromStr for SquareBoard<N> {
+    type Err = DiagramError;
+
+    fn from_str(diagram: &str) -> Result<Self, DiagramError> {
+        parse(diagram)
+    }
+}
+
+impl<const N: usize> FromStr for TorusBoard<N> {
+    type Err = DiagramError;
+
+    fn from_str(diagram: &str) -> Result<Self, DiagramError> {
+        parse(diagram)
+    }
+}
+
+impl FromStr for BitBoard19 {
+    type Err = DiagramError;
+
+    fn from_str(diagram: &str) -> Result<Self, DiagramError> {
+        parse(diagram)
+    }
+}
+
+impl FromStr for BoardN {
+    type Err = DiagramError;
+
+    /// Builds a board just large enough for the diagram
+    fn from_str(diagram: &str) -> Result<Self, DiagramError> {
+        let rows = rows(diagram)?;
+        let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let size = *[rows.len(), columns, MIN_SIZE].iter().max().unwrap();
+
+        let mut board = BoardN::with_size(size).ok_or(DiagramError::TooLarge)?;
+        place(&mut board, &rows)?;
+        Ok(board)
+    }
+}
@@ -0,0 +1,64 @@
+use aga::{Board19x19, Position19x19};
+use go::board::{BoardN, SquareBoard};
+use go::diagram::{parse, DiagramError};
+use go::{Board, Player, Stone};
+use render::ascii::render;
+
+#[test]
+fn parse_partial() {
+    let board: Board19x19 = parse("# O . .
+                                   O # O .")
+        .unwrap();
+
+    assert_eq!(board.at(&Position19x19 { x: 0, y: 0 }), Stone::Black);
+    assert_eq!(board.at(&Position19x19 { x: 1, y: 0 }), Stone::White);
+    assert_eq!(board.at(&Position19x19 { x: 2, y: 1 }), Stone::White);
+    assert_eq!(board.at(&Position19x19 { x: 3, y: 1 }), Stone::Empty);
+    assert_eq!(board.at(&Position19x19 { x: 0, y: 2 }), Stone::Empty);
+
+    let captured = board.would_be_captured(&Player::White, &Position19x19 { x: 1, y: 2 });
+    assert_eq!(captured.len(), 1);
+}
+
+#[test]
+fn errors() {
+    assert_eq!(parse::<Board19x19>("X?"),
+               Err(DiagramError::UnknownCharacter('?')));
+    assert_eq!("......".parse::<SquareBoard<5>>(),
+               Err(DiagramError::TooLarge));
+}
+
+#[test]
+fn round_trip() {
+    let board: SquareBoard<9> = "..X......
+                                 .XOX.....
+                                 ..X......
+                                 .........
+                                 ....+....
+                                 .........
+                                 ......O..
+                                 .........
+                                 ........."
+        .parse()
+        .unwrap();
+
+    assert_eq!(render(&board).parse(), Ok(board));
+}
+
+#[test]
+fn board_n_size() {
+    let small: BoardN = "X.O".parse().unwrap();
+    assert_eq!(small.size(), 5);
+
+    let board: BoardN = ". . . . . . .
+                         . X . . . . .
+                         . . . . . . .
+                         . . . . . . .
+                         . . . . . . .
+                         . . . . . O .
+                         . . . . . . ."
+        .parse()
+        .unwrap();
+    assert_eq!(board.size(), 7);
+    assert_eq!(board.at(&Position19x19 { x: 5, y: 5 }), Stone::White);
+}
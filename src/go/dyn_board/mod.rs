@@ -0,0 +1,97 @@
+use go::{GridBoard, Player, Stone};
+
+use std::fmt::Debug;
+
+#[cfg(test)]
+mod test;
+
+/// An object safe board
+///
+/// Addresses intersections by (x, y) coordinates instead of the
+/// board's position type, so applications can pick the board size at
+/// runtime and keep it behind a `Box<dyn DynBoard>`.
+pub trait DynBoard: Debug {
+    /// Returns the number of lines of the board
+    fn size(&self) -> usize;
+
+    /// Returns true if the coordinates are on the board
+    fn on_board(&self, x: usize, y: usize) -> bool;
+
+    /// Returns the stone at the given coordinates
+    fn at(&self, x: usize, y: usize) -> Stone;
+
+    /// Sets the stone at the given coordinates
+    fn set(&mut self, x: usize, y: usize, stone: Stone);
+
+    /// Sets the requested amount of handicap stones
+    fn set_handicap(&mut self, stones: u8);
+
+    /// Returns the coordinates of the stones that would be captured if the
+    /// given player plays at the given coordinates
+    fn would_be_captured(&self, player: Player, x: usize, y: usize) -> Vec<(usize, usize)>;
+
+    /// Returns if a play at the given coordinates would be suicide
+    fn would_be_suicide(&self, player: Player, x: usize, y: usize) -> bool;
+
+    /// Returns (black_score, white_score) using area scoring
+    fn area_scoring(&self) -> (usize, usize);
+
+    /// Returns a boxed copy of the board
+    fn clone_box(&self) -> Box<dyn DynBoard>;
+}
+
+/// Wraps a board to make it usable as a `DynBoard`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ErasedBoard<TBoard>(pub TBoard) where TBoard: GridBoard;
+
+impl<TBoard> DynBoard for ErasedBoard<TBoard>
+    where TBoard: GridBoard + Debug + 'static
+{
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn on_board(&self, x: usize, y: usize) -> bool {
+        let size = self.0.size();
+        x < size && y < size && self.0.on_board(&self.0.position(x, y))
+    }
+
+    fn at(&self, x: usize, y: usize) -> Stone {
+        self.0.at(&self.0.position(x, y))
+    }
+
+    fn set(&mut self, x: usize, y: usize, stone: Stone) {
+        let position = self.0.position(x, y);
+        self.0.set(&position, &stone);
+    }
+
+    fn set_handicap(&mut self, stones: u8) {
+        self.0.set_handicap(stones);
+    }
+
+    fn would_be_captured(&self, player: Player, x: usize, y: usize) -> Vec<(usize, usize)> {
+        self.0
+            .would_be_captured(&player, &self.0.position(x, y))
+            .iter()
+            .map(|p| self.0.coordinates(p))
+            .collect()
+    }
+
+    fn would_be_suicide(&self, player: Player, x: usize, y: usize) -> bool {
+        self.0.would_be_suicide(&self.0.position(x, y), &player)
+    }
+
+    fn area_scoring(&self) -> (usize, usize) {
+        self.0.area_scoring()
+    }
+
+    fn clone_box(&self) -> Box<dyn DynBoard> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn DynBoard> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,29 @@
+use aga::board::board_with_size;
+use go::{DynBoard, Player, Stone};
+
+#[test]
+fn with_size() {
+    assert!(board_with_size(19).is_some());
+    assert!(board_with_size(9).is_some());
+    assert!(board_with_size(30).is_none());
+
+    let board = board_with_size(19).unwrap();
+    assert_eq!(board.size(), 19);
+    assert!(board.on_board(18, 18));
+    assert!(!board.on_board(19, 0));
+}
+
+#[test]
+fn play() {
+    let mut board: Box<dyn DynBoard> = board_with_size(19).unwrap();
+
+    board.set(0, 0, Stone::White); // OT
+    board.set(0, 1, Stone::Black); // X
+    assert_eq!(board.would_be_captured(Player::Black, 1, 0), vec![(0, 0)]);
+    assert!(!board.would_be_suicide(Player::White, 1, 1));
+
+    let copy = board.clone();
+    board.set(1, 0, Stone::Black);
+    assert_eq!(copy.at(1, 0), Stone::Empty);
+    assert_eq!(board.at(1, 0), Stone::Black);
+}
@@ -0,0 +1,85 @@
+use go::{Board, Group, Player, Stone};
+use go::group;
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Remembers the groups found on an immutable board
+///
+/// Every group is flood-filled once and then shared by all of its
+/// positions. Use one cache for several legality checks on the same
+/// board, e.g. when enumerating all moves of a position.
+pub struct GroupCache<'boardlt, TBoard>
+    where TBoard: Board + 'boardlt
+{
+    /// The board the groups are taken from
+    board: &'boardlt TBoard,
+    /// The group of every position visited so far
+    groups: RefCell<HashMap<TBoard::Position, Rc<Group<'boardlt, TBoard>>>>,
+}
+
+impl<'boardlt, TBoard> GroupCache<'boardlt, TBoard>
+    where TBoard: Board + 'boardlt
+{
+    /// Creates an empty cache for the given board
+    pub fn new(board: &'boardlt TBoard) -> GroupCache<'boardlt, TBoard> {
+        GroupCache {
+            board,
+            groups: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the board the groups are taken from
+    pub fn board(&self) -> &'boardlt TBoard {
+        self.board
+    }
+
+    /// Returns the group at the given position
+    ///
+    /// Empty positions yield an empty group that is not cached.
+    pub fn group_at(&self, position: &TBoard::Position) -> Rc<Group<'boardlt, TBoard>> {
+        if self.board.at(position) == Stone::Empty {
+            return Rc::new(Group::new(self.board, position));
+        }
+
+        if let Some(group) = self.groups.borrow().get(position) {
+            return group.clone();
+        }
+
+        let group = Rc::new(Group::new(self.board, position));
+        let mut groups = self.groups.borrow_mut();
+        for member in &group.positions {
+            groups.insert(*member, group.clone());
+        }
+
+        group
+    }
+
+    /// Returns the groups that have a liberty at the given position
+    ///
+    /// Behaves like `Board::groups_with_liberty_at`.
+    pub fn groups_with_liberty_at(&self,
+                                  position: &TBoard::Position)
+                                  -> Vec<Rc<Group<'boardlt, TBoard>>> {
+        group::groups_with_liberty_at(self.board, position, |pos| self.group_at(pos))
+    }
+
+    /// Returns the set of stones that would be captured if the given player plays at
+    /// the given position
+    ///
+    /// Behaves like `Board::would_be_captured`.
+    pub fn would_be_captured(&self,
+                             player: &Player,
+                             position: &TBoard::Position)
+                             -> HashSet<TBoard::Position> {
+        group::would_be_captured(self.board, player, position, |pos| self.group_at(pos))
+    }
+
+    /// Returns if a play here would be suicide
+    ///
+    /// Behaves like `Board::would_be_suicide`.
+    pub fn would_be_suicide(&self, position: &TBoard::Position, player: &Player) -> bool {
+        group::would_be_suicide(self.board, position, player, |pos| self.group_at(pos))
+    }
+}
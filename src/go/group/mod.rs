@@ -1,7 +1,12 @@
-use go::Stone;
-use go::Board;
+use go::{Board, Player, Stone};
 
+use std::borrow::Borrow;
 use std::collections::HashSet;
+use std::hash::Hash;
+
+pub mod cache;
+
+pub use self::cache::GroupCache;
 
 #[cfg(test)]
 mod test;
@@ -33,35 +38,15 @@ impl<'boardlt, TBoard> Group<'boardlt, TBoard>
             Stone::Empty => {
                 Group {
                     positions: HashSet::new(),
-                    board: board,
+                    board,
                 }
             }
             stone => {
-                let mut stack = Vec::<TBoard::Position>::new();
-                let mut content = HashSet::<TBoard::Position>::new();
-
-                stack.push(*position);
-                content.insert(*position);
-
-                while stack.len() != 0 {
-                    let top = stack.pop().unwrap();
-
-                    for n in &board.neighbors(&top) {
-                        match board.at(n) {
-                            Stone::Empty => {}
-                            owner => {
-                                if owner == stone && !content.contains(n) {
-                                    content.insert(*n);
-                                    stack.push(*n);
-                                }
-                            }
-                        }
-                    }
-                }
-
                 Group {
-                    positions: content,
-                    board: board,
+                    positions: flood_fill(position,
+                                          |p, f| board.for_each_neighbor(p, f),
+                                          |p| board.at(p) == stone),
+                    board,
                 }
             }
         }
@@ -74,11 +59,23 @@ impl<'boardlt, TBoard> Group<'boardlt, TBoard>
 
     /// Returns the hashset of positions that are liberties of the group
     pub fn liberties(&self) -> HashSet<TBoard::Position> {
-        self.positions
-            .iter()
-            .flat_map(|p| self.board.neighbors(p))
-            .filter(|p| self.board.at(p) == Stone::Empty)
-            .collect()
+        let mut liberties = HashSet::new();
+        for position in &self.positions {
+            self.board.for_each_neighbor(position, |p| {
+                if self.board.at(&p) == Stone::Empty {
+                    liberties.insert(p);
+                }
+            });
+        }
+
+        liberties
+    }
+
+    /// Returns true if the group has exactly one liberty left
+    ///
+    /// Empty groups are never in atari.
+    pub fn is_in_atari(&self) -> bool {
+        !self.positions.is_empty() && self.liberties().len() == 1
     }
 
     /// Returns the groups stone-color
@@ -86,3 +83,172 @@ impl<'boardlt, TBoard> Group<'boardlt, TBoard>
         self.positions.iter().next().map(|p| self.board.at(p))
     }
 }
+
+/// Collects the connected positions reachable from start
+///
+/// Walks from position to neighbor as long as `same` holds for the
+/// neighbor, the start itself is not checked. Works on any kind of
+/// intersection content, `Group::new` uses it for stones of one color.
+pub fn flood_fill<TPosition, FNeighbors, FSame>(start: &TPosition,
+                                                mut for_each_neighbor: FNeighbors,
+                                                mut same: FSame)
+                                                -> HashSet<TPosition>
+    where TPosition: Copy + Eq + Hash,
+          FNeighbors: FnMut(&TPosition, &mut dyn FnMut(TPosition)),
+          FSame: FnMut(&TPosition) -> bool
+{
+    let mut stack = vec![*start];
+    let mut content = HashSet::new();
+    content.insert(*start);
+
+    while let Some(top) = stack.pop() {
+        for_each_neighbor(&top, &mut |n| {
+            if !content.contains(&n) && same(&n) {
+                content.insert(n);
+                stack.push(n);
+            }
+        });
+    }
+
+    content
+}
+
+/// Returns the stones a play at the given position would capture
+///
+/// Works on any kind of stones: `content_at` returns None for empty
+/// intersections and every content other than `mover` is an opponent.
+/// All neighboring groups are checked before any is removed, so groups
+/// of different opponents that lose their last liberty are taken
+/// together.
+pub fn captured_by<TPosition, TContent, FNeighbors, FContent>(position: &TPosition,
+                                                              mover: &TContent,
+                                                              mut for_each_neighbor: FNeighbors,
+                                                              content_at: FContent)
+                                                              -> HashSet<TPosition>
+    where TPosition: Copy + Eq + Hash,
+          TContent: Eq,
+          FNeighbors: FnMut(&TPosition, &mut dyn FnMut(TPosition)),
+          FContent: Fn(&TPosition) -> Option<TContent>
+{
+    let mut neighbors = Vec::new();
+    for_each_neighbor(position, &mut |n| neighbors.push(n));
+
+    let mut captured = HashSet::new();
+    for neighbor in neighbors {
+        let content = match content_at(&neighbor) {
+            Some(content) if content != *mover && !captured.contains(&neighbor) => content,
+            _ => continue,
+        };
+
+        let group = flood_fill(&neighbor,
+                               &mut for_each_neighbor,
+                               |p| content_at(p).as_ref() == Some(&content));
+        let mut free = false;
+        for member in &group {
+            for_each_neighbor(member,
+                              &mut |n| free |= n != *position && content_at(&n).is_none());
+        }
+
+        if !free {
+            captured.extend(group);
+        }
+    }
+
+    captured
+}
+
+/// Returns the groups that have a liberty at the given position
+///
+/// Takes the groups of the neighbors from `group_at`, which may
+/// flood-fill them or look them up in a `GroupCache`. Every empty
+/// neighbor yields an empty group, occupied positions yield none.
+pub fn groups_with_liberty_at<'boardlt, TBoard, TGroup, F>(board: &TBoard,
+                                                          position: &TBoard::Position,
+                                                          mut group_at: F)
+                                                          -> Vec<TGroup>
+    where TBoard: Board + 'boardlt,
+          TGroup: Borrow<Group<'boardlt, TBoard>>,
+          F: FnMut(&TBoard::Position) -> TGroup
+{
+    if board.at(position) != Stone::Empty {
+        return Vec::new();
+    }
+
+    let mut found_groups = Vec::<TGroup>::new();
+    board.for_each_neighbor(position, |pos| {
+        if !found_groups.iter().any(|g| g.borrow().positions.contains(&pos)) {
+            found_groups.push(group_at(&pos));
+        }
+    });
+
+    found_groups
+}
+
+/// Returns the set of stones that would be captured if the given player plays at
+/// the given position
+///
+/// Takes the groups from `group_at` like `groups_with_liberty_at`.
+pub fn would_be_captured<'boardlt, TBoard, TGroup, F>(board: &TBoard,
+                                                     player: &Player,
+                                                     position: &TBoard::Position,
+                                                     group_at: F)
+                                                     -> HashSet<TBoard::Position>
+    where TBoard: Board + 'boardlt,
+          TGroup: Borrow<Group<'boardlt, TBoard>>,
+          F: FnMut(&TBoard::Position) -> TGroup
+{
+    groups_with_liberty_at(board, position, group_at)
+        .iter()
+        .map(|g| g.borrow())
+        .filter(|g| {
+            g.stone().unwrap_or(Stone::Empty) != player.stone() && g.liberties().len() == 1
+        })
+        .flat_map(|g| g.positions.iter())
+        .cloned()
+        .collect()
+}
+
+/// Returns if a play here would be suicide
+///
+/// Returns false if a play at position by player would:
+/// * have an empty neighboring intersection
+/// * kill something
+/// * connect own groups that have at least two remaining liberties
+///
+/// If none of those match, the played stone would be left without
+/// liberties and it returns true. Returns false for occupied positions.
+/// Takes the groups from `group_at` like `groups_with_liberty_at`.
+pub fn would_be_suicide<'boardlt, TBoard, TGroup, F>(board: &TBoard,
+                                                    position: &TBoard::Position,
+                                                    player: &Player,
+                                                    group_at: F)
+                                                    -> bool
+    where TBoard: Board + 'boardlt,
+          TGroup: Borrow<Group<'boardlt, TBoard>>,
+          F: FnMut(&TBoard::Position) -> TGroup
+{
+    //  OOOO   consider X to play in the middle
+    // .X.XO   the left X has still a remaining liberty
+    //  OOOO   => no group of X can die
+    if board.at(position) != Stone::Empty {
+        return false;
+    }
+
+    for group in groups_with_liberty_at(board, position, group_at).iter() {
+        let group = group.borrow();
+        let liberties = group.liberties();
+
+        match group.stone() {
+            None => return false, //there is a free neighbor
+            Some(owner) if owner == player.other().stone() && liberties.len() == 1 => {
+                return false; //we kill something
+            }
+            Some(owner) if owner == player.stone() && liberties.len() > 1 => {
+                return false; //a friendly stone has a remaining liberty
+            }
+            _ => {}
+        }
+    }
+
+    true
+}
@@ -1,35 +1,126 @@
-#[cfg(test)]
-mod test {
-    use aga::{Board19x19, Position19x19};
-    use go::{Board, Stone, Group};
-
-    #[test]
-    fn create() {
-        let mut board = Board19x19::new();
-        board.set(&Position19x19 { x: 4, y: 4 }, &Stone::Black);
-        board.set(&Position19x19 { x: 8, y: 8 }, &Stone::White);
-        board.set(&Position19x19 { x: 8, y: 9 }, &Stone::White);
-
-        let empty_group = Group::new(&board, &Position19x19 { x: 0, y: 0 });
-        let black_group = Group::new(&board, &Position19x19 { x: 4, y: 4 });
-        let white_group = Group::new(&board, &Position19x19 { x: 8, y: 8 });
-        let alternative = Group::new(&board, &Position19x19 { x: 8, y: 9 });
-
-        assert_eq!(empty_group.positions.len(), 0);
-        assert_eq!(black_group.positions.len(), 1);
-        assert_eq!(white_group.positions.len(), 2);
-        assert_eq!(white_group, alternative);
-    }
+use aga::{Board19x19, Position19x19};
+use go::{Board, Group, GroupCache, Player, Stone};
+use go::group;
+
+use std::rc::Rc;
+
+#[test]
+fn create() {
+    let mut board = Board19x19::new();
+    board.set(&Position19x19 { x: 4, y: 4 }, &Stone::Black);
+    board.set(&Position19x19 { x: 8, y: 8 }, &Stone::White);
+    board.set(&Position19x19 { x: 8, y: 9 }, &Stone::White);
+
+    let empty_group = Group::new(&board, &Position19x19 { x: 0, y: 0 });
+    let black_group = Group::new(&board, &Position19x19 { x: 4, y: 4 });
+    let white_group = Group::new(&board, &Position19x19 { x: 8, y: 8 });
+    let alternative = Group::new(&board, &Position19x19 { x: 8, y: 9 });
+
+    assert_eq!(empty_group.positions.len(), 0);
+    assert_eq!(black_group.positions.len(), 1);
+    assert_eq!(white_group.positions.len(), 2);
+    assert_eq!(white_group, alternative);
+}
 
-    #[test]
-    fn liberties() {
-        let mut board = Board19x19::new();
-        board.set(&Position19x19 { x: 7, y: 8 }, &Stone::White); //   .
-        board.set(&Position19x19 { x: 8, y: 7 }, &Stone::Black); //  .O.
-        board.set(&Position19x19 { x: 8, y: 8 }, &Stone::White); //  XOO.
-        board.set(&Position19x19 { x: 8, y: 9 }, &Stone::White); //   ..
+#[test]
+fn liberties() {
+    let mut board = Board19x19::new();
+    board.set(&Position19x19 { x: 7, y: 8 }, &Stone::White); //   .
+    board.set(&Position19x19 { x: 8, y: 7 }, &Stone::Black); //  .O.
+    board.set(&Position19x19 { x: 8, y: 8 }, &Stone::White); //  XOO.
+    board.set(&Position19x19 { x: 8, y: 9 }, &Stone::White); //   ..
+
+    let white_group = Group::new(&board, &Position19x19 { x: 8, y: 8 });
+    assert_eq!(white_group.liberties().len(), 6);
+}
+
+#[test]
+fn cache_shares_groups() {
+    let board: Board19x19 = "..XX.
+                             ..XO.
+                             ...O."
+        .parse()
+        .unwrap();
+    let cache = GroupCache::new(&board);
+
+    let first = cache.group_at(&Position19x19 { x: 2, y: 0 });
+    let second = cache.group_at(&Position19x19 { x: 2, y: 1 });
+    let white = cache.group_at(&Position19x19 { x: 3, y: 2 });
+
+    assert!(Rc::ptr_eq(&first, &second));
+    assert_eq!(*first, board.group_at(&Position19x19 { x: 3, y: 0 }));
+    assert_eq!(white.positions.len(), 2);
+    assert_eq!(cache.group_at(&Position19x19 { x: 0, y: 0 }).positions.len(), 0);
+}
 
-        let white_group = Group::new(&board, &Position19x19 { x: 8, y: 8 });
-        assert_eq!(white_group.liberties().len(), 6);
+#[test]
+fn cache_matches_board() {
+    let board: Board19x19 = ".XO.O
+                             XO.OX
+                             .XOX.
+                             XO..."
+        .parse()
+        .unwrap();
+    let cache = GroupCache::new(&board);
+
+    for position in board.iter_positions() {
+        for player in &[Player::Black, Player::White] {
+            assert_eq!(cache.would_be_suicide(&position, player),
+                       board.would_be_suicide(&position, player));
+            assert_eq!(cache.would_be_captured(player, &position),
+                       board.would_be_captured(player, &position));
+        }
     }
 }
+
+#[test]
+fn is_in_atari() {
+    let board: Board19x19 = "XO.
+                             ...
+                             ..."
+        .parse()
+        .unwrap();
+
+    assert!(Group::new(&board, &Position19x19 { x: 0, y: 0 }).is_in_atari());
+    assert!(!Group::new(&board, &Position19x19 { x: 1, y: 0 }).is_in_atari());
+    assert!(!Group::new(&board, &Position19x19 { x: 2, y: 2 }).is_in_atari());
+}
+
+#[test]
+fn flood_fill_empty_region() {
+    let board: Board19x19 = ".X...
+                             X....
+                             ....."
+        .parse()
+        .unwrap();
+
+    let corner = group::flood_fill(&Position19x19 { x: 0, y: 0 },
+                                   |p, f| board.for_each_neighbor(p, f),
+                                   |p| board.at(p) == Stone::Empty);
+    assert_eq!(corner.len(), 1);
+
+    let rest = group::flood_fill(&Position19x19 { x: 4, y: 4 },
+                                 |p, f| board.for_each_neighbor(p, f),
+                                 |p| board.at(p) == Stone::Empty);
+    assert_eq!(rest.len(), 19 * 19 - 3);
+}
+
+#[test]
+fn captured_by_matches_board() {
+    let board: Board19x19 = ".XO..
+                             XO...
+                             O...."
+        .parse()
+        .unwrap();
+    let at = Position19x19 { x: 0, y: 0 };
+
+    let captured = group::captured_by(&at,
+                                      &Stone::White,
+                                      |p, f| board.for_each_neighbor(p, f),
+                                      |p| match board.at(p) {
+                                          Stone::Empty => None,
+                                          stone => Some(stone),
+                                      });
+    assert_eq!(captured, board.would_be_captured(&Player::White, &at));
+    assert_eq!(captured.len(), 2);
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod test;
+
+/// A table of fixed handicap placements
+///
+/// Maps a board size and a number of handicap stones to the
+/// coordinates (x, y) the stones are placed at. `SquareBoard` and
+/// `BoardN` place stones from a table with `set_handicap_from`, so
+/// other conventions or board sizes can supply their own patterns.
+/// `Board::set_handicap` and thus `Action::Handicap` always use the
+/// AGA placements of `fixed_points`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HandicapTable {
+    patterns: HashMap<(usize, u8), Vec<(usize, usize)>>,
+}
+
+impl HandicapTable {
+    /// Creates a table without any patterns
+    pub fn new() -> Self {
+        HandicapTable { patterns: HashMap::new() }
+    }
+
+    /// Creates a table holding the official AGA patterns
+    ///
+    /// Covers two to five stones on 9x9 and two to nine stones on
+    /// 13x13 and 19x19 boards.
+    pub fn aga() -> Self {
+        let mut table = HandicapTable::new();
+
+        for &size in &[9, 13, 19] {
+            for stones in 2..=max_handicap(size) {
+                if let Some(placements) = fixed_points(size, stones) {
+                    table.insert(size, stones, placements);
+                }
+            }
+        }
+
+        table
+    }
+
+    /// Sets the placements for the given board size and number of stones
+    ///
+    /// Replaces a previously stored pattern.
+    pub fn insert(&mut self, size: usize, stones: u8, placements: Vec<(usize, usize)>) {
+        self.patterns.insert((size, stones), placements);
+    }
+
+    /// Returns the placements for the given board size and number of stones
+    ///
+    /// Returns None if the table has no pattern for that combination.
+    pub fn placements(&self, size: usize, stones: u8) -> Option<&[(usize, usize)]> {
+        self.patterns.get(&(size, stones)).map(|p| p.as_slice())
+    }
+}
+
+impl Default for HandicapTable {
+    fn default() -> Self {
+        HandicapTable::aga()
+    }
+}
+
+/// Returns the fixed AGA placements of the handicap stones
+///
+/// Covers two up to `max_handicap` stones on 9x9, 13x13 and 19x19
+/// boards and returns None for other combinations. Boards place their
+/// handicap from here, `HandicapTable` is for other conventions.
+pub fn fixed_points(size: usize, stones: u8) -> Option<Vec<(usize, usize)>> {
+    if stones < 2 || stones > max_handicap(size) {
+        return None;
+    }
+
+    match size {
+        9 => Some(pattern(2, 4, 6, stones)),
+        13 => Some(pattern(3, 6, 9, stones)),
+        19 => Some(pattern(3, 9, 15, stones)),
+        _ => None,
+    }
+}
+
+/// Returns the largest fixed handicap for a board with the given number of lines
+///
+/// Small boards get fewer stones, nine stones on 9x9 leave white no
+/// room to play. Zero for sizes without fixed placements.
+pub fn max_handicap(size: usize) -> u8 {
+    match size {
+        9 => 5,
+        13 | 19 => 9,
+        _ => 0,
+    }
+}
+
+/// Returns the star points of a board with the given number of lines
+///
+/// The four corner points and the center on 9x9 and 13x13, also the
+/// four side points on 19x19. Returns an empty vector for other sizes.
+pub fn star_points(size: usize) -> Vec<(usize, usize)> {
+    match size {
+        9 => pattern(2, 4, 6, 5),
+        13 => pattern(3, 6, 9, 5),
+        19 => pattern(3, 9, 15, 9),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the placements of the given number of stones on the handicap points
+///
+/// The points are spanned by the low, middle and high line.
+fn pattern(low: usize, middle: usize, high: usize, stones: u8) -> Vec<(usize, usize)> {
+    // upper right and lower left
+    let mut placements = vec![(high, low), (low, high)];
+
+    if stones >= 3 {
+        // lower right
+        placements.push((high, high));
+    }
+    if stones >= 4 {
+        // upper left
+        placements.push((low, low));
+    }
+    if stones == 5 || stones == 7 || stones == 9 {
+        // middle
+        placements.push((middle, middle));
+    }
+    if stones >= 6 {
+        // left side and right side
+        placements.push((low, middle));
+        placements.push((high, middle));
+    }
+    if stones >= 8 {
+        // upper side and lower side
+        placements.push((middle, low));
+        placements.push((middle, high));
+    }
+
+    placements
+}
@@ -0,0 +1,61 @@
+use go::HandicapTable;
+use go::handicap::{fixed_points, max_handicap};
+
+#[test]
+fn aga() {
+    let table = HandicapTable::aga();
+
+    assert_eq!(table.placements(19, 1), None);
+    assert_eq!(table.placements(19, 10), None);
+    assert_eq!(table.placements(15, 2), None);
+
+    for stones in 2..6 {
+        assert_eq!(table.placements(9, stones).unwrap().len(), stones as usize);
+    }
+    assert_eq!(table.placements(9, 6), None);
+
+    for stones in 2..10 {
+        assert_eq!(table.placements(13, stones).unwrap().len(), stones as usize);
+        assert_eq!(table.placements(19, stones).unwrap().len(), stones as usize);
+    }
+
+    assert_eq!(table.placements(9, 5), Some(&[(6, 2), (2, 6), (6, 6), (2, 2), (4, 4)][..]));
+    assert_eq!(table.placements(13, 3), Some(&[(9, 3), (3, 9), (9, 9)][..]));
+}
+
+#[test]
+fn insert() {
+    let mut table = HandicapTable::new();
+    assert_eq!(table.placements(9, 2), None);
+
+    table.insert(9, 2, vec![(6, 2), (2, 6)]);
+    assert_eq!(table.placements(9, 2), Some(&[(6, 2), (2, 6)][..]));
+
+    table.insert(9, 2, vec![(2, 2), (6, 6)]);
+    assert_eq!(table.placements(9, 2), Some(&[(2, 2), (6, 6)][..]));
+}
+
+#[test]
+fn fixed() {
+    assert_eq!(fixed_points(19, 1), None);
+    assert_eq!(fixed_points(19, 10), None);
+    assert_eq!(fixed_points(11, 4), None);
+    assert_eq!(fixed_points(9, 6), None);
+
+    assert_eq!(fixed_points(9, 2), Some(vec![(6, 2), (2, 6)]));
+    assert_eq!(fixed_points(13, 4), Some(vec![(9, 3), (3, 9), (9, 9), (3, 3)]));
+    assert_eq!(fixed_points(19, 2), Some(vec![(15, 3), (3, 15)]));
+    let nine = fixed_points(19, 9).unwrap();
+    assert_eq!(nine.len(), 9);
+    assert!(nine.contains(&(9, 9)));
+    assert!(nine.contains(&(3, 3)));
+    assert!(nine.contains(&(15, 9)));
+}
+
+#[test]
+fn maximum() {
+    assert_eq!(max_handicap(9), 5);
+    assert_eq!(max_handicap(13), 9);
+    assert_eq!(max_handicap(19), 9);
+    assert_eq!(max_handicap(7), 0);
+}
@@ -1,9 +1,19 @@
+pub mod analysis;
 pub mod board;
+pub mod diagram;
+pub mod dyn_board;
 pub mod group;
+pub mod handicap;
 pub mod player;
+pub mod score;
 pub mod stone;
+pub mod zobrist;
 
-pub use self::board::Board;
+pub use self::board::{Board, CaptureInfo, Corner, DecodeError, GridBoard, GroupStatistics,
+                      PlayError, Rect};
+pub use self::dyn_board::{DynBoard, ErasedBoard};
 pub use self::stone::Stone;
-pub use self::group::Group;
-pub use self::player::Player;
+pub use self::group::{Group, GroupCache};
+pub use self::handicap::HandicapTable;
+pub use self::player::{Color, Player, PlayerId};
+pub use self::zobrist::{ZobristBoard, ZobristHash};
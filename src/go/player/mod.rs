@@ -1,5 +1,8 @@
 use go::Stone;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 #[cfg(test)]
 mod test;
 
@@ -7,6 +10,7 @@ mod test;
 ///
 /// Either black or white.
 #[derive(Copy, PartialEq, Clone, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Player {
     Black,
     White,
@@ -29,3 +33,60 @@ impl Player {
         }
     }
 }
+
+/// The color of a player in games with any number of players
+///
+/// Colors are numbered in turn order, black and white are the first
+/// two. Two player games use `Player` instead.
+#[derive(Copy, PartialEq, Clone, Eq, Hash, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Color(pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0);
+    pub const WHITE: Color = Color(1);
+
+    /// Returns the color moving after this one among the given number of colors
+    pub fn next(&self, colors: u8) -> Color {
+        Color((self.0 + 1) % colors)
+    }
+
+    /// Returns the two player equivalent of black and white
+    pub fn player(&self) -> Option<Player> {
+        match *self {
+            Color::BLACK => Some(Player::Black),
+            Color::WHITE => Some(Player::White),
+            _ => None,
+        }
+    }
+}
+
+impl From<Player> for Color {
+    fn from(player: Player) -> Color {
+        match player {
+            Player::Black => Color::BLACK,
+            Player::White => Color::WHITE,
+        }
+    }
+}
+
+/// The identity of a person taking part in a game
+///
+/// Assigned by whoever runs the games, e.g. a server's account id.
+#[derive(Copy, PartialEq, Clone, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerId(pub u64);
+
+/// Returns (black, white) as decided by nigiri
+///
+/// The holder grabs a handful of white stones, the guesser guesses
+/// whether their number is odd. The guesser takes black if the guess
+/// was right.
+pub fn nigiri(holder: PlayerId, guesser: PlayerId, stones: u32, guessed_odd: bool)
+              -> (PlayerId, PlayerId) {
+    if (stones % 2 == 1) == guessed_odd {
+        (guesser, holder)
+    } else {
+        (holder, guesser)
+    }
+}
@@ -1,4 +1,5 @@
-use go::{Player, Stone};
+use go::{Color, Player, PlayerId, Stone};
+use go::player::nigiri;
 
 #[test]
 fn other() {
@@ -11,3 +12,22 @@ fn to_stone() {
     assert_eq!(Player::Black.stone(), Stone::Black);
     assert_eq!(Player::White.stone(), Stone::White);
 }
+
+#[test]
+fn nigiri_colors() {
+    let (holder, guesser) = (PlayerId(1), PlayerId(2));
+
+    assert_eq!(nigiri(holder, guesser, 7, true), (guesser, holder));
+    assert_eq!(nigiri(holder, guesser, 8, false), (guesser, holder));
+    assert_eq!(nigiri(holder, guesser, 8, true), (holder, guesser));
+}
+
+#[test]
+fn colors() {
+    assert_eq!(Color::from(Player::White), Color::WHITE);
+    assert_eq!(Color::WHITE.player(), Some(Player::White));
+    assert_eq!(Color(2).player(), None);
+
+    assert_eq!(Color::WHITE.next(3), Color(2));
+    assert_eq!(Color(2).next(3), Color::BLACK);
+}
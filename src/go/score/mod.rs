@@ -0,0 +1,314 @@
+use go::{Board, Player};
+
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod test;
+
+/// The winner of a game
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Winner {
+    Black,
+    White,
+    /// Both players have the same score
+    Jigo,
+}
+
+/// The half points a resignation is stored as
+const RESIGNATION: i64 = i64::MAX;
+
+/// The lead of black over white in half points
+///
+/// Negative scores are leads of white. A resignation is a lead larger
+/// than any other and is not changed by arithmetic. Displayed like
+/// results in SGF: "B+3.5", "W+R" or "Jigo".
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Score {
+    half_points: i64,
+}
+
+impl Score {
+    /// The score of a game both players scored equally in
+    pub const JIGO: Score = Score { half_points: 0 };
+
+    /// Creates a score from half points
+    pub fn from_half_points(half_points: i64) -> Score {
+        Score { half_points: half_points.clamp(-RESIGNATION + 1, RESIGNATION - 1) }
+    }
+
+    /// Creates a score from points, rounded to the nearest half point
+    pub fn from_points(points: f64) -> Score {
+        Score::from_half_points((points * 2.0).round() as i64)
+    }
+
+    /// Creates the score of a game the given player won by resignation
+    pub fn resignation(winner: Player) -> Score {
+        match winner {
+            Player::Black => Score { half_points: RESIGNATION },
+            Player::White => Score { half_points: -RESIGNATION },
+        }
+    }
+
+    /// Returns the lead of black in half points
+    pub fn half_points(&self) -> i64 {
+        self.half_points
+    }
+
+    /// Returns the lead of black in points
+    pub fn points(&self) -> f64 {
+        self.half_points as f64 / 2.0
+    }
+
+    /// Returns true if the game ended by resignation
+    pub fn is_resignation(&self) -> bool {
+        self.half_points.abs() == RESIGNATION
+    }
+
+    /// Returns the player who is ahead
+    pub fn winner(&self) -> Winner {
+        if self.half_points > 0 {
+            Winner::Black
+        } else if self.half_points < 0 {
+            Winner::White
+        } else {
+            Winner::Jigo
+        }
+    }
+}
+
+impl Add for Score {
+    type Output = Score;
+
+    fn add(self, other: Score) -> Score {
+        if self.is_resignation() {
+            self
+        } else if other.is_resignation() {
+            other
+        } else {
+            Score::from_half_points(self.half_points.saturating_add(other.half_points))
+        }
+    }
+}
+
+impl Sub for Score {
+    type Output = Score;
+
+    fn sub(self, other: Score) -> Score {
+        self + -other
+    }
+}
+
+impl Neg for Score {
+    type Output = Score;
+
+    fn neg(self) -> Score {
+        Score { half_points: -self.half_points }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let winner = match self.winner() {
+            Winner::Black => 'B',
+            Winner::White => 'W',
+            Winner::Jigo => return write!(f, "Jigo"),
+        };
+
+        if self.is_resignation() {
+            write!(f, "{}+R", winner)
+        } else {
+            write!(f, "{}+{}", winner, self.points().abs())
+        }
+    }
+}
+
+/// The result of a game
+///
+/// The stored player is the winner. Displayed like results in SGF:
+/// "W+0.5", "B+R", "W+T", "B+F", "0" for jigo and "Void". Rules
+/// deciding ties give the winner a score of zero, "B+0".
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GameResult {
+    /// The player won by counting with the given score
+    Score(Player, Score),
+    /// The player won by resignation of the other one
+    Resignation(Player),
+    /// The player won since the other one ran out of time
+    Timeout(Player),
+    /// The player won since the other one forfeited the game otherwise
+    Forfeit(Player),
+    /// Both players scored equally
+    Jigo,
+    /// The game ended without a result
+    Void,
+}
+
+impl GameResult {
+    /// Creates the result of a counted game
+    pub fn from_score(score: Score) -> GameResult {
+        match score.winner() {
+            Winner::Black => GameResult::Score(Player::Black, score),
+            Winner::White => GameResult::Score(Player::White, score),
+            Winner::Jigo => GameResult::Jigo,
+        }
+    }
+
+    /// Returns the winner, None for jigo and void games
+    pub fn winner(&self) -> Option<Player> {
+        match *self {
+            GameResult::Score(player, _) |
+            GameResult::Resignation(player) |
+            GameResult::Timeout(player) |
+            GameResult::Forfeit(player) => Some(player),
+            GameResult::Jigo | GameResult::Void => None,
+        }
+    }
+
+    /// Returns the points the winner of a counted game won by
+    ///
+    /// Komi is already included, jigo is a margin of 0. None for games
+    /// that were not counted.
+    pub fn margin(&self) -> Option<f64> {
+        match *self {
+            GameResult::Score(_, score) => Some(score.points().abs()),
+            GameResult::Jigo => Some(0.0),
+            _ => None,
+        }
+    }
+
+    /// Returns the score of a counted or resigned game
+    pub fn score(&self) -> Option<Score> {
+        match *self {
+            GameResult::Score(_, score) => Some(score),
+            GameResult::Resignation(player) => Some(Score::resignation(player)),
+            GameResult::Jigo => Some(Score::JIGO),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letter = |player: Player| match player {
+            Player::Black => 'B',
+            Player::White => 'W',
+        };
+
+        match *self {
+            GameResult::Score(player, score) if score == Score::JIGO => {
+                write!(f, "{}+0", letter(player))
+            }
+            GameResult::Score(_, score) => write!(f, "{}", score),
+            GameResult::Resignation(player) => write!(f, "{}+R", letter(player)),
+            GameResult::Timeout(player) => write!(f, "{}+T", letter(player)),
+            GameResult::Forfeit(player) => write!(f, "{}+F", letter(player)),
+            GameResult::Jigo => write!(f, "0"),
+            GameResult::Void => write!(f, "Void"),
+        }
+    }
+}
+
+/// The points of both players counted by area or territory, with komi
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CountResult {
+    /// The points of black
+    pub black: usize,
+    /// The points of white
+    pub white: usize,
+    /// The points white receives as compensation for moving second
+    pub komi: f64,
+}
+
+impl CountResult {
+    /// Returns the lead of black, negative if white is ahead
+    ///
+    /// This is black's points minus white's points minus komi.
+    pub fn margin(&self) -> f64 {
+        self.black as f64 - self.white as f64 - self.komi
+    }
+
+    /// Returns the margin as a score, rounded to half points
+    pub fn score(&self) -> Score {
+        Score::from_points(self.margin())
+    }
+
+    /// Returns the player with the higher score
+    pub fn winner(&self) -> Winner {
+        self.score().winner()
+    }
+}
+
+/// Scores the board the chinese way
+///
+/// Removes the dead stones, counts the area of both players using
+/// `Board::area_scoring_with_dead` and gives white the komi.
+pub fn chinese_scoring<TBoard>(board: &TBoard,
+                               dead_stones: &[TBoard::Position],
+                               komi: f64)
+                               -> CountResult
+    where TBoard: Board
+{
+    let (black, white) = board.area_scoring_with_dead(dead_stones);
+
+    CountResult { black, white, komi }
+}
+
+/// Both counts of a final position, to compare area and territory scoring
+///
+/// The counts differ by the stones on the board and the prisoners, so
+/// the margins agree unless the players made a different number of
+/// moves, passes were free or there are points in seki.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ScoringReport {
+    /// The area of black and white
+    pub area: CountResult,
+    /// The territory plus prisoners of black and white
+    pub territory: CountResult,
+}
+
+impl ScoringReport {
+    /// Returns the lead of black by area minus the one by territory
+    pub fn discrepancy(&self) -> Score {
+        self.area.score() - self.territory.score()
+    }
+
+    /// Checks if both methods give the same margin
+    pub fn agrees(&self) -> bool {
+        self.discrepancy() == Score::JIGO
+    }
+}
+
+/// Counts the position by area and by territory
+///
+/// The dead stones are removed first. `prisoners_black` are the stones
+/// black has captured during the game, `prisoners_white` those white
+/// has captured. The komi may differ between the counts, e.g. when
+/// white is compensated for handicap stones under area scoring only.
+pub fn scoring_report<TBoard>(board: &TBoard,
+                              dead_stones: &[TBoard::Position],
+                              prisoners_black: usize,
+                              prisoners_white: usize,
+                              area_komi: f64,
+                              territory_komi: f64)
+                              -> ScoringReport
+    where TBoard: Board
+{
+    let (black, white) =
+        board.territory_scoring(dead_stones, prisoners_black, prisoners_white);
+
+    ScoringReport {
+        area: chinese_scoring(board, dead_stones, area_komi),
+        territory: CountResult {
+            black,
+            white,
+            komi: territory_komi,
+        },
+    }
+}
@@ -0,0 +1,112 @@
+use aga::{Board9x9, Position19x19};
+use go::score::{chinese_scoring, scoring_report, GameResult, Score, Winner};
+use go::{Board, Player, Stone};
+
+#[test]
+fn chinese() {
+    let board: Board9x9 = "....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO..."
+        .parse()
+        .unwrap();
+
+    let even = chinese_scoring(&board, &[], 0.0);
+    assert_eq!((even.black, even.white), (45, 36));
+    assert_eq!(even.margin(), 9.0);
+    assert_eq!(even.winner(), Winner::Black);
+
+    let komi = chinese_scoring(&board, &[], 9.5);
+    assert_eq!(komi.margin(), -0.5);
+    assert_eq!(komi.winner(), Winner::White);
+    assert_eq!(komi.score().to_string(), "W+0.5");
+
+    assert_eq!(chinese_scoring(&board, &[], 9.0).winner(), Winner::Jigo);
+
+    let mut invaded = board.clone();
+    invaded.set(&Position19x19 { x: 0, y: 4 }, &Stone::White);
+
+    let alive = chinese_scoring(&invaded, &[], 0.0);
+    assert_eq!((alive.black, alive.white), (9, 37));
+
+    let dead = chinese_scoring(&invaded, &[Position19x19 { x: 0, y: 4 }], 0.0);
+    assert_eq!((dead.black, dead.white), (45, 36));
+}
+
+#[test]
+fn score() {
+    let area = Score::from_half_points(19);
+    let komi = Score::from_points(6.5);
+
+    assert_eq!(area.to_string(), "B+9.5");
+    assert_eq!((area - komi).to_string(), "B+3");
+    assert_eq!((komi - area).to_string(), "W+3");
+    assert_eq!((area - area).to_string(), "Jigo");
+    assert_eq!(area - area, Score::JIGO);
+    assert_eq!(Score::from_points(7.4).half_points(), 15);
+    assert_eq!((-komi).points(), -6.5);
+    assert!(komi < area);
+
+    let resigned = Score::resignation(Player::White);
+    assert_eq!(resigned.to_string(), "W+R");
+    assert!(resigned.is_resignation());
+    assert_eq!(resigned + area, resigned);
+    assert_eq!(area - resigned, Score::resignation(Player::Black));
+    assert!(resigned < -area);
+    assert!(Score::resignation(Player::Black) > area);
+    assert_eq!(resigned.winner(), Winner::White);
+}
+
+#[test]
+fn game_result() {
+    let won = GameResult::from_score(Score::from_points(-0.5));
+    assert_eq!(won, GameResult::Score(Player::White, Score::from_points(-0.5)));
+    assert_eq!(won.winner(), Some(Player::White));
+    assert_eq!(won.to_string(), "W+0.5");
+    assert_eq!(won.margin(), Some(0.5));
+
+    assert_eq!(GameResult::from_score(Score::JIGO), GameResult::Jigo);
+    assert_eq!(GameResult::Jigo.winner(), None);
+    assert_eq!(GameResult::Jigo.to_string(), "0");
+    assert_eq!(GameResult::Jigo.margin(), Some(0.0));
+
+    let resigned = GameResult::Resignation(Player::Black);
+    assert_eq!(resigned.to_string(), "B+R");
+    assert_eq!(resigned.score(), Some(Score::resignation(Player::Black)));
+    assert_eq!(resigned.margin(), None);
+
+    assert_eq!(GameResult::Timeout(Player::White).to_string(), "W+T");
+    assert_eq!(GameResult::Forfeit(Player::Black).to_string(), "B+F");
+    assert_eq!(GameResult::Forfeit(Player::Black).score(), None);
+    assert_eq!(GameResult::Void.to_string(), "Void");
+}
+
+#[test]
+fn report() {
+    let board: Board9x9 = "....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO..."
+        .parse()
+        .unwrap();
+
+    let report = scoring_report(&board, &[], 0, 0, 6.5, 6.5);
+    assert_eq!((report.territory.black, report.territory.white), (36, 27));
+    assert_eq!(report.area.score(), report.territory.score());
+    assert!(report.agrees());
+
+    // black captured one stone more than white
+    let report = scoring_report(&board, &[], 1, 0, 6.5, 6.5);
+    assert_eq!(report.discrepancy(), Score::from_points(-1.0));
+    assert!(!report.agrees());
+}
@@ -1,7 +1,11 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// A stone
 ///
 /// Either black, white or empty.
 #[derive(Copy, PartialEq, Clone, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Stone {
     Black,
     White,
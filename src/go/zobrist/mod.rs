@@ -0,0 +1,194 @@
+use go::{Board, GridBoard, Player, Stone};
+use go::board::{BitBoard19, BoardN, Direction, MaskedBoard, SquareBoard, TorusBoard};
+
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod test;
+
+/// Returns the zobrist key of a stone at the given coordinates
+///
+/// The keys are derived from the coordinates and the color alone, so
+/// they are the same on every run and for every board. Empty
+/// intersections have the key 0.
+pub fn stone_key(x: usize, y: usize, stone: &Stone) -> u64 {
+    let color = match *stone {
+        Stone::Empty => return 0,
+        Stone::Black => 0,
+        Stone::White => 1,
+    };
+
+    split_mix(((y as u64) << 33) | ((x as u64) << 1) | color)
+}
+
+/// Returns the zobrist key of the player to move
+///
+/// Combine it with a board hash to tell apart equal boards with
+/// different players to move.
+pub fn player_key(player: &Player) -> u64 {
+    match *player {
+        Player::Black => split_mix(u64::MAX - 1),
+        Player::White => split_mix(u64::MAX),
+    }
+}
+
+/// Scrambles a value with the splitmix64 finalizer
+fn split_mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// A stable 64 bit hash of the stones on a board
+///
+/// The hash is the xor of the `stone_key`s of all stones, so it can be
+/// updated on every `set` by xoring out the old and in the new key.
+pub trait ZobristHash: GridBoard {
+    /// Returns the zobrist hash of the board
+    ///
+    /// Computes the hash from all positions unless the board keeps
+    /// track of it.
+    fn zobrist_hash(&self) -> u64 {
+        self.iter_positions().fold(0, |hash, p| {
+            let (x, y) = self.coordinates(&p);
+            hash ^ stone_key(x, y, &self.at(&p))
+        })
+    }
+}
+
+impl ZobristHash for BitBoard19 {}
+impl ZobristHash for BoardN {}
+impl<const N: usize> ZobristHash for SquareBoard<N> {}
+impl<const N: usize> ZobristHash for TorusBoard<N> {}
+impl<TBoard> ZobristHash for MaskedBoard<TBoard> where TBoard: GridBoard {}
+
+/// A board that keeps its zobrist hash up to date
+///
+/// Updates the hash incrementally whenever a stone is set, making
+/// `zobrist_hash` free.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+pub struct ZobristBoard<TBoard>
+    where TBoard: GridBoard
+{
+    board: TBoard,
+    hash: u64,
+}
+
+impl<TBoard> ZobristBoard<TBoard>
+    where TBoard: ZobristHash
+{
+    /// Wraps the board, computing its hash once
+    pub fn from_board(board: TBoard) -> Self {
+        let hash = board.zobrist_hash();
+        ZobristBoard { board, hash }
+    }
+
+    /// Returns the wrapped board
+    pub fn board(&self) -> &TBoard {
+        &self.board
+    }
+}
+
+impl<TBoard> Board for ZobristBoard<TBoard>
+    where TBoard: ZobristHash
+{
+    type Position = TBoard::Position;
+
+    fn new() -> Self {
+        ZobristBoard::from_board(TBoard::new())
+    }
+
+    fn on_board(&self, position: &Self::Position) -> bool {
+        self.board.on_board(position)
+    }
+
+    fn at(&self, position: &Self::Position) -> Stone {
+        self.board.at(position)
+    }
+
+    fn set(&mut self, position: &Self::Position, stone: &Stone) {
+        let (x, y) = self.board.coordinates(position);
+        self.hash ^= stone_key(x, y, &self.board.at(position)) ^ stone_key(x, y, stone);
+        self.board.set(position, stone);
+    }
+
+    fn set_handicap(&mut self, stones: u8) {
+        self.board.set_handicap(stones);
+        self.hash = self.board.zobrist_hash();
+    }
+
+    fn max_handicap(&self) -> u8 {
+        self.board.max_handicap()
+    }
+
+    fn star_points(&self) -> Vec<Self::Position> {
+        self.board.star_points()
+    }
+
+    fn iter_positions<'boardlt>(&'boardlt self)
+                                -> impl Iterator<Item = Self::Position> + 'boardlt {
+        self.board.iter_positions()
+    }
+
+    fn for_each_neighbor<F>(&self, position: &Self::Position, f: F)
+        where F: FnMut(Self::Position)
+    {
+        self.board.for_each_neighbor(position, f);
+    }
+
+    fn step(&self, position: &Self::Position, direction: Direction) -> Option<Self::Position> {
+        self.board.step(position, direction)
+    }
+
+    fn for_each_diagonal<F>(&self, position: &Self::Position, f: F)
+        where F: FnMut(Self::Position)
+    {
+        self.board.for_each_diagonal(position, f);
+    }
+
+    fn would_capture_any(&self, player: &Player, position: &Self::Position) -> bool {
+        self.board.would_capture_any(player, position)
+    }
+
+    fn would_be_captured(&self,
+                         player: &Player,
+                         position: &Self::Position)
+                         -> HashSet<Self::Position> {
+        self.board.would_be_captured(player, position)
+    }
+
+    fn would_be_suicide(&self, position: &Self::Position, player: &Player) -> bool {
+        self.board.would_be_suicide(position, player)
+    }
+
+    fn legal_moves<'boardlt>(&'boardlt self,
+                             player: &Player)
+                             -> impl Iterator<Item = Self::Position> + 'boardlt {
+        self.board.legal_moves(player)
+    }
+}
+
+impl<TBoard> GridBoard for ZobristBoard<TBoard>
+    where TBoard: ZobristHash
+{
+    fn size(&self) -> usize {
+        self.board.size()
+    }
+
+    fn position(&self, x: usize, y: usize) -> Self::Position {
+        self.board.position(x, y)
+    }
+
+    fn coordinates(&self, position: &Self::Position) -> (usize, usize) {
+        self.board.coordinates(position)
+    }
+}
+
+impl<TBoard> ZobristHash for ZobristBoard<TBoard>
+    where TBoard: ZobristHash
+{
+    fn zobrist_hash(&self) -> u64 {
+        self.hash
+    }
+}
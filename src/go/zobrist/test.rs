@@ -0,0 +1,50 @@
+use aga::{Board19x19, Position19x19};
+use go::board::BitBoard19;
+use go::{Board, Player, Stone, ZobristBoard, ZobristHash};
+use go::zobrist::player_key;
+
+#[test]
+fn stable() {
+    let mut array = Board19x19::new();
+    let mut bits = BitBoard19::new();
+    assert_eq!(array.zobrist_hash(), 0);
+
+    array.set(&Position19x19 { x: 3, y: 3 }, &Stone::Black);
+    bits.set(&Position19x19 { x: 3, y: 3 }, &Stone::Black);
+    assert!(array.zobrist_hash() != 0);
+    assert_eq!(array.zobrist_hash(), bits.zobrist_hash());
+
+    array.set(&Position19x19 { x: 3, y: 3 }, &Stone::White);
+    assert!(array.zobrist_hash() != bits.zobrist_hash());
+
+    assert!(player_key(&Player::Black) != player_key(&Player::White));
+}
+
+#[test]
+fn incremental() {
+    let mut board = ZobristBoard::<Board19x19>::new();
+
+    board.set_handicap(4);
+    board.set(&Position19x19 { x: 2, y: 2 }, &Stone::White);
+    board.set(&Position19x19 { x: 2, y: 3 }, &Stone::Black);
+    board.set(&Position19x19 { x: 2, y: 3 }, &Stone::White);
+    assert_eq!(board.zobrist_hash(), board.board().zobrist_hash());
+
+    board.set(&Position19x19 { x: 2, y: 2 }, &Stone::Empty);
+    board.set(&Position19x19 { x: 2, y: 3 }, &Stone::Empty);
+
+    let mut handicap = Board19x19::new();
+    handicap.set_handicap(4);
+    assert_eq!(board.zobrist_hash(), handicap.zobrist_hash());
+}
+
+#[test]
+fn forwards_to_board() {
+    let mut board = ZobristBoard::<BitBoard19>::new();
+    assert_eq!(board.star_points(), board.board().star_points());
+    assert_eq!(board.star_points().len(), 9);
+
+    board.set_handicap(2);
+    assert_eq!(board.legal_moves(&Player::White).count(),
+               board.board().legal_moves(&Player::White).count());
+}
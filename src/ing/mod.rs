@@ -0,0 +1,3 @@
+pub mod rules;
+
+pub use ing::rules::{Action, GameState, KOMI, fill_in_counting, winner};
@@ -0,0 +1,168 @@
+use std::fmt;
+use std::ops::Deref;
+
+use aga;
+use aga::RejectReason;
+use engine;
+use engine::Action as EngineAction;
+use go::{Board, Player, ZobristHash};
+use go::score::{chinese_scoring, CountResult, GameResult, Score, Winner};
+use rules::RuleSet;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod test;
+
+/// The komi of the Ing rules
+///
+/// White receives eight points, black wins games ending in a tie.
+pub const KOMI: f64 = 8.0;
+
+/// The state of a game as used by the Ing rule set
+///
+/// Ing games are played like AGA games under `RuleSet::ing`: suicide
+/// of more than one stone is legal and a whole board position may not
+/// be repeated with the same player to move. Dereferences to the AGA
+/// state for everything else.
+#[derive(Clone)]
+pub struct GameState<TBoard>(aga::rules::GameState<TBoard>) where TBoard: ZobristHash;
+
+impl<TBoard> engine::GameState for GameState<TBoard>
+    where TBoard: ZobristHash
+{
+    fn new() -> Self {
+        let mut state = aga::rules::GameState::new();
+        aga::Action::SetRules { rules: RuleSet::ing() }.execute(&mut state);
+
+        GameState(state)
+    }
+}
+
+impl<TBoard> engine::PositionHash for GameState<TBoard>
+    where TBoard: ZobristHash
+{
+    fn position_hash(&self) -> u64 {
+        self.0.position_hash()
+    }
+}
+
+impl<TBoard> Deref for GameState<TBoard>
+    where TBoard: ZobristHash
+{
+    type Target = aga::rules::GameState<TBoard>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<TBoard> GameState<TBoard>
+    where TBoard: ZobristHash
+{
+    /// Returns the result of the game, None while it is not over
+    ///
+    /// Ties are wins of black by zero points, there is no jigo under
+    /// Ing rules.
+    pub fn result(&self) -> Option<GameResult> {
+        self.0.result().map(|result| match result {
+            GameResult::Jigo => GameResult::Score(Player::Black, Score::JIGO),
+            result => result,
+        })
+    }
+
+    /// Returns the winner of an ended game
+    ///
+    /// Black wins ties. Returns None while the game is still running
+    /// or if it ended without result.
+    pub fn winner(&self) -> Option<Winner> {
+        self.result().and_then(|result| result.winner()).map(|player| match player {
+            Player::Black => Winner::Black,
+            Player::White => Winner::White,
+        })
+    }
+}
+
+/// An action in a game under Ing rules
+///
+/// Wraps the AGA actions. The rules and the komi are fixed, so
+/// actions changing them are rejected.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde",
+           serde(bound(serialize = "TBoard::Position: Serialize",
+                       deserialize = "TBoard::Position: Deserialize<'de>")))]
+pub struct Action<TBoard>(pub aga::Action<TBoard>) where TBoard: ZobristHash;
+
+impl<TBoard> From<aga::Action<TBoard>> for Action<TBoard>
+    where TBoard: ZobristHash
+{
+    fn from(action: aga::Action<TBoard>) -> Self {
+        Action(action)
+    }
+}
+
+impl<TBoard> fmt::Display for Action<TBoard>
+    where TBoard: ZobristHash,
+          TBoard::Position: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<TBoard> engine::Action for Action<TBoard>
+    where TBoard: ZobristHash
+{
+    type GameState = GameState<TBoard>;
+    type Reason = RejectReason;
+    type Position = TBoard::Position;
+
+    fn check(&self, state: &Self::GameState) -> Result<(), RejectReason> {
+        match self.0 {
+            aga::Action::SetRules { .. } |
+            aga::Action::SetSuicideRule { .. } |
+            aga::Action::SetKomi { .. } => Err(RejectReason::FixedRules),
+            ref action => action.validate(&state.0),
+        }
+    }
+
+    fn execute(&self, state: &mut Self::GameState) {
+        self.0.execute(&mut state.0);
+    }
+}
+
+/// Counts the board the Ing way
+///
+/// Both players fill their territory with their own stones and count
+/// the stones on the board, which gives the same numbers as area
+/// scoring. White receives the Ing komi.
+pub fn fill_in_counting<TBoard>(board: &TBoard, dead_stones: &[TBoard::Position]) -> CountResult
+    where TBoard: Board
+{
+    chinese_scoring(board, dead_stones, KOMI)
+}
+
+/// Returns the winner of a counted game under Ing rules
+///
+/// Unlike plain area scoring, a tie is a win for black.
+pub fn winner(result: &CountResult) -> Winner {
+    match result.winner() {
+        Winner::Jigo => Winner::Black,
+        winner => winner,
+    }
+}
+
+impl<TBoard> engine::Setup for Action<TBoard>
+    where TBoard: ZobristHash
+{
+    /// Sets up the state like the AGA actions, except for the fixed rules
+    fn setup(state: &GameState<TBoard>) -> Vec<Self> {
+        <aga::Action<TBoard> as engine::Setup>::setup(&state.0)
+            .into_iter()
+            .filter(|action| !matches!(*action, aga::Action::SetRules { .. }))
+            .map(Action)
+            .collect()
+    }
+}
@@ -0,0 +1,142 @@
+use engine::{Game, InsertError, Path};
+use go::{Board, Player, Stone};
+use go::score::{CountResult, GameResult, Score, Winner};
+use aga;
+use aga::{Board9x9, Board19x19, Position19x19, RejectReason, SuicideRule};
+use ing::{Action, KOMI, fill_in_counting, winner};
+
+type IngGame = Game<Action<Board19x19>>;
+
+fn play(player: Player, x: usize, y: usize) -> Action<Board19x19> {
+    Action(aga::Action::Play {
+        player,
+        at: Position19x19 { x, y },
+        time_used: None,
+    })
+}
+
+fn pass(player: Player) -> Action<Board19x19> {
+    Action(aga::Action::Pass {
+        player,
+        time_used: None,
+    })
+}
+
+#[test]
+fn multi_stone_suicide() {
+    let mut game = IngGame::new();
+    let mut cursor = Path::Root;
+
+    let moves = [(0, 1), (0, 2), (1, 0), (1, 1), (5, 5), (2, 0)];
+    for (ply, &(x, y)) in moves.iter().enumerate() {
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        cursor = game.insert(&cursor, play(player, x, y)).unwrap().into();
+    }
+
+    cursor = game.insert(&cursor, play(Player::Black, 0, 0)).unwrap().into();
+    assert!(game.get_state(&cursor).unwrap().last_move() == Some(&Position19x19 { x: 0, y: 0 }));
+}
+
+#[test]
+fn rules_are_fixed() {
+    let mut game = IngGame::new();
+
+    assert!(game.insert(&Path::Root,
+                        Action(aga::Action::SetSuicideRule { rule: SuicideRule::Forbidden })).is_none());
+    assert_eq!(game.try_insert(&Path::Root,
+                               Action(aga::Action::SetSuicideRule { rule: SuicideRule::Forbidden })),
+               Err(InsertError::Rejected(RejectReason::FixedRules)));
+    assert_eq!(game.try_insert(&Path::Root, Action(aga::Action::SetKomi { komi: 0.5 })),
+               Err(InsertError::Rejected(RejectReason::FixedRules)));
+    assert_eq!(game.get_state(&Path::Root).unwrap().komi(), KOMI);
+}
+
+#[test]
+fn black_wins_ties() {
+    let mut game = IngGame::new();
+    let mut cursor = game.insert(&Path::Root, play(Player::Black, 3, 3)).unwrap();
+    assert!(game.get_state(&cursor).unwrap().winner().is_none());
+
+    cursor = game.insert(&cursor, pass(Player::White)).unwrap();
+    cursor = game.insert(&cursor, pass(Player::Black)).unwrap();
+    cursor = game.insert(&cursor, pass(Player::White)).unwrap();
+    cursor = game.insert(&cursor,
+                         aga::Action::RequestEnd {
+                                 player: Player::Black,
+                                 dead_stones: vec![],
+                             }
+                             .into()).unwrap();
+    cursor = game.insert(&cursor, aga::Action::AcceptEnd { player: Player::White }.into()).unwrap();
+
+    assert!(game.get_state(&cursor).unwrap().winner() == Some(Winner::Black));
+}
+
+#[test]
+fn tie_is_stored_as_black_win() {
+    let board: Board9x9 = "....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ...X.O...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO..."
+        .parse()
+        .unwrap();
+    let stones = |stone: Stone| {
+        board.iter_positions().filter(|position| board.at(position) == stone).collect()
+    };
+
+    let mut game = Game::<Action<Board9x9>>::new();
+    let setup = aga::Action::Setup {
+        add_black: stones(Stone::Black),
+        add_white: stones(Stone::White),
+        clear: vec![],
+        to_move: None,
+    };
+    let mut cursor = game.insert(&Path::Root, Action(setup)).unwrap();
+    let ending = [Action(aga::Action::Pass { player: Player::Black, time_used: None }),
+                  Action(aga::Action::Pass { player: Player::White, time_used: None }),
+                  Action(aga::Action::RequestEnd {
+                      player: Player::Black,
+                      dead_stones: vec![],
+                  }),
+                  Action(aga::Action::AcceptEnd { player: Player::White })];
+    for action in ending.iter().cloned() {
+        cursor = game.insert(&cursor, action).unwrap();
+    }
+
+    let state = game.get_state(&cursor).unwrap();
+    assert_eq!(state.counted(), Some((44, 36)));
+    assert_eq!(state.result(), Some(GameResult::Score(Player::Black, Score::JIGO)));
+    assert_eq!(state.result().unwrap().to_string(), "B+0");
+    assert_eq!(state.winner(), Some(Winner::Black));
+}
+
+#[test]
+fn counting() {
+    let board: Board9x9 = "....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO...
+                           ....XO..."
+        .parse()
+        .unwrap();
+
+    let result = fill_in_counting(&board, &[]);
+    assert_eq!(result.margin(), 1.0);
+    assert_eq!(winner(&result), Winner::Black);
+
+    let tied = CountResult {
+        black: 44,
+        white: 36,
+        komi: KOMI,
+    };
+    assert_eq!(tied.winner(), Winner::Jigo);
+    assert_eq!(winner(&tied), Winner::Black);
+}
@@ -1,3 +1,15 @@
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
 pub mod engine;
+pub mod formats;
 pub mod go;
 pub mod aga;
+pub mod ing;
+pub mod render;
+pub mod rules;
+pub mod time;
+pub mod variants;
+pub mod view;
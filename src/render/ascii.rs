@@ -0,0 +1,130 @@
+use go::{GridBoard, Stone, ZobristBoard, ZobristHash};
+use go::board::{BitBoard19, BoardN, CapturingBoard, MaskedBoard, SquareBoard, TorusBoard,
+                TrackedBoard};
+
+use std::fmt;
+
+/// Returns the letter of the column with the given x coordinate
+///
+/// Columns are labeled from A on, skipping I as usual in go.
+pub fn column_letter(x: usize) -> char {
+    let letter = if x < 8 { x } else { x + 1 };
+    (b'A' + letter as u8) as char
+}
+
+/// Returns the number of the row with the given y coordinate
+///
+/// Rows are numbered from the bottom, the top row (y = 0) has the
+/// highest number.
+pub fn row_number(size: usize, y: usize) -> usize {
+    size - y
+}
+
+/// Returns the character a stone is drawn with
+fn stone_char(stone: Stone) -> char {
+    match stone {
+        Stone::Black => 'X',
+        Stone::White => 'O',
+        Stone::Empty => '.',
+    }
+}
+
+/// Writes the column letters of the board
+fn write_columns(f: &mut dyn fmt::Write, size: usize) -> fmt::Result {
+    write!(f, "  ")?;
+    for x in 0..size {
+        write!(f, " {}", column_letter(x))?;
+    }
+    writeln!(f)
+}
+
+/// Writes the board as text
+///
+/// Draws black stones as `X`, white stones as `O` and empty
+/// intersections as `.`, framed by column letters and row numbers.
+/// Intersections that are not on the board are left blank.
+pub fn write<TBoard>(f: &mut dyn fmt::Write, board: &TBoard) -> fmt::Result
+    where TBoard: GridBoard
+{
+    let size = board.size();
+
+    write_columns(f, size)?;
+    for y in 0..size {
+        write!(f, "{:>2}", row_number(size, y))?;
+        for x in 0..size {
+            let position = board.position(x, y);
+            if board.on_board(&position) {
+                write!(f, " {}", stone_char(board.at(&position)))?;
+            } else {
+                write!(f, "  ")?;
+            }
+        }
+        writeln!(f, " {}", row_number(size, y))?;
+    }
+    write_columns(f, size)
+}
+
+/// Returns the board as text as written by `write`
+pub fn render<TBoard>(board: &TBoard) -> String
+    where TBoard: GridBoard
+{
+    let mut text = String::new();
+    write(&mut text, board).expect("writing to a string does not fail");
+    text
+}
+
+impl<const N: usize> fmt::Display for SquareBoard<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write(f, self)
+    }
+}
+
+impl<const N: usize> fmt::Display for TorusBoard<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write(f, self)
+    }
+}
+
+impl fmt::Display for BoardN {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write(f, self)
+    }
+}
+
+impl fmt::Display for BitBoard19 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write(f, self)
+    }
+}
+
+impl<TBoard> fmt::Display for MaskedBoard<TBoard>
+    where TBoard: GridBoard
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write(f, self)
+    }
+}
+
+impl<TBoard> fmt::Display for TrackedBoard<TBoard>
+    where TBoard: GridBoard
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write(f, self)
+    }
+}
+
+impl<TBoard> fmt::Display for CapturingBoard<TBoard>
+    where TBoard: GridBoard
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write(f, self)
+    }
+}
+
+impl<TBoard> fmt::Display for ZobristBoard<TBoard>
+    where TBoard: ZobristHash
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write(f, self)
+    }
+}
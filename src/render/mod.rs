@@ -0,0 +1,5 @@
+pub mod ascii;
+pub mod svg;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,102 @@
+use go::{GridBoard, Stone};
+use render::ascii::{column_letter, row_number};
+
+use std::fmt::Write;
+
+/// The distance between two lines in pixels
+const CELL: usize = 30;
+
+/// Returns the pixel coordinate of the line with the given index
+///
+/// Leaves room for one cell of coordinates at the upper and left side.
+fn pixel(line: usize) -> usize {
+    (line + 1) * CELL
+}
+
+/// Renders the board as an SVG image
+///
+/// Draws the grid with star points, column letters and row numbers
+/// as well as all stones. Marks the last move with a ring if given.
+pub fn render<TBoard>(board: &TBoard, last_move: Option<&TBoard::Position>) -> String
+    where TBoard: GridBoard
+{
+    let size = board.size();
+    let extent = pixel(size);
+    let mut svg = String::new();
+
+    // writing to a String does not fail
+    let _ = writeln!(svg,
+                     "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" \
+                      viewBox=\"0 0 {0} {0}\">",
+                     extent);
+    let _ = writeln!(svg,
+                     "<rect width=\"{0}\" height=\"{0}\" fill=\"#dcb35c\"/>",
+                     extent);
+
+    for line in 0..size {
+        let _ = writeln!(svg,
+                         "<line x1=\"{0}\" y1=\"{1}\" x2=\"{2}\" y2=\"{1}\" stroke=\"black\"/>",
+                         pixel(0),
+                         pixel(line),
+                         pixel(size - 1));
+        let _ = writeln!(svg,
+                         "<line x1=\"{0}\" y1=\"{1}\" x2=\"{0}\" y2=\"{2}\" stroke=\"black\"/>",
+                         pixel(line),
+                         pixel(0),
+                         pixel(size - 1));
+        let _ = writeln!(svg,
+                         "<text x=\"{}\" y=\"{}\" font-size=\"12\" text-anchor=\"middle\">{}</text>",
+                         pixel(line),
+                         CELL / 2,
+                         column_letter(line));
+        let _ = writeln!(svg,
+                         "<text x=\"{}\" y=\"{}\" font-size=\"12\" text-anchor=\"middle\" \
+                          dominant-baseline=\"central\">{}</text>",
+                         CELL / 2,
+                         pixel(line),
+                         row_number(size, line));
+    }
+
+    for star_point in board.star_points() {
+        let (x, y) = board.coordinates(&star_point);
+        let _ = writeln!(svg,
+                         "<circle cx=\"{}\" cy=\"{}\" r=\"3\" fill=\"black\"/>",
+                         pixel(x),
+                         pixel(y));
+    }
+
+    for position in board.iter_positions() {
+        let (x, y) = board.coordinates(&position);
+        let fill = match board.at(&position) {
+            Stone::Black => "black",
+            Stone::White => "white",
+            Stone::Empty => continue,
+        };
+
+        let _ = writeln!(svg,
+                         "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"black\"/>",
+                         pixel(x),
+                         pixel(y),
+                         CELL / 2 - 1,
+                         fill);
+    }
+
+    if let Some(position) = last_move {
+        let (x, y) = board.coordinates(position);
+        let stroke = match board.at(position) {
+            Stone::Black => "white",
+            _ => "black",
+        };
+
+        let _ = writeln!(svg,
+                         "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"{}\" \
+                          stroke-width=\"2\"/>",
+                         pixel(x),
+                         pixel(y),
+                         CELL / 4,
+                         stroke);
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,73 @@
+use go::board::{BoardN, MaskedBoard, Position, SquareBoard};
+use go::{Board, Stone};
+use render::ascii::{column_letter, render};
+use render::svg;
+
+#[test]
+fn columns() {
+    assert_eq!(column_letter(0), 'A');
+    assert_eq!(column_letter(7), 'H');
+    assert_eq!(column_letter(8), 'J');
+    assert_eq!(column_letter(18), 'T');
+}
+
+#[test]
+fn ascii() {
+    let mut board = SquareBoard::<5>::new();
+    board.set(&Position { x: 1, y: 0 }, &Stone::Black);
+    board.set(&Position { x: 3, y: 4 }, &Stone::White);
+
+    let expected = ["   A B C D E",
+                    " 5 . X . . . 5",
+                    " 4 . . . . . 4",
+                    " 3 . . . . . 3",
+                    " 2 . . . . . 2",
+                    " 1 . . . O . 1",
+                    "   A B C D E",
+                    ""]
+        .join("\n");
+
+    assert_eq!(render(&board), expected);
+    assert_eq!(board.to_string(), expected);
+}
+
+#[test]
+fn ascii_two_digit_rows() {
+    let board = BoardN::with_size(11).unwrap();
+    let text = board.to_string();
+
+    assert!(text.starts_with("   A B C D E F G H J K L\n11 . . ."));
+    assert!(text.ends_with(" 1 . . . . . . . . . . . 1\n   A B C D E F G H J K L\n"));
+}
+
+#[test]
+fn svg() {
+    let mut board = SquareBoard::<9>::new();
+    board.set(&Position { x: 2, y: 2 }, &Stone::Black);
+    board.set(&Position { x: 6, y: 6 }, &Stone::White);
+
+    let image = svg::render(&board, Some(&Position { x: 6, y: 6 }));
+
+    assert!(image.starts_with("<svg "));
+    assert!(image.ends_with("</svg>\n"));
+    assert_eq!(image.matches("<line ").count(), 18);
+    assert_eq!(image.matches("fill=\"black\" stroke=\"black\"").count(), 1);
+    assert_eq!(image.matches("fill=\"white\" stroke=\"black\"").count(), 1);
+    assert_eq!(image.matches(" r=\"3\" ").count(), 5);
+    assert_eq!(image.matches("fill=\"none\"").count(), 1);
+    assert!(image.contains(">J</text>"));
+    assert!(!image.contains(">I</text>"));
+
+    assert_eq!(svg::render(&board, None).matches("fill=\"none\"").count(), 0);
+}
+
+#[test]
+fn svg_star_points_of_board() {
+    let mask = vec![Position { x: 4, y: 4 }].into_iter().collect();
+    let board = MaskedBoard::with_mask(SquareBoard::<9>::new(), mask);
+
+    // no star point is drawn on the hole
+    let image = svg::render(&board, None);
+    assert_eq!(image.matches(" r=\"3\" ").count(), 4);
+    assert!(!image.contains("cx=\"150\" cy=\"150\" r=\"3\""));
+}
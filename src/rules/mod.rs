@@ -0,0 +1,149 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod test;
+
+/// Which repetitions of a position are forbidden
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum KoRule {
+    /// Only the immediate recapture of a single stone ko is forbidden
+    Simple,
+    /// No board layout may repeat with the same player to move
+    SituationalSuperko,
+    /// No board layout may repeat, no matter who is to move
+    PositionalSuperko,
+}
+
+/// Whether a play may leave its own group without liberties
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SuicideRule {
+    /// Suicide is illegal, as in the AGA rules
+    Forbidden,
+    /// Suicide of more than one stone is legal, as in the New Zealand
+    /// rules. The suicided stones are removed from the board.
+    MultiStone,
+}
+
+/// How the score of a finished game is counted
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ScoringMethod {
+    /// Stones on the board plus surrounded intersections
+    Area,
+    /// Surrounded intersections plus prisoners
+    Territory,
+}
+
+/// Which passes end the game
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PassRule {
+    /// Black passes and white passes directly afterwards
+    BlackThenWhite,
+    /// Any two passes in a row
+    TwoConsecutive,
+}
+
+/// A combination of rules a game is played under
+///
+/// The presets describe the rules of the federations, servers may
+/// combine the single rules freely.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RuleSet {
+    /// Which repetitions are forbidden
+    pub ko: KoRule,
+    /// Whether suicide is legal
+    pub suicide: SuicideRule,
+    /// How the final score is counted
+    pub scoring: ScoringMethod,
+    /// The points white receives as compensation for moving second
+    pub komi: f64,
+    /// Which passes end the game
+    pub passes: PassRule,
+    /// Whether a passing player hands a stone to the opponent as prisoner
+    pub pass_stones: bool,
+    /// Whether the first pass takes a button worth half a point
+    ///
+    /// Button go makes area and territory scoring agree.
+    pub button: bool,
+}
+
+impl RuleSet {
+    /// The rules of the American Go Association
+    ///
+    /// White has to pass last and every pass hands a stone to the
+    /// opponent, so area and territory scoring give the same result.
+    pub fn aga() -> Self {
+        RuleSet {
+            ko: KoRule::SituationalSuperko,
+            suicide: SuicideRule::Forbidden,
+            scoring: ScoringMethod::Area,
+            komi: 7.5,
+            passes: PassRule::BlackThenWhite,
+            pass_stones: true,
+            button: false,
+        }
+    }
+
+    /// The rules of the New Zealand Go Society
+    pub fn new_zealand() -> Self {
+        RuleSet {
+            ko: KoRule::SituationalSuperko,
+            suicide: SuicideRule::MultiStone,
+            scoring: ScoringMethod::Area,
+            komi: 7.0,
+            passes: PassRule::TwoConsecutive,
+            pass_stones: false,
+            button: false,
+        }
+    }
+
+    /// The Ing rules, their ko rules approximated by situational superko
+    pub fn ing() -> Self {
+        RuleSet {
+            ko: KoRule::SituationalSuperko,
+            suicide: SuicideRule::MultiStone,
+            scoring: ScoringMethod::Area,
+            komi: 8.0,
+            passes: PassRule::BlackThenWhite,
+            pass_stones: false,
+            button: false,
+        }
+    }
+
+    /// The rules of the Chinese Weiqi Association
+    pub fn chinese() -> Self {
+        RuleSet {
+            ko: KoRule::PositionalSuperko,
+            suicide: SuicideRule::Forbidden,
+            scoring: ScoringMethod::Area,
+            komi: 7.5,
+            passes: PassRule::TwoConsecutive,
+            pass_stones: false,
+            button: false,
+        }
+    }
+
+    /// The rules of the Nihon Ki-in
+    pub fn japanese() -> Self {
+        RuleSet {
+            ko: KoRule::Simple,
+            suicide: SuicideRule::Forbidden,
+            scoring: ScoringMethod::Territory,
+            komi: 6.5,
+            passes: PassRule::TwoConsecutive,
+            pass_stones: false,
+            button: false,
+        }
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet::aga()
+    }
+}
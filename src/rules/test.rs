@@ -0,0 +1,22 @@
+use rules::{KoRule, PassRule, RuleSet, ScoringMethod, SuicideRule};
+
+#[test]
+fn presets() {
+    assert_eq!(RuleSet::default(), RuleSet::aga());
+
+    let aga = RuleSet::aga();
+    assert_eq!(aga.suicide, SuicideRule::Forbidden);
+    assert_eq!(aga.scoring, ScoringMethod::Area);
+    assert_eq!(aga.passes, PassRule::BlackThenWhite);
+    assert!(aga.pass_stones);
+
+    assert_eq!(RuleSet::new_zealand().suicide, SuicideRule::MultiStone);
+    assert_eq!(RuleSet::ing().komi, 8.0);
+    assert_eq!(RuleSet::chinese().ko, KoRule::PositionalSuperko);
+
+    let japanese = RuleSet::japanese();
+    assert_eq!(japanese.ko, KoRule::Simple);
+    assert_eq!(japanese.scoring, ScoringMethod::Territory);
+    assert!(!japanese.pass_stones);
+    assert!(!japanese.button);
+}
@@ -0,0 +1,219 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+use go::Player;
+
+#[cfg(test)]
+mod test;
+
+/// How much thinking time the players get
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TimeControl {
+    /// No time limit at all
+    Unlimited,
+    /// A fixed amount of time for the whole game
+    Absolute { main: Duration },
+    /// Main time followed by periods of fixed length
+    ///
+    /// A move made within a period keeps it, every overrun period is lost.
+    ByoYomi {
+        main: Duration,
+        period: Duration,
+        periods: u32,
+    },
+    /// Main time followed by periods in which a number of stones is due
+    Canadian {
+        main: Duration,
+        period: Duration,
+        stones: u32,
+    },
+    /// Main time that grows by an increment after every move
+    Fischer {
+        main: Duration,
+        increment: Duration,
+        max: Option<Duration>,
+    },
+}
+
+/// The time one player has left
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerClock {
+    /// The main time left
+    pub main: Duration,
+    /// The time left in the current overtime period
+    pub period: Duration,
+    /// The byo-yomi periods left
+    pub periods: u32,
+    /// The stones left to play in the current canadian period
+    pub stones: u32,
+    /// Whether the player ran out of time
+    pub expired: bool,
+}
+
+/// The clocks of both players
+///
+/// Charges the time spent by the players according to the time control.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Clock {
+    control: TimeControl,
+    black: PlayerClock,
+    white: PlayerClock,
+}
+
+impl PlayerClock {
+    /// The clock of a player at the start of the game
+    fn new(control: &TimeControl) -> PlayerClock {
+        let mut clock = PlayerClock {
+            main: Duration::from_secs(0),
+            period: Duration::from_secs(0),
+            periods: 0,
+            stones: 0,
+            expired: false,
+        };
+
+        match *control {
+            TimeControl::Unlimited => {}
+            TimeControl::Absolute { main } |
+            TimeControl::Fischer { main, .. } => clock.main = main,
+            TimeControl::ByoYomi { main, period, periods } => {
+                clock.main = main;
+                clock.period = period;
+                clock.periods = periods;
+            }
+            TimeControl::Canadian { main, period, stones } => {
+                clock.main = main;
+                clock.period = period;
+                clock.stones = stones;
+            }
+        }
+
+        clock
+    }
+
+    /// Charges the elapsed time and returns whether the clock expired
+    fn spend(&mut self, control: &TimeControl, elapsed: Duration) -> bool {
+        if let TimeControl::Unlimited = *control {
+            return false;
+        }
+
+        // main time is used up first
+        let overtime = match elapsed.checked_sub(self.main) {
+            Some(overtime) => {
+                self.main = Duration::from_secs(0);
+                overtime
+            }
+            None => {
+                self.main -= elapsed;
+                Duration::from_secs(0)
+            }
+        };
+
+        match *control {
+            TimeControl::Unlimited => {}
+            TimeControl::Absolute { .. } => self.expired = overtime > Duration::from_secs(0),
+            TimeControl::Fischer { increment, max, .. } => {
+                if overtime > Duration::from_secs(0) {
+                    self.expired = true;
+                } else {
+                    self.main += increment;
+                    if let Some(max) = max {
+                        self.main = self.main.min(max);
+                    }
+                }
+            }
+            TimeControl::ByoYomi { period, .. } => {
+                if overtime > Duration::from_secs(0) {
+                    let lost = overtime.as_nanos()
+                        .checked_div(period.as_nanos())
+                        .unwrap_or(u128::MAX);
+                    if lost >= u128::from(self.periods) {
+                        self.periods = 0;
+                        self.expired = true;
+                    } else {
+                        self.periods -= lost as u32;
+                    }
+                }
+            }
+            TimeControl::Canadian { period, stones, .. } => {
+                if overtime > self.period {
+                    self.expired = true;
+                } else if self.main == Duration::from_secs(0) {
+                    self.period -= overtime;
+                    self.stones = self.stones.saturating_sub(1);
+                    if self.stones == 0 {
+                        self.period = period;
+                        self.stones = stones;
+                    }
+                }
+            }
+        }
+
+        self.expired
+    }
+}
+
+impl Clock {
+    /// Creates the clocks for a game under the given time control
+    pub fn new(control: TimeControl) -> Clock {
+        Clock {
+            control,
+            black: PlayerClock::new(&control),
+            white: PlayerClock::new(&control),
+        }
+    }
+
+    /// Returns the time control
+    pub fn control(&self) -> &TimeControl {
+        &self.control
+    }
+
+    /// Returns the clock of the given player
+    pub fn player(&self, player: &Player) -> &PlayerClock {
+        match *player {
+            Player::Black => &self.black,
+            Player::White => &self.white,
+        }
+    }
+
+    /// Charges the time the player spent on a move
+    ///
+    /// Returns whether the player ran out of time. Time spent after
+    /// the clock expired is ignored.
+    pub fn spend(&mut self, player: &Player, elapsed: Duration) -> bool {
+        let control = self.control;
+        let clock = match *player {
+            Player::Black => &mut self.black,
+            Player::White => &mut self.white,
+        };
+
+        clock.expired || clock.spend(&control, elapsed)
+    }
+
+    /// Returns the time the player has left, None if unlimited
+    ///
+    /// Includes the overtime, all remaining byo-yomi periods count fully.
+    pub fn remaining(&self, player: &Player) -> Option<Duration> {
+        let clock = self.player(player);
+        if clock.expired {
+            return Some(Duration::from_secs(0));
+        }
+
+        match self.control {
+            TimeControl::Unlimited => None,
+            TimeControl::Absolute { .. } |
+            TimeControl::Fischer { .. } => Some(clock.main),
+            TimeControl::ByoYomi { period, .. } => Some(clock.main + period * clock.periods),
+            TimeControl::Canadian { .. } => Some(clock.main + clock.period),
+        }
+    }
+
+    /// Checks if the player ran out of time
+    pub fn is_expired(&self, player: &Player) -> bool {
+        self.player(player).expired
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use go::Player;
+use time::{Clock, TimeControl};
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+#[test]
+fn unlimited() {
+    let mut clock = Clock::new(TimeControl::Unlimited);
+
+    assert!(!clock.spend(&Player::Black, secs(100_000)));
+    assert!(!clock.is_expired(&Player::Black));
+    assert!(clock.remaining(&Player::Black).is_none());
+}
+
+#[test]
+fn absolute() {
+    let mut clock = Clock::new(TimeControl::Absolute { main: secs(60) });
+
+    assert!(!clock.spend(&Player::Black, secs(60)));
+    assert!(clock.player(&Player::Black).main == secs(0));
+    assert!(clock.player(&Player::White).main == secs(60));
+
+    assert!(clock.spend(&Player::Black, secs(1)));
+    assert!(clock.is_expired(&Player::Black));
+    assert!(!clock.is_expired(&Player::White));
+}
+
+#[test]
+fn byo_yomi() {
+    let mut clock = Clock::new(TimeControl::ByoYomi {
+        main: secs(10),
+        period: secs(30),
+        periods: 3,
+    });
+
+    // a move within the period keeps all periods
+    assert!(!clock.spend(&Player::Black, secs(39)));
+    assert!(clock.player(&Player::Black).periods == 3);
+
+    assert!(!clock.spend(&Player::Black, secs(65)));
+    assert!(clock.player(&Player::Black).periods == 1);
+
+    assert!(clock.remaining(&Player::Black) == Some(secs(30)));
+    assert!(clock.remaining(&Player::White) == Some(secs(100)));
+
+    assert!(clock.spend(&Player::Black, secs(30)));
+    assert!(clock.player(&Player::Black).periods == 0);
+    assert!(clock.remaining(&Player::Black) == Some(secs(0)));
+}
+
+#[test]
+fn canadian() {
+    let mut clock = Clock::new(TimeControl::Canadian {
+        main: secs(0),
+        period: secs(60),
+        stones: 2,
+    });
+
+    assert!(!clock.spend(&Player::White, secs(40)));
+    assert!(clock.player(&Player::White).period == secs(20));
+    assert!(clock.player(&Player::White).stones == 1);
+
+    // all stones played, a new period starts
+    assert!(!clock.spend(&Player::White, secs(20)));
+    assert!(clock.player(&Player::White).period == secs(60));
+    assert!(clock.player(&Player::White).stones == 2);
+
+    assert!(clock.spend(&Player::White, secs(61)));
+}
+
+#[test]
+fn fischer() {
+    let mut clock = Clock::new(TimeControl::Fischer {
+        main: secs(60),
+        increment: secs(10),
+        max: Some(secs(65)),
+    });
+
+    assert!(!clock.spend(&Player::Black, secs(20)));
+    assert!(clock.player(&Player::Black).main == secs(50));
+
+    // the increment is capped
+    assert!(!clock.spend(&Player::Black, secs(0)));
+    assert!(!clock.spend(&Player::Black, secs(0)));
+    assert!(clock.player(&Player::Black).main == secs(65));
+
+    assert!(clock.spend(&Player::Black, secs(66)));
+    // time spent after expiry is ignored
+    assert!(clock.spend(&Player::Black, secs(0)));
+}
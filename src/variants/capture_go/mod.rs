@@ -0,0 +1,118 @@
+use engine;
+use go::{Board, Player, Stone};
+
+#[cfg(test)]
+mod test;
+
+/// The state of a game of capture go (atari go)
+///
+/// The player who captures first wins. Suicide is forbidden as in
+/// regular go, ko can not arise since the first capture ends the game.
+#[derive(Clone)]
+pub struct GameState<TBoard>
+    where TBoard: Board
+{
+    board: TBoard,
+    ply: u32,
+    winner: Option<Player>,
+}
+
+impl<TBoard> engine::GameState for GameState<TBoard>
+    where TBoard: Board
+{
+    fn new() -> Self {
+        GameState {
+            board: TBoard::new(),
+            ply: 0,
+            winner: None,
+        }
+    }
+}
+
+impl<TBoard> GameState<TBoard>
+    where TBoard: Board
+{
+    /// Returns the board
+    pub fn board(&self) -> &TBoard {
+        &self.board
+    }
+
+    /// Returns the number of plies made so far
+    pub fn ply(&self) -> u32 {
+        self.ply
+    }
+
+    /// Returns the player to move, black moves first
+    pub fn current_player(&self) -> Player {
+        if self.ply.is_multiple_of(2) { Player::Black } else { Player::White }
+    }
+
+    /// Returns the winner, None while the game is running
+    pub fn winner(&self) -> Option<Player> {
+        self.winner
+    }
+
+    /// Checks if the game is over
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some()
+    }
+}
+
+/// An action of a game of capture go
+#[derive(Clone, PartialEq, Debug)]
+pub enum Action<TBoard>
+    where TBoard: Board
+{
+    /// The given player plays at the given position
+    ///
+    /// Allowed on the player's turn on empty intersections, unless the
+    /// play is suicide. Capturing stones wins the game.
+    Play {
+        player: Player,
+        at: TBoard::Position,
+    },
+
+    /// The given player resigns
+    ///
+    /// Allowed at any time before the game ended.
+    Resign { player: Player },
+}
+
+impl<TBoard> engine::Action for Action<TBoard>
+    where TBoard: Board
+{
+    type GameState = GameState<TBoard>;
+    type Reason = ();
+    type Position = TBoard::Position;
+
+    fn check(&self, state: &Self::GameState) -> Result<(), ()> {
+        if state.is_over() {
+            return Err(());
+        }
+
+        let applicable = match *self {
+            Action::Play { ref player, ref at } => {
+                *player == state.current_player() && state.board.on_board(at) &&
+                state.board.at(at) == Stone::Empty &&
+                !state.board.would_be_suicide(at, player)
+            }
+            Action::Resign { .. } => true,
+        };
+
+        if applicable { Ok(()) } else { Err(()) }
+    }
+
+    fn execute(&self, state: &mut Self::GameState) {
+        match *self {
+            Action::Play { ref player, ref at } => {
+                if let Ok(info) = state.board.play(player, at) {
+                    if !info.captured.is_empty() {
+                        state.winner = Some(*player);
+                    }
+                }
+                state.ply += 1;
+            }
+            Action::Resign { player } => state.winner = Some(player.other()),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use engine::{AsPath, Game, NodeId, Path};
+use go::Player;
+use go::board::{Position, SquareBoard};
+use variants::capture_go::Action;
+
+type CaptureGame = Game<Action<SquareBoard<9>>>;
+
+fn play<P>(game: &mut CaptureGame, cursor: &P, player: Player, x: usize, y: usize) -> Option<NodeId>
+    where P: AsPath
+{
+    game.insert(cursor,
+                Action::Play {
+                    player,
+                    at: Position { x, y },
+                })
+}
+
+#[test]
+fn first_capture_wins() {
+    let mut game = CaptureGame::new();
+    let mut cursor = Path::Root;
+
+    let moves = [(0, 1), (0, 0), (2, 2)];
+    for (ply, &(x, y)) in moves.iter().enumerate() {
+        let player = if ply % 2 == 0 { Player::Black } else { Player::White };
+        cursor = play(&mut game, &cursor, player, x, y).unwrap().into();
+    }
+    assert!(game.get_state(&cursor).unwrap().winner().is_none());
+
+    // white's corner stone is captured
+    cursor = play(&mut game, &cursor, Player::White, 5, 5).unwrap().into();
+    cursor = play(&mut game, &cursor, Player::Black, 1, 0).unwrap().into();
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.winner() == Some(Player::Black));
+    assert!(state.is_over());
+    assert!(play(&mut game, &cursor, Player::White, 6, 6).is_none());
+}
+
+#[test]
+fn illegal_plays() {
+    let mut game = CaptureGame::new();
+    let mut cursor = play(&mut game, &Path::Root, Player::Black, 1, 0).unwrap();
+
+    assert!(play(&mut game, &cursor, Player::Black, 2, 0).is_none());
+    assert!(play(&mut game, &cursor, Player::White, 1, 0).is_none());
+    assert!(play(&mut game, &cursor, Player::White, 9, 0).is_none());
+
+    cursor = play(&mut game, &cursor, Player::White, 5, 5).unwrap();
+    cursor = play(&mut game, &cursor, Player::Black, 0, 1).unwrap();
+
+    // the corner is suicide for white
+    assert!(play(&mut game, &cursor, Player::White, 0, 0).is_none());
+
+    cursor = game.insert(&cursor, Action::Resign { player: Player::White }).unwrap();
+    assert!(game.get_state(&cursor).unwrap().winner() == Some(Player::Black));
+}
@@ -0,0 +1,2 @@
+pub mod capture_go;
+pub mod multi_color;
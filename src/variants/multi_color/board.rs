@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+
+use go::{Color, PlayError};
+use go::board::{Direction, Position};
+use go::group::{captured_by, flood_fill};
+
+/// A square board holding stones of any number of colors
+///
+/// Every other color is an opponent: a play captures all neighboring
+/// groups of other colors it takes the last liberty from.
+///
+/// `Stone`, `Group` and `Board` stay two-colored, this board holds
+/// `Color`s instead and finds groups and captures with the generic
+/// `go::group::flood_fill` and `go::group::captured_by`.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+pub struct ColorBoard<const N: usize> {
+    state: [[Option<Color>; N]; N],
+}
+
+impl<const N: usize> Default for ColorBoard<N> {
+    fn default() -> Self {
+        ColorBoard::new()
+    }
+}
+
+impl<const N: usize> ColorBoard<N> {
+    /// Creates an empty board
+    pub fn new() -> Self {
+        ColorBoard { state: [[None; N]; N] }
+    }
+
+    /// Checks if the position is on the board
+    pub fn on_board(&self, position: &Position) -> bool {
+        position.x < N && position.y < N
+    }
+
+    /// Returns the color of the stone at the position, None if empty
+    pub fn at(&self, position: &Position) -> Option<Color> {
+        self.state[position.y][position.x]
+    }
+
+    /// Sets or removes a stone
+    pub fn set(&mut self, position: &Position, color: Option<Color>) {
+        self.state[position.y][position.x] = color;
+    }
+
+    /// Returns the neighbors of the position
+    pub fn neighbors(&self, position: &Position) -> Vec<Position> {
+        Direction::ALL
+            .iter()
+            .filter_map(|&direction| position.offset(direction))
+            .filter(|neighbor| self.on_board(neighbor))
+            .collect()
+    }
+
+    /// Returns the connected intersections of the same content and their
+    /// bordering intersections
+    ///
+    /// For stones the border holds liberties and stones of other colors,
+    /// for empty regions it holds the surrounding stones.
+    pub fn region(&self, position: &Position) -> (HashSet<Position>, HashSet<Position>) {
+        let content = self.at(position);
+        let members = flood_fill(position,
+                                 |p, f| self.neighbors(p).into_iter().for_each(f),
+                                 |p| self.at(p) == content);
+        let border = members.iter()
+            .flat_map(|member| self.neighbors(member))
+            .filter(|neighbor| self.at(neighbor) != content)
+            .collect();
+
+        (members, border)
+    }
+
+    /// Returns the liberties of the group at the position
+    pub fn liberties(&self, position: &Position) -> HashSet<Position> {
+        let (_, border) = self.region(position);
+        border.into_iter().filter(|p| self.at(p).is_none()).collect()
+    }
+
+    /// Plays a stone of the given color at the given position
+    ///
+    /// Removes the groups of all other colors left without liberties
+    /// and returns their stones in reading order. A play capturing nothing and leaving
+    /// its own group without liberties is suicide.
+    pub fn play(&mut self, color: Color, position: &Position) -> Result<Vec<Position>, PlayError> {
+        if !self.on_board(position) {
+            return Err(PlayError::OffBoard);
+        }
+        if self.at(position).is_some() {
+            return Err(PlayError::Occupied);
+        }
+
+        let captured = captured_by(position,
+                                   &color,
+                                   |p, f| self.neighbors(p).into_iter().for_each(f),
+                                   |p| self.at(p));
+
+        let mut board = self.clone();
+        board.set(position, Some(color));
+        for stone in &captured {
+            board.set(stone, None);
+        }
+
+        if board.liberties(position).is_empty() {
+            return Err(PlayError::Suicide);
+        }
+
+        let mut captured: Vec<_> = captured.into_iter().collect();
+        captured.sort();
+
+        *self = board;
+        Ok(captured)
+    }
+
+    /// Returns the area of each of the given number of colors
+    ///
+    /// Counts the stones of a color and the empty regions bordered by
+    /// that color only.
+    pub fn area_scoring(&self, colors: u8) -> Vec<usize> {
+        let mut area = vec![0; colors as usize];
+        let mut counted = HashSet::new();
+
+        for y in 0..N {
+            for x in 0..N {
+                let position = Position { x, y };
+                match self.at(&position) {
+                    Some(Color(color)) => {
+                        if let Some(points) = area.get_mut(color as usize) {
+                            *points += 1;
+                        }
+                    }
+                    None if !counted.contains(&position) => {
+                        let (region, border) = self.region(&position);
+                        let owners: HashSet<_> = border.iter().filter_map(|p| self.at(p)).collect();
+                        if owners.len() == 1 {
+                            let Color(owner) = *owners.iter().next().unwrap();
+                            if let Some(points) = area.get_mut(owner as usize) {
+                                *points += region.len();
+                            }
+                        }
+                        counted.extend(region);
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        area
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+
+use engine;
+use go::Color;
+use go::board::Position;
+
+pub mod board;
+
+pub use self::board::ColorBoard;
+
+#[cfg(test)]
+mod test;
+
+/// The state of a go game with three or more players
+///
+/// The colors move in round-robin order starting with black. A board
+/// position may never repeat (positional superko) and the game ends
+/// once every color passed in a row. Scored by area.
+#[derive(Clone)]
+pub struct GameState<const N: usize> {
+    board: ColorBoard<N>,
+    colors: u8,
+    ply: u32,
+    to_move: Color,
+    passes: u8,
+    positions: HashSet<ColorBoard<N>>,
+    prisoners: Vec<usize>,
+}
+
+impl<const N: usize> engine::GameState for GameState<N> {
+    fn new() -> Self {
+        GameState {
+            board: ColorBoard::new(),
+            colors: 3,
+            ply: 0,
+            to_move: Color::BLACK,
+            passes: 0,
+            positions: HashSet::new(),
+            prisoners: vec![0; 3],
+        }
+    }
+}
+
+impl<const N: usize> GameState<N> {
+    /// Returns the board
+    pub fn board(&self) -> &ColorBoard<N> {
+        &self.board
+    }
+
+    /// Returns the number of colors taking part
+    pub fn colors(&self) -> u8 {
+        self.colors
+    }
+
+    /// Returns the number of plies made so far
+    pub fn ply(&self) -> u32 {
+        self.ply
+    }
+
+    /// Returns the color to move
+    pub fn current_color(&self) -> Color {
+        self.to_move
+    }
+
+    /// Returns the number of stones the color captured
+    pub fn prisoners(&self, color: &Color) -> usize {
+        self.prisoners.get(color.0 as usize).cloned().unwrap_or(0)
+    }
+
+    /// Checks if all colors passed in a row
+    pub fn is_over(&self) -> bool {
+        self.passes >= self.colors
+    }
+
+    /// Returns the area of every color, indexed by the color number
+    pub fn area(&self) -> Vec<usize> {
+        self.board.area_scoring(self.colors)
+    }
+
+    /// Hands the turn to the next color
+    fn next_turn(&mut self) {
+        self.ply += 1;
+        self.to_move = self.to_move.next(self.colors);
+    }
+}
+
+/// An action of a go game with three or more players on an N x N board
+#[derive(Clone, PartialEq, Debug)]
+pub enum Action<const N: usize> {
+    /// Sets the number of colors taking part, at least two
+    ///
+    /// Allowed before the 1st ply, does not count as a ply.
+    SetColors { colors: u8 },
+
+    /// The given color passes
+    Pass { color: Color },
+
+    /// The given color plays at the given position
+    ///
+    /// Captures every neighboring group of another color it takes the
+    /// last liberty from. Suicide and repeating a position is forbidden.
+    Play { color: Color, at: Position },
+}
+
+impl<const N: usize> engine::Action for Action<N> {
+    type GameState = GameState<N>;
+    type Reason = ();
+    type Position = Position;
+
+    fn check(&self, state: &Self::GameState) -> Result<(), ()> {
+        if state.is_over() {
+            return Err(());
+        }
+
+        let applicable = match *self {
+            Action::SetColors { colors } => state.ply == 0 && colors >= 2,
+            Action::Pass { color } => color == state.to_move,
+            Action::Play { color, ref at } => {
+                if color != state.to_move {
+                    return Err(());
+                }
+
+                let mut board = state.board.clone();
+                board.play(color, at).is_ok() && !state.positions.contains(&board)
+            }
+        };
+
+        if applicable { Ok(()) } else { Err(()) }
+    }
+
+    fn execute(&self, state: &mut Self::GameState) {
+        match *self {
+            Action::SetColors { colors } => {
+                state.colors = colors;
+                state.prisoners = vec![0; colors as usize];
+            }
+            Action::Pass { color: _color } => {
+                state.passes += 1;
+                state.next_turn();
+            }
+            Action::Play { color, ref at } => {
+                if state.positions.is_empty() {
+                    state.positions.insert(state.board.clone());
+                }
+                if let Ok(captured) = state.board.play(color, at) {
+                    state.prisoners[color.0 as usize] += captured.len();
+                }
+                state.positions.insert(state.board.clone());
+                state.passes = 0;
+                state.next_turn();
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use engine::{AsPath, Game, NodeId, Path};
+use go::Color;
+use go::board::Position;
+use variants::multi_color::{Action, ColorBoard};
+
+type MultiGame = Game<Action<5>>;
+
+fn play<P>(game: &mut MultiGame, cursor: &P, color: u8, x: usize, y: usize) -> Option<NodeId>
+    where P: AsPath
+{
+    game.insert(cursor,
+                Action::Play {
+                    color: Color(color),
+                    at: Position { x, y },
+                })
+}
+
+#[test]
+fn round_robin() {
+    let mut game = MultiGame::new();
+    let mut cursor = play(&mut game, &Path::Root, 0, 0, 0).unwrap();
+    cursor = play(&mut game, &cursor, 1, 4, 4).unwrap();
+
+    // the third color moves before black again
+    assert!(play(&mut game, &cursor, 0, 2, 2).is_none());
+    cursor = play(&mut game, &cursor, 2, 2, 2).unwrap();
+    assert!(game.get_state(&cursor).unwrap().current_color() == Color::BLACK);
+
+    let mut two = game.insert(&Path::Root, Action::SetColors { colors: 2 }).unwrap();
+    two = play(&mut game, &two, 0, 0, 0).unwrap();
+    two = play(&mut game, &two, 1, 4, 4).unwrap();
+    assert!(game.get_state(&two).unwrap().current_color() == Color::BLACK);
+    assert!(game.insert(&two, Action::SetColors { colors: 4 }).is_none());
+}
+
+#[test]
+fn all_others_are_opponents() {
+    let mut board = ColorBoard::<5>::new();
+    board.set(&Position { x: 0, y: 0 }, Some(Color(2)));
+    board.set(&Position { x: 1, y: 0 }, Some(Color::BLACK));
+
+    // white takes the last liberty of the corner stone
+    let captured = board.play(Color::WHITE, &Position { x: 0, y: 1 }).unwrap();
+    assert_eq!(captured, vec![Position { x: 0, y: 0 }]);
+    assert_eq!(board.at(&Position { x: 0, y: 0 }), None);
+
+    // playing into the corner is suicide for the third color
+    board.set(&Position { x: 0, y: 0 }, None);
+    assert!(board.play(Color(2), &Position { x: 0, y: 0 }).is_err());
+    assert!(board.play(Color::WHITE, &Position { x: 0, y: 0 }).is_ok());
+}
+
+#[test]
+fn passes_end_the_game() {
+    let mut game = MultiGame::new();
+    let mut cursor = play(&mut game, &Path::Root, 0, 1, 1).unwrap();
+    for color in 1..4 {
+        assert!(!game.get_state(&cursor).unwrap().is_over());
+        cursor = game.insert(&cursor, Action::Pass { color: Color(color % 3) }).unwrap();
+    }
+
+    let state = game.get_state(&cursor).unwrap();
+    assert!(state.is_over());
+    assert_eq!(state.area(), vec![25, 0, 0]);
+}
+
+#[test]
+fn area() {
+    let mut board = ColorBoard::<5>::new();
+    for y in 0..5 {
+        board.set(&Position { x: 1, y }, Some(Color(0)));
+        board.set(&Position { x: 3, y }, Some(Color(2)));
+    }
+
+    // the middle column borders two colors
+    assert_eq!(board.area_scoring(3), vec![10, 0, 10]);
+}
+
+#[test]
+fn captures_all_colors_at_once() {
+    let mut board = ColorBoard::<5>::new();
+    board.set(&Position { x: 0, y: 0 }, Some(Color(2)));
+    board.set(&Position { x: 1, y: 0 }, Some(Color::WHITE));
+    board.set(&Position { x: 1, y: 1 }, Some(Color::WHITE));
+    board.set(&Position { x: 2, y: 0 }, Some(Color::BLACK));
+    board.set(&Position { x: 2, y: 1 }, Some(Color::BLACK));
+    board.set(&Position { x: 1, y: 2 }, Some(Color::BLACK));
+
+    // both groups lose their last liberty to the same stone
+    let captured = board.play(Color::BLACK, &Position { x: 0, y: 1 }).unwrap();
+    assert_eq!(captured,
+               vec![Position { x: 0, y: 0 }, Position { x: 1, y: 0 }, Position { x: 1, y: 1 }]);
+    assert_eq!(board.at(&Position { x: 0, y: 0 }), None);
+    assert_eq!(board.at(&Position { x: 1, y: 0 }), None);
+    assert_eq!(board.at(&Position { x: 1, y: 1 }), None);
+}
@@ -0,0 +1,87 @@
+use aga::rules::GameState;
+use aga::GamePhase;
+use go::{Player, Stone, ZobristHash};
+use time::PlayerClock;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod test;
+
+/// What the players of a game are allowed to see
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Visibility {
+    /// All stones look the same, as in one-color go
+    pub one_color: bool,
+    /// Players do not see the clock of their opponent
+    pub hide_opponent_clock: bool,
+}
+
+/// A game state as seen by one player or a spectator
+///
+/// Holds copies of everything the viewer may see, ready to be sent to
+/// a client. Hidden information is replaced or left out.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde",
+           serde(bound(serialize = "TBoard: Serialize, TBoard::Position: Serialize",
+                       deserialize = "TBoard: Deserialize<'de>, \
+                                      TBoard::Position: Deserialize<'de>")))]
+pub struct View<TBoard>
+    where TBoard: ZobristHash
+{
+    /// The board, with all stones black in one-color games
+    pub board: TBoard,
+    /// The number of plies made so far
+    pub ply: u32,
+    /// The player to make the next ply
+    pub to_move: Player,
+    /// The current game phase
+    pub phase: GamePhase,
+    /// The position of the last play
+    pub last_move: Option<TBoard::Position>,
+    /// The prisoners of (black, white)
+    pub prisoners: (usize, usize),
+    /// The clocks of (black, white), None if hidden from the viewer
+    pub clocks: (Option<PlayerClock>, Option<PlayerClock>),
+}
+
+impl<TBoard> View<TBoard>
+    where TBoard: ZobristHash
+{
+    /// Projects the state to what the viewer may see
+    ///
+    /// The viewer is None for spectators, who are shown every clock.
+    pub fn new(state: &GameState<TBoard>,
+               viewer: Option<Player>,
+               visibility: &Visibility)
+               -> View<TBoard> {
+        let mut board = state.board().clone();
+        if visibility.one_color {
+            for position in board.positions() {
+                if board.at(&position) == Stone::White {
+                    board.set(&position, &Stone::Black);
+                }
+            }
+        }
+
+        let clock = |player: Player| {
+            match viewer {
+                Some(viewer) if viewer != player && visibility.hide_opponent_clock => None,
+                _ => Some(*state.clock().player(&player)),
+            }
+        };
+
+        View {
+            board,
+            ply: state.ply(),
+            to_move: state.current_player(),
+            phase: *state.phase(),
+            last_move: state.last_move().cloned(),
+            prisoners: (state.prisoners(&Player::Black), state.prisoners(&Player::White)),
+            clocks: (clock(Player::Black), clock(Player::White)),
+        }
+    }
+}
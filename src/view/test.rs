@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use aga::{Action, Board19x19, Position19x19};
+use engine::{Game, NodeId, Path};
+use go::{Board, Player, Stone};
+use time::TimeControl;
+use view::{View, Visibility};
+
+fn game() -> (Game<Action<Board19x19>>, NodeId) {
+    let mut game = Game::new();
+    let mut cursor = game.insert(&Path::Root,
+                                 Action::SetTimeControl {
+                                     control: TimeControl::Absolute {
+                                         main: Duration::from_secs(60),
+                                     },
+                                 }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::Black,
+                             at: Position19x19 { x: 3, y: 3 },
+                             time_used: None,
+                         }).unwrap();
+    cursor = game.insert(&cursor,
+                         Action::Play {
+                             player: Player::White,
+                             at: Position19x19 { x: 15, y: 15 },
+                             time_used: Some(Duration::from_secs(10)),
+                         }).unwrap();
+    (game, cursor)
+}
+
+#[test]
+fn full_view() {
+    let (game, cursor) = game();
+    let state = game.get_state(&cursor).unwrap();
+    let view = View::new(&state, None, &Visibility::default());
+
+    assert!(view.board == *state.board());
+    assert!(view.ply == 2);
+    assert!(view.to_move == Player::Black);
+    assert!(view.last_move == Some(Position19x19 { x: 15, y: 15 }));
+    assert!(view.clocks.1.unwrap().main == Duration::from_secs(50));
+}
+
+#[test]
+fn hidden_information() {
+    let (game, cursor) = game();
+    let state = game.get_state(&cursor).unwrap();
+    let visibility = Visibility {
+        one_color: true,
+        hide_opponent_clock: true,
+    };
+
+    let view = View::new(&state, Some(Player::Black), &visibility);
+    assert!(view.board.at(&Position19x19 { x: 15, y: 15 }) == Stone::Black);
+    assert!(view.board.at(&Position19x19 { x: 3, y: 3 }) == Stone::Black);
+    assert!(view.clocks.0.is_some());
+    assert!(view.clocks.1.is_none());
+
+    // spectators see both clocks
+    let view = View::new(&state, None, &visibility);
+    assert!(view.clocks.0.is_some() && view.clocks.1.is_some());
+}